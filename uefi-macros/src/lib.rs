@@ -10,8 +10,8 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::{TokenStreamExt, quote, quote_spanned};
 use syn::spanned::Spanned;
 use syn::{
-    Error, Expr, ExprLit, ExprPath, ItemFn, ItemStruct, Lit, Visibility, parse_macro_input,
-    parse_quote, parse_quote_spanned,
+    Data, DeriveInput, Error, Expr, ExprLit, ExprPath, ItemFn, ItemStruct, Lit, Member, Type,
+    Visibility, parenthesized, parse_macro_input, parse_quote, parse_quote_spanned, token,
 };
 
 macro_rules! err {
@@ -218,3 +218,147 @@ pub fn entry(args: TokenStream, input: TokenStream) -> TokenStream {
     };
     result.into()
 }
+
+/// Derive macro for the [`VariableValue`] trait.
+///
+/// The macro can be applied to structs with `#[repr(C)]` or
+/// `#[repr(C, packed)]`, whose fields all implement [`VariableValue`] with a
+/// fixed size. The struct is encoded with the same layout as in memory,
+/// with zeroed padding, and fields of packed structs must be `Copy`.
+///
+/// # Example
+///
+/// ```
+/// use uefi::Guid;
+/// use uefi::runtime::VariableValue;
+///
+/// #[derive(VariableValue)]
+/// #[repr(C, packed)]
+/// struct Config {
+///     enabled: bool,
+///     owner: Guid,
+///     retries: u16,
+/// }
+///
+/// assert_eq!(Config::SIZE, Some(19));
+/// ```
+///
+/// [`VariableValue`]: https://docs.rs/uefi/latest/uefi/runtime/trait.VariableValue.html
+#[proc_macro_derive(VariableValue)]
+pub fn derive_variable_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let Data::Struct(data) = &input.data else {
+        return err!(input.ident, "VariableValue can only be derived for structs").into();
+    };
+    if !input.generics.params.is_empty() {
+        return err!(
+            input.generics.params,
+            "VariableValue can't be derived for generic structs"
+        )
+        .into();
+    }
+
+    // Check that the layout of the struct is well-defined.
+    let mut repr_c = false;
+    let mut packed = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            } else if meta.path.is_ident("packed") {
+                packed = true;
+            }
+            // Skip arguments such as `align(8)` or `packed(2)`.
+            if meta.input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        });
+        if let Err(err) = result {
+            return err.to_compile_error().into();
+        }
+    }
+    if !repr_c {
+        return err!(
+            input.ident,
+            "VariableValue can only be derived for `#[repr(C)]` structs"
+        )
+        .into();
+    }
+
+    let ident = &input.ident;
+    let members: Vec<Member> = data.fields.members().collect();
+    let types: Vec<&Type> = data.fields.iter().map(|field| &field.ty).collect();
+
+    // Fields of packed structs can't be borrowed, so copy them instead.
+    let values = members.iter().map(|member| {
+        if packed {
+            quote!(&{ self.#member })
+        } else {
+            quote!(&self.#member)
+        }
+    });
+
+    // Check at compile time that each field has a fixed size, with the error
+    // pointing at the type of the field.
+    let size_checks = types.iter().map(|ty| {
+        quote_spanned! {ty.span()=>
+            assert!(
+                match <#ty as ::uefi::runtime::VariableValue>::SIZE {
+                    ::core::option::Option::Some(size) => size == ::core::mem::size_of::<#ty>(),
+                    ::core::option::Option::None => false,
+                },
+                "the fields of a VariableValue struct must have a fixed size"
+            );
+        }
+    });
+
+    quote! {
+        const _: () = {
+            #(#size_checks)*
+        };
+
+        impl ::uefi::runtime::VariableValue for #ident {
+            const SIZE: ::core::option::Option<usize> =
+                ::core::option::Option::Some(::core::mem::size_of::<Self>());
+
+            fn encoded_len(&self) -> usize {
+                ::core::mem::size_of::<Self>()
+            }
+
+            fn encode(&self, out: &mut [u8]) {
+                // Zero the padding.
+                out.fill(0);
+                #(
+                    ::uefi::runtime::VariableValue::encode(
+                        #values,
+                        &mut out[::core::mem::offset_of!(Self, #members)..]
+                            [..::core::mem::size_of::<#types>()],
+                    );
+                )*
+            }
+
+            fn decode(data: &[u8]) -> ::core::option::Option<Self> {
+                if data.len() != ::core::mem::size_of::<Self>() {
+                    return ::core::option::Option::None;
+                }
+                ::core::option::Option::Some(Self {
+                    #(
+                        #members: <#types as ::uefi::runtime::VariableValue>::decode(
+                            &data[::core::mem::offset_of!(Self, #members)..]
+                                [..::core::mem::size_of::<#types>()],
+                        )?,
+                    )*
+                })
+            }
+        }
+    }
+    .into()
+}
@@ -0,0 +1,9 @@
+use uefi::runtime::VariableValue;
+
+#[derive(VariableValue)]
+struct Settings {
+    a: u8,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use uefi::runtime::VariableValue;
+
+/// A value without a fixed size.
+struct Name;
+
+impl VariableValue for Name {
+    fn encoded_len(&self) -> usize {
+        0
+    }
+
+    fn encode(&self, _out: &mut [u8]) {}
+
+    fn decode(_data: &[u8]) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+#[derive(VariableValue)]
+#[repr(C)]
+struct Settings {
+    a: u8,
+    name: Name,
+}
+
+fn main() {}
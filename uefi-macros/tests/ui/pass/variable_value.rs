@@ -0,0 +1,27 @@
+use uefi::Guid;
+use uefi::runtime::VariableValue;
+
+#[derive(VariableValue)]
+#[repr(C)]
+struct Named {
+    a: u8,
+    b: u32,
+    c: [u8; 2],
+}
+
+#[derive(VariableValue)]
+#[repr(C, packed)]
+struct Packed {
+    a: u8,
+    guid: Guid,
+}
+
+#[derive(VariableValue)]
+#[repr(C, align(8))]
+struct Tuple(u16, bool);
+
+fn main() {
+    assert_eq!(Named::SIZE, Some(12));
+    assert_eq!(Packed::SIZE, Some(17));
+    assert_eq!(Tuple::SIZE, Some(8));
+}
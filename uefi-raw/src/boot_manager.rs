@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Types used by the boot manager.
+//!
+//! The boot manager is configured with load options stored in `Boot####`,
+//! `Driver####`, `SysPrep####` and `Key####` UEFI variables.
+
+use bitflags::bitflags;
+
+bitflags! {
+    /// Attributes of an `EFI_LOAD_OPTION`.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct LoadOptionAttributes: u32 {
+        /// The load option is active. The boot manager skips inactive
+        /// options.
+        const ACTIVE = 0x0000_0001;
+
+        /// For driver load options, the boot manager reconnects all
+        /// controllers after loading all drivers.
+        const FORCE_RECONNECT = 0x0000_0002;
+
+        /// The load option is not shown in the boot manager menu.
+        const HIDDEN = 0x0000_0008;
+
+        /// Mask of the category bits. A category of zero is a boot option
+        /// that is part of the normal boot processing.
+        const CATEGORY = 0x0000_1f00;
+
+        /// The load option is an application that is only run when
+        /// selected by the user, for example a diagnostic tool.
+        const CATEGORY_APP = 0x0000_0100;
+    }
+}
+
+bitflags! {
+    /// Options of a `EFI_KEY_OPTION`, also known as `EFI_BOOT_KEY_DATA`.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct BootKeyData: u32 {
+        /// Mask of the revision of the key option. Currently zero.
+        const REVISION = 0x0000_00ff;
+
+        /// Either shift key must be pressed.
+        const SHIFT_PRESSED = 0x0000_0100;
+
+        /// Either control key must be pressed.
+        const CONTROL_PRESSED = 0x0000_0200;
+
+        /// Either alt key must be pressed.
+        const ALT_PRESSED = 0x0000_0400;
+
+        /// Either logo key must be pressed.
+        const LOGO_PRESSED = 0x0000_0800;
+
+        /// The menu key must be pressed.
+        const MENU_PRESSED = 0x0000_1000;
+
+        /// The SysReq key must be pressed.
+        const SYS_REQ_PRESSED = 0x0000_2000;
+
+        /// Mask of the number of keys (0 to 3) that follow the key option.
+        const INPUT_KEY_COUNT = 0xc000_0000;
+    }
+}
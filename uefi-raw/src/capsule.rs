@@ -5,7 +5,7 @@
 //! Capsules are used to pass information to the firmware, for example to
 //! trigger a firmware update.
 
-use crate::{Guid, PhysicalAddress};
+use crate::{Guid, PhysicalAddress, guid};
 use bitflags::bitflags;
 
 /// Descriptor that defines a scatter-gather list for passing a set of capsules
@@ -125,3 +125,90 @@ pub struct CapsuleHeader {
     /// Size in bytes of the entire capsule, including the header.
     pub capsule_image_size: u32,
 }
+
+impl CapsuleHeader {
+    /// Capsule GUID of a capsule whose payload starts with a
+    /// [`FirmwareManagementCapsuleHeader`] (`EFI_FIRMWARE_MANAGEMENT_CAPSULE_ID_GUID`).
+    pub const FIRMWARE_MANAGEMENT_CAPSULE_GUID: Guid =
+        guid!("6dcbd5ed-e82d-4c44-bda1-7194199ad92a");
+}
+
+/// Header of the payload of a firmware management capsule
+/// (`EFI_FIRMWARE_MANAGEMENT_CAPSULE_HEADER`).
+///
+/// The header is followed by an array of `embedded_driver_count +
+/// payload_item_count` `u64` offsets, relative to the start of this header.
+/// The embedded drivers come first, followed by the payload items, which
+/// each start with a [`FirmwareManagementCapsuleImageHeader`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct FirmwareManagementCapsuleHeader {
+    /// Version of the header, [`FirmwareManagementCapsuleHeader::INIT_VERSION`].
+    pub version: u32,
+
+    /// Number of embedded drivers, which are loaded before processing the
+    /// payload items.
+    pub embedded_driver_count: u16,
+
+    /// Number of payload items.
+    pub payload_item_count: u16,
+}
+
+impl FirmwareManagementCapsuleHeader {
+    /// The current version of the header.
+    pub const INIT_VERSION: u32 = 1;
+}
+
+bitflags! {
+    /// Capabilities of a firmware image in a firmware management capsule.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct ImageCapsuleSupport: u64 {
+        /// The image starts with an `EFI_FIRMWARE_IMAGE_AUTHENTICATION`.
+        const AUTHENTICATION = 1 << 0;
+
+        /// The image contains a dependency expression.
+        const DEPENDENCY = 1 << 1;
+    }
+}
+
+/// Header of a payload item of a firmware management capsule
+/// (`EFI_FIRMWARE_MANAGEMENT_CAPSULE_IMAGE_HEADER`).
+///
+/// The header is followed by `update_image_size` bytes of the image, and
+/// `update_vendor_code_size` bytes of vendor code.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct FirmwareManagementCapsuleImageHeader {
+    /// Version of the header,
+    /// [`FirmwareManagementCapsuleImageHeader::INIT_VERSION`].
+    pub version: u32,
+
+    /// Identifies the firmware management protocol instance that processes
+    /// the image, by the `ImageTypeId` of its image descriptors.
+    pub update_image_type_id: Guid,
+
+    /// Index of the image to update, passed to `SetImage`. Starts at 1.
+    pub update_image_index: u8,
+
+    /// Reserved, must be zero.
+    pub reserved_bytes: [u8; 3],
+
+    /// Size in bytes of the image.
+    pub update_image_size: u32,
+
+    /// Size in bytes of the vendor code after the image.
+    pub update_vendor_code_size: u32,
+
+    /// The hardware instance to update, or zero to update all instances.
+    /// Added in version 2.
+    pub update_hardware_instance: u64,
+
+    /// Capabilities of the image. Added in version 3.
+    pub image_capsule_support: ImageCapsuleSupport,
+}
+
+impl FirmwareManagementCapsuleImageHeader {
+    /// The current version of the header.
+    pub const INIT_VERSION: u32 = 3;
+}
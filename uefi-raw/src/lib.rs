@@ -25,9 +25,11 @@
 #[macro_use]
 mod enums;
 
+pub mod boot_manager;
 pub mod capsule;
 pub mod firmware_storage;
 pub mod protocol;
+pub mod signature;
 pub mod table;
 pub mod time;
 
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Signature databases used by Secure Boot.
+//!
+//! The `PK`, `KEK`, `db`, `dbx`, `dbt` and `dbr` variables contain a list of
+//! [`SignatureList`]s.
+
+use crate::time::Time;
+use crate::{Guid, guid};
+
+newtype_enum! {
+    /// Type of the signatures in a [`SignatureList`].
+    pub enum SignatureType: Guid => {
+        /// SHA-256 hash of an image. Each signature is 32 bytes.
+        SHA256 = guid!("c1c41626-504c-4092-aca9-41f936934328"),
+
+        /// RSA-2048 public key modulus. Each signature is 256 bytes.
+        RSA2048 = guid!("3c5766e8-269c-4e34-aa14-ed776e85b3b6"),
+
+        /// RSA-2048 signature of a SHA-256 hash. Each signature is 256
+        /// bytes.
+        RSA2048_SHA256 = guid!("e2b36190-879b-4a3d-ad8d-f2e7bba32784"),
+
+        /// SHA-1 hash of an image. Each signature is 20 bytes.
+        SHA1 = guid!("826ca512-cf10-4ac9-b187-be01496631bd"),
+
+        /// RSA-2048 signature of a SHA-1 hash. Each signature is 256 bytes.
+        RSA2048_SHA1 = guid!("67f8444f-8743-48f1-a328-1eaab8736080"),
+
+        /// DER-encoded X.509 certificate. The signature size is the size of
+        /// the certificate.
+        X509 = guid!("a5c059a1-94e4-4aa7-87b5-ab155c2bf072"),
+
+        /// SHA-224 hash of an image. Each signature is 28 bytes.
+        SHA224 = guid!("0b6e5233-a65c-44c9-9407-d9ab83bfc8bd"),
+
+        /// SHA-384 hash of an image. Each signature is 48 bytes.
+        SHA384 = guid!("ff3e5307-9fd0-48c9-85f1-8ad56c701e01"),
+
+        /// SHA-512 hash of an image. Each signature is 64 bytes.
+        SHA512 = guid!("093e0fae-a6c4-4f50-9f1b-d41e2b89c19a"),
+
+        /// SHA-256 hash of the to-be-signed part of an X.509 certificate,
+        /// with the time of revocation. Each signature is a
+        /// [`CertX509Sha256`].
+        X509_SHA256 = guid!("3bd2a492-96c0-4079-b420-fcf98ef103ed"),
+
+        /// SHA-384 hash of the to-be-signed part of an X.509 certificate,
+        /// with the time of revocation.
+        X509_SHA384 = guid!("7076876e-80c2-4ee6-aad2-28b349a6865b"),
+
+        /// SHA-512 hash of the to-be-signed part of an X.509 certificate,
+        /// with the time of revocation.
+        X509_SHA512 = guid!("446dbf63-2502-4cda-bcfa-2465d2b0fe9d"),
+    }
+}
+
+/// Header of a list of signatures of the same type and size
+/// (`EFI_SIGNATURE_LIST`).
+///
+/// The header is followed by `signature_header_size` bytes of header data
+/// defined by the signature type, and then by signatures of
+/// `signature_size` bytes each. Each signature starts with the [`Guid`] of
+/// its owner (`EFI_SIGNATURE_DATA`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct SignatureList {
+    /// Type of the signatures.
+    pub signature_type: SignatureType,
+
+    /// Total size of the list in bytes, including this header.
+    pub signature_list_size: u32,
+
+    /// Size of the header data after this header.
+    pub signature_header_size: u32,
+
+    /// Size of each signature, including the owner [`Guid`].
+    pub signature_size: u32,
+}
+
+/// Signature data of type [`SignatureType::X509_SHA256`]
+/// (`EFI_CERT_X509_SHA256`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C, packed)]
+pub struct CertX509Sha256 {
+    /// SHA-256 hash of the to-be-signed part of the certificate.
+    pub to_be_signed_hash: [u8; 32],
+
+    /// Time of the revocation of the certificate. An all-zero time means
+    /// the certificate is always revoked.
+    pub time_of_revocation: Time,
+}
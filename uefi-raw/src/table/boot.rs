@@ -4,7 +4,7 @@
 
 use crate::protocol::device_path::DevicePathProtocol;
 use crate::table::Header;
-use crate::{Boolean, Char16, Event, Guid, Handle, PhysicalAddress, Status, VirtualAddress};
+use crate::{Boolean, Char16, Event, Guid, Handle, PhysicalAddress, Status, VirtualAddress, guid};
 use bitflags::bitflags;
 use core::ffi::c_void;
 use core::ops::RangeInclusive;
@@ -288,6 +288,35 @@ pub enum InterfaceType: u32 => {
 /// Raw event notification function.
 pub type EventNotifyFn = unsafe extern "efiapi" fn(event: Event, context: *mut c_void);
 
+/// Event group signaled when `ExitBootServices` is called, after
+/// [`EVENT_GROUP_BEFORE_EXIT_BOOT_SERVICES`]. Equivalent to
+/// [`EventType::SIGNAL_EXIT_BOOT_SERVICES`].
+pub const EVENT_GROUP_EXIT_BOOT_SERVICES: Guid = guid!("27abf055-b1b8-4c26-8048-748f37baa2df");
+
+/// Event group signaled when `ExitBootServices` is called, before
+/// [`EVENT_GROUP_EXIT_BOOT_SERVICES`].
+pub const EVENT_GROUP_BEFORE_EXIT_BOOT_SERVICES: Guid =
+    guid!("8be0e274-3970-4b44-80c5-1ab9502f3bfc");
+
+/// Event group signaled when `SetVirtualAddressMap` is called. Equivalent to
+/// [`EventType::SIGNAL_VIRTUAL_ADDRESS_CHANGE`].
+pub const EVENT_GROUP_VIRTUAL_ADDRESS_CHANGE: Guid = guid!("13fa7698-c831-49c7-87ea-8f43fcc25196");
+
+/// Event group signaled when the memory map changes.
+pub const EVENT_GROUP_MEMORY_MAP_CHANGE: Guid = guid!("78bee926-692f-48fd-9edb-01422ef0d7ab");
+
+/// Event group signaled by the boot manager before it attempts to boot a boot
+/// option.
+pub const EVENT_GROUP_READY_TO_BOOT: Guid = guid!("7ce88fb3-4bd7-4679-87a8-a8d8dee50d2b");
+
+/// Event group signaled by the boot manager right after
+/// [`EVENT_GROUP_READY_TO_BOOT`].
+pub const EVENT_GROUP_AFTER_READY_TO_BOOT: Guid = guid!("3a2a00ad-98b9-4cdf-a478-702777f1c10b");
+
+/// Event group signaled when `ResetSystem` is called, before the platform is
+/// reset.
+pub const EVENT_GROUP_RESET_SYSTEM: Guid = guid!("62da6a56-13fb-485a-a8da-a3dd7912cb6b");
+
 bitflags! {
     /// Flags describing the capabilities of a memory range.
     #[repr(transparent)]
@@ -468,6 +497,28 @@ pub struct OpenProtocolInformationEntry {
     pub open_count: u32,
 }
 
+bitflags! {
+    /// Attributes used when opening a protocol with
+    /// [`BootServices::open_protocol`], and reported in
+    /// [`OpenProtocolInformationEntry::attributes`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[repr(transparent)]
+    pub struct OpenProtocolAttributes: u32 {
+        /// Opened by the implementation of `HandleProtocol`.
+        const BY_HANDLE_PROTOCOL = 0x0000_0001;
+        /// Opened by a driver to get a protocol interface, without tracking.
+        const GET_PROTOCOL = 0x0000_0002;
+        /// Only tests whether the protocol is present on the handle.
+        const TEST_PROTOCOL = 0x0000_0004;
+        /// Opened by a bus driver on behalf of a child controller.
+        const BY_CHILD_CONTROLLER = 0x0000_0008;
+        /// Opened by a driver that manages the controller.
+        const BY_DRIVER = 0x0000_0010;
+        /// Opened for exclusive access.
+        const EXCLUSIVE = 0x0000_0020;
+    }
+}
+
 newtype_enum! {
 /// Task priority level.
 ///
@@ -81,6 +81,12 @@ pub struct RuntimeServices {
     ) -> Status,
 }
 
+impl RuntimeServices {
+    /// Flag for the `debug_disposition` parameter of `convert_pointer`. If
+    /// set, a null pointer is left unchanged instead of causing an error.
+    pub const OPTIONAL_POINTER: usize = 0x1;
+}
+
 newtype_enum! {
     #[derive(Default)]
     /// The type of system reset.
@@ -159,6 +165,42 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags of the `OsIndications` and `OsIndicationsSupported` global
+    /// variables, with which the OS requests firmware features and the
+    /// firmware reports the supported ones.
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct OsIndications: u64 {
+        /// Stop in the firmware user interface on the next boot.
+        const BOOT_TO_FW_UI = 0x0001;
+
+        /// Time stamp based revocation with the `dbt` database is
+        /// supported.
+        const TIMESTAMP_REVOCATION = 0x0002;
+
+        /// Process the capsules in the `\EFI\UpdateCapsule` directory of
+        /// the EFI system partition on the next boot.
+        const FILE_CAPSULE_DELIVERY_SUPPORTED = 0x0004;
+
+        /// Firmware management protocol capsules are supported.
+        const FMP_CAPSULE_SUPPORTED = 0x0008;
+
+        /// Capsule results are reported in `Capsule####` variables.
+        const CAPSULE_RESULT_VAR_SUPPORTED = 0x0010;
+
+        /// Start OS-defined recovery on the next boot.
+        const START_OS_RECOVERY = 0x0020;
+
+        /// Start platform-defined recovery on the next boot.
+        const START_PLATFORM_RECOVERY = 0x0040;
+
+        /// Collect the current configuration and refresh the JSON
+        /// configuration data on the next boot.
+        const JSON_CONFIG_DATA_REFRESH = 0x0080;
+    }
+}
+
 newtype_enum! {
     /// Variable vendor GUID. This serves as a namespace for variables to
     /// avoid naming conflicts between vendors. The UEFI specification
@@ -171,3 +213,78 @@ newtype_enum! {
         IMAGE_SECURITY_DATABASE = guid!("d719b2cb-3d3a-4596-a3bc-dad00e67656f"),
     }
 }
+
+newtype_enum! {
+    /// Type of the certificate in a [`WinCertificate`].
+    pub enum WinCertificateType: u16 => {
+        /// PKCS#7 `SignedData` structure.
+        PKCS_SIGNED_DATA = 0x0002,
+
+        /// PKCS#1 v1.5 signature, in a `WIN_CERTIFICATE_EFI_PKCS1_15`.
+        EFI_PKCS115 = 0x0ef0,
+
+        /// Certificate whose type is identified by a [`Guid`], in a
+        /// [`WinCertificateUefiGuid`].
+        EFI_GUID = 0x0ef1,
+    }
+}
+
+/// Header of a certificate (`WIN_CERTIFICATE`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct WinCertificate {
+    /// Size of the certificate in bytes, including this header.
+    pub length: u32,
+
+    /// Revision of the certificate structure, [`WinCertificate::REVISION`].
+    pub revision: u16,
+
+    /// Type of the certificate.
+    pub certificate_type: WinCertificateType,
+}
+
+impl WinCertificate {
+    /// The current revision of the certificate structure.
+    pub const REVISION: u16 = 0x0200;
+}
+
+/// Certificate whose type is identified by a [`Guid`]
+/// (`WIN_CERTIFICATE_UEFI_GUID`).
+///
+/// The header is followed by the certificate data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct WinCertificateUefiGuid {
+    /// Header, with a certificate type of [`WinCertificateType::EFI_GUID`].
+    pub header: WinCertificate,
+
+    /// Type of the certificate data.
+    pub cert_type: Guid,
+}
+
+impl WinCertificateUefiGuid {
+    /// Certificate data that is a DER-encoded PKCS#7 `SignedData` structure.
+    pub const CERT_TYPE_PKCS7: Guid = guid!("4aafd29d-68df-49ee-8aa9-347d375665a7");
+
+    /// Certificate data that is an RSA-2048 public key followed by an
+    /// RSA-2048 signature of a SHA-256 hash.
+    pub const CERT_TYPE_RSA2048_SHA256: Guid = guid!("a7717414-c616-4977-9420-844712a735bf");
+}
+
+/// Header of the data of a variable written with
+/// [`VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS`]
+/// (`EFI_VARIABLE_AUTHENTICATION_2`).
+///
+/// The header is followed by the certificate data of `auth_info`, and then
+/// by the variable data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct VariableAuthentication2 {
+    /// Time of the write. Only the date and time fields are used, the
+    /// other fields must be zero.
+    pub time_stamp: Time,
+
+    /// Signature of the write, with a certificate type of
+    /// [`WinCertificateUefiGuid::CERT_TYPE_PKCS7`].
+    pub auth_info: WinCertificateUefiGuid,
+}
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use uefi::acpi::{AcpiError, AcpiTables, Bgrt, Fadt, Hpet, Madt, MadtEntry, Mcfg, Spcr};
+
+pub fn test() {
+    info!("Testing ACPI tables");
+
+    let tables = AcpiTables::from_config_table().expect("failed to find the ACPI tables");
+    info!("RSDP: {:?}", tables.rsdp());
+    info!("Root table: {:?}", tables.root());
+    assert!(tables.rsdp().revision() >= 2);
+
+    // All tables listed in the XSDT are valid.
+    for table in &tables {
+        let table = table.unwrap();
+        info!("Found {} ({} bytes)", table.signature(), table.length());
+    }
+
+    let fadt = tables.get::<Fadt>().unwrap();
+    info!("FADT flags: {:?}", fadt.flags());
+    let dsdt = tables.dsdt().unwrap();
+    info!("DSDT is {} bytes", dsdt.length());
+
+    // Every platform supported by the test runner has at least one CPU.
+    let madt = tables.get::<Madt>().unwrap();
+    let cpus = madt
+        .entries()
+        .filter(|entry| {
+            matches!(
+                entry,
+                MadtEntry::LocalApic(_)
+                    | MadtEntry::LocalX2Apic(_)
+                    | MadtEntry::GicSystemInterface(_)
+            )
+        })
+        .count();
+    info!("MADT lists {cpus} CPUs");
+    assert!(cpus >= 1);
+
+    // The other tables depend on the machine.
+    match tables.get::<Mcfg>() {
+        Ok(mcfg) => {
+            for entry in mcfg.entries() {
+                info!("ECAM region: {entry:?}");
+            }
+        }
+        Err(err) => assert_eq!(err, AcpiError::NotFound),
+    }
+    match tables.get::<Hpet>() {
+        Ok(hpet) => info!("HPET at {:?}", hpet.base_address()),
+        Err(err) => assert_eq!(err, AcpiError::NotFound),
+    }
+    match tables.get::<Spcr>() {
+        Ok(spcr) => info!("SPCR console at {:?}", spcr.base_address()),
+        Err(err) => assert_eq!(err, AcpiError::NotFound),
+    }
+    match tables.get::<Bgrt>() {
+        Ok(bgrt) => info!("BGRT image at {:#x}", bgrt.image_address()),
+        Err(err) => assert_eq!(err, AcpiError::NotFound),
+    }
+}
@@ -4,6 +4,7 @@ pub fn test() {
     info!("Testing memory functions");
 
     bootservices::allocate_pages();
+    bootservices::owned_pages();
     bootservices::allocate_pool();
     bootservices::memory_map();
 
@@ -14,9 +15,11 @@ pub fn test() {
 /// Tests that directly use UEFI boot services to allocate memory.
 mod bootservices {
     use alloc::vec::Vec;
+    use core::mem::MaybeUninit;
     use uefi::boot;
     use uefi::boot::AllocateType;
     use uefi::mem::memory_map::{MemoryMap, MemoryMapMut};
+    use uefi::mem::{PageBox, Pages};
     use uefi_raw::table::boot::MemoryType;
 
     /// Tests the `allocate_pages` boot service.
@@ -37,6 +40,34 @@ mod bootservices {
         unsafe { boot::free_pages(ptr, num_pages) }.unwrap();
     }
 
+    /// Tests `Pages` and `PageBox`.
+    pub fn owned_pages() {
+        let mut pages = Pages::allocate(2, MemoryType::LOADER_DATA).unwrap();
+        assert_eq!(pages.count(), 2);
+        assert_eq!(pages.size(), 2 * 4096);
+        assert_eq!(pages.phys_addr() % 4096, 0);
+        pages.as_uninit_slice_mut().fill(MaybeUninit::new(0xff));
+
+        // Allocate pages at the address that was just freed.
+        let addr = pages.phys_addr();
+        drop(pages);
+        let pages = Pages::allocate_at(addr, 1, MemoryType::LOADER_DATA).unwrap();
+        assert_eq!(pages.phys_addr(), addr);
+
+        // Allocate below 4 GiB, and leak and reclaim the pages.
+        let pages = Pages::allocate_below(0xffff_ffff, 1, MemoryType::LOADER_DATA).unwrap();
+        assert!(pages.phys_addr() + 4096 <= 0x1_0000_0000);
+        let ptr = pages.leak();
+        drop(unsafe { Pages::from_raw(ptr, 1, MemoryType::LOADER_DATA) });
+
+        let mut value = PageBox::new(MemoryType::LOADER_DATA, [1u32; 2048]).unwrap();
+        assert_eq!(PageBox::pages(&value).count(), 2);
+        value[2047] = 2;
+        let value = PageBox::into_inner(value);
+        assert_eq!(value[..2], [1, 1]);
+        assert_eq!(value[2047], 2);
+    }
+
     /// Tests the `allocate_pool` boot service.
     pub fn allocate_pool() {
         let ptr = boot::allocate_pool(MemoryType::LOADER_DATA, 10).unwrap();
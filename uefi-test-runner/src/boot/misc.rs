@@ -1,23 +1,34 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use alloc::string::ToString;
+use alloc::sync::Arc;
 use core::ffi::c_void;
 use core::ptr::{self, NonNull};
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::time::Duration;
 
 use uefi::boot::{
-    EventType, OpenProtocolAttributes, OpenProtocolParams, SearchType, TimerTrigger, Tpl,
+    EventGroup, EventType, OpenProtocolAttributeFlags, OpenProtocolAttributes, OpenProtocolParams,
+    ProtocolInterfaces, RaceOutcome, SearchType, Timer, TimerTrigger, Tpl,
 };
 use uefi::mem::memory_map::MemoryType;
+use uefi::proto::loaded_image::LoadedImage;
 use uefi::proto::unsafe_protocol;
-use uefi::{Event, Guid, Identify, boot, guid, system};
+use uefi::sync::{OnceTpl, TplCell, TplMutex};
+use uefi::{Event, Guid, Identify, Status, boot, guid, system};
 
 pub fn test() {
     test_tpl();
     info!("Testing timer...");
     test_timer();
+    test_timer_wrapper();
     info!("Testing events...");
     test_check_event();
     test_callback_with_ctx();
     test_signal_event();
+    test_create_event_with();
+    test_event_group();
+    test_tpl_sync();
     info!("Testing watchdog...");
     test_watchdog();
     info!("Testing protocol handler services...");
@@ -25,7 +36,10 @@ pub fn test() {
     test_install_protocol_interface();
     test_reinstall_protocol_interface();
     test_uninstall_protocol_interface();
+    test_install_multiple_protocol_interfaces();
     test_install_configuration_table();
+    test_open_protocol_information();
+    test_handle_database();
     info!("Testing crc32...");
     test_calculate_crc32();
 }
@@ -63,6 +77,34 @@ fn test_timer() {
     boot::close_event(timer_event).unwrap();
 }
 
+fn test_timer_wrapper() {
+    // One-shot timers fire once.
+    let mut timer = Timer::one_shot(Duration::from_millis(1)).unwrap();
+    timer.wait().unwrap();
+    assert!(!timer.has_fired().unwrap());
+
+    // Periodic timers keep firing.
+    let mut timer = Timer::periodic(Duration::from_millis(1)).unwrap();
+    for tick in timer.ticks().take(3) {
+        tick.unwrap();
+    }
+    boot::stall(Duration::from_millis(10));
+    assert!(timer.has_fired().unwrap());
+    timer.cancel().unwrap();
+
+    // Race a timer against an event that is already signaled, and one that
+    // never is.
+    let event =
+        unsafe { boot::create_event(EventType::empty(), Tpl::CALLBACK, None, None) }.unwrap();
+    let mut timer = Timer::one_shot(Duration::from_secs(60)).unwrap();
+    boot::signal_event(&event).unwrap();
+    assert_eq!(timer.race(&event).unwrap(), RaceOutcome::Event);
+    timer.set_one_shot(Duration::from_millis(1)).unwrap();
+    assert_eq!(timer.race(&event).unwrap(), RaceOutcome::Timer);
+    assert!(!boot::wait_for_event_with_timeout(&event, Duration::from_millis(1)).unwrap());
+    boot::close_event(event).unwrap();
+}
+
 fn test_callback_with_ctx() {
     let mut data = 123u32;
 
@@ -127,6 +169,103 @@ fn test_signal_event() {
     assert_eq!(data, 456);
 }
 
+fn test_create_event_with() {
+    static COUNT: AtomicU32 = AtomicU32::new(0);
+
+    let data = Arc::new(AtomicU32::new(123));
+    let data_in_callback = data.clone();
+
+    let event = boot::create_event_with(EventType::NOTIFY_SIGNAL, Tpl::CALLBACK, move |_event| {
+        info!("Inside the closure event callback");
+        data_in_callback.store(456, Ordering::Relaxed);
+        COUNT.fetch_add(1, Ordering::Relaxed);
+    })
+    .expect("Failed to create event with closure");
+
+    boot::signal_event(&event).expect("Failed to signal event");
+
+    // Check that `data` was updated inside the closure.
+    assert_eq!(data.load(Ordering::Relaxed), 456);
+    assert_eq!(COUNT.load(Ordering::Relaxed), 1);
+
+    // Dropping the event closes it and frees the closure, releasing its
+    // reference to `data`.
+    drop(event);
+    assert_eq!(Arc::strong_count(&data), 1);
+}
+
+fn test_event_group() {
+    static GROUP: Guid = guid!("2f0ab7a5-0d9f-4f4c-9a43-cf1b1e3be2e9");
+    static COUNT: AtomicU32 = AtomicU32::new(0);
+
+    let events = [(); 2].map(|_| {
+        boot::create_event_group_with(EventGroup::Custom(&GROUP), Tpl::CALLBACK, |_event| {
+            COUNT.fetch_add(1, Ordering::Relaxed);
+        })
+        .expect("Failed to create event in group")
+    });
+
+    // Signaling one event signals the whole group.
+    boot::signal_event(&events[0]).expect("Failed to signal event");
+    assert_eq!(COUNT.load(Ordering::Relaxed), 2);
+
+    unsafe { boot::signal_event_group(EventGroup::Custom(&GROUP)) }
+        .expect("Failed to signal event group");
+    assert_eq!(COUNT.load(Ordering::Relaxed), 4);
+
+    // Closed events are removed from the group.
+    drop(events);
+    unsafe { boot::signal_event_group(EventGroup::Custom(&GROUP)) }
+        .expect("Failed to signal event group");
+    assert_eq!(COUNT.load(Ordering::Relaxed), 4);
+}
+
+fn test_tpl_sync() {
+    info!("Testing TPL synchronization primitives");
+
+    static COUNTER: TplMutex<u32> = unsafe { TplMutex::new(Tpl::CALLBACK, 0) };
+    static LAST: TplCell<u32> = unsafe { TplCell::new(Tpl::CALLBACK, 0) };
+    static ONCE: OnceTpl<u32> = unsafe { OnceTpl::new(Tpl::CALLBACK) };
+
+    extern "efiapi" fn callback(_event: Event, _ctx: Option<NonNull<c_void>>) {
+        let mut counter = COUNTER.try_lock().expect("mutex is locked in callback");
+        *counter += 1;
+        LAST.set(*counter);
+    }
+
+    let event = unsafe {
+        boot::create_event(
+            EventType::NOTIFY_SIGNAL,
+            Tpl::CALLBACK,
+            Some(callback),
+            None,
+        )
+    }
+    .unwrap();
+
+    // The callback runs immediately while the mutex is unlocked.
+    boot::signal_event(&event).unwrap();
+    assert_eq!(*COUNTER.lock(), 1);
+
+    // The callback is deferred while the mutex is locked.
+    {
+        let mut counter = COUNTER.lock();
+        boot::signal_event(&event).unwrap();
+        assert_eq!(*counter, 1);
+        *counter += 10;
+    }
+    assert_eq!(*COUNTER.lock(), 12);
+    assert_eq!(LAST.get(), 12);
+    assert_eq!(LAST.update(|last| last * 2), 24);
+
+    assert_eq!(ONCE.get(), None);
+    assert_eq!(*ONCE.get_or_init(|| 5), 5);
+    assert_eq!(*ONCE.get_or_init(|| 6), 5);
+    assert_eq!(ONCE.set(7), Err(7));
+
+    boot::close_event(event).unwrap();
+}
+
 fn test_watchdog() {
     // There's no way to check the watchdog timer value, so just test setting it.
 
@@ -219,6 +358,68 @@ fn test_uninstall_protocol_interface() {
     }
 }
 
+/// Dummy protocols for the multiple protocol interfaces test.
+#[unsafe_protocol("2c8ea0b3-04c4-4b5b-9d32-3d0c0d1b8f1e")]
+struct TestProtocolA {
+    _data: u32,
+}
+
+#[unsafe_protocol("9a0f54ae-7b49-4bd6-8c4f-6b1f5e3e2a77")]
+struct TestProtocolB {
+    _data: u32,
+}
+
+fn test_install_multiple_protocol_interfaces() {
+    info!("Installing multiple protocol interfaces");
+
+    static A: TestProtocolA = TestProtocolA { _data: 1 };
+    static B: TestProtocolB = TestProtocolB { _data: 2 };
+
+    let interfaces = unsafe {
+        ProtocolInterfaces::new()
+            .with_protocol::<TestProtocolA>(&A)
+            .with_protocol::<TestProtocolB>(&B)
+    };
+    let installed = unsafe { boot::install_multiple_protocol_interfaces(None, &interfaces) }
+        .expect("Failed to install multiple protocol interfaces");
+    let handle = installed.handle();
+
+    let protocols = boot::protocols_per_handle(handle).unwrap();
+    assert!(protocols.contains(&&TestProtocolA::GUID));
+    assert!(protocols.contains(&&TestProtocolB::GUID));
+
+    // Installing a protocol that is already on the handle fails, and rolls
+    // back the other interfaces.
+    let duplicate = unsafe {
+        ProtocolInterfaces::new()
+            .with(TestProtocol::GUID, ptr::null())
+            .with_protocol::<TestProtocolA>(&A)
+    };
+    let err = unsafe { boot::install_multiple_protocol_interfaces(Some(handle), &duplicate) }
+        .unwrap_err();
+    assert_eq!(err.status(), Status::INVALID_PARAMETER);
+    assert!(
+        !boot::protocols_per_handle(handle)
+            .unwrap()
+            .contains(&&TestProtocol::GUID)
+    );
+
+    // Dropping the guard uninstalls all interfaces.
+    drop(installed);
+    assert_eq!(
+        boot::locate_handle_buffer(SearchType::from_proto::<TestProtocolA>())
+            .unwrap_err()
+            .status(),
+        Status::NOT_FOUND
+    );
+    assert_eq!(
+        boot::locate_handle_buffer(SearchType::from_proto::<TestProtocolB>())
+            .unwrap_err()
+            .status(),
+        Status::NOT_FOUND
+    );
+}
+
 fn test_install_configuration_table() {
     // Get the current number of entries.
     let initial_table_count = system::with_config_table(|t| t.len());
@@ -250,6 +451,30 @@ fn test_install_configuration_table() {
     }
 }
 
+fn test_open_protocol_information() {
+    info!("Testing open_protocol_information");
+
+    let image = boot::image_handle();
+    let _loaded_image = boot::open_protocol_exclusive::<LoadedImage>(image).unwrap();
+
+    let info = boot::open_protocol_information(image, &LoadedImage::GUID).unwrap();
+    let entry = info
+        .iter()
+        .find(|entry| entry.agent_handle() == Some(image))
+        .expect("missing open protocol entry for the image");
+    assert!(
+        entry
+            .attributes()
+            .contains(OpenProtocolAttributeFlags::EXCLUSIVE)
+    );
+    assert_eq!(entry.open_count(), 1);
+
+    let all = boot::open_protocol_information_per_handle(image).unwrap();
+    assert!(all.iter().any(|(guid, info)| {
+        *guid == LoadedImage::GUID && info.iter().any(|e| e.agent_handle() == Some(image))
+    }));
+}
+
 fn test_calculate_crc32() {
     let data = "uefi-rs";
 
@@ -257,3 +482,34 @@ fn test_calculate_crc32() {
 
     assert_eq!(crc, 0xcfc96a3e);
 }
+
+fn test_handle_database() {
+    info!("Testing handle_database");
+
+    let db = boot::handle_database().expect("failed to get handle database");
+    assert!(!db.handles.is_empty());
+
+    // The image handle has the loaded image protocol.
+    let image = db
+        .get(boot::image_handle())
+        .expect("image handle not in database");
+    let loaded_image = image
+        .protocol(&LoadedImage::GUID)
+        .expect("loaded image protocol not in database");
+    assert_eq!(loaded_image.name, Some("LoadedImage"));
+    assert_eq!(boot::protocol_name(&LoadedImage::GUID), Some("LoadedImage"));
+
+    // At least one handle has a device path that can be converted to text.
+    assert!(db.handles.iter().any(|info| info.device_path.is_some()));
+
+    // Drivers in OVMF implement the component name protocol.
+    assert!(db.handles.iter().any(|info| info.driver_name.is_some()));
+    assert!(db.handles.iter().any(|info| info.controller_name.is_some()));
+
+    let text = db.to_string();
+    assert!(text.contains("LoadedImage (5b1b31a1-9562-11d2-8e3f-00a0c969723b)"));
+
+    // SAFETY: the handles are from this boot.
+    let parsed = unsafe { boot::HandleDatabase::from_bytes(&db.to_bytes()) };
+    assert_eq!(parsed, Ok(db));
+}
@@ -10,17 +10,21 @@ extern crate alloc;
 
 use alloc::string::ToString;
 use alloc::vec::Vec;
-use uefi::mem::memory_map::MemoryMap;
+use core::sync::atomic::{AtomicBool, Ordering};
+use uefi::boot::{EventGroup, Tpl};
+use uefi::mem::memory_map::{MemoryAttribute, MemoryDescriptor, MemoryMap};
 use uefi::prelude::*;
 use uefi::proto::console::serial::Serial;
 use uefi::proto::device_path::build::{self, DevicePathBuilder};
 use uefi::proto::device_path::messaging::Vendor;
 use uefi::{Result, print, println, system};
 
+mod acpi;
 mod boot;
 mod fs;
 mod proto;
 mod runtime;
+mod task;
 
 #[entry]
 fn efi_main() -> Status {
@@ -47,6 +51,10 @@ fn efi_main() -> Status {
 
     boot::test();
 
+    acpi::test();
+
+    task::test();
+
     // Test all the supported protocols.
     proto::test();
 
@@ -208,9 +216,29 @@ fn shutdown() -> ! {
 
     info!("Testing complete, exiting boot services...");
 
+    // Check that exit hooks and the exit boot services event group are run.
+    static HOOK_RAN: AtomicBool = AtomicBool::new(false);
+    static EVENT_SIGNALED: AtomicBool = AtomicBool::new(false);
+    uefi::boot::register_exit_boot_services_hook(|| {
+        info!("Running exit boot services hook");
+        HOOK_RAN.store(true, Ordering::Relaxed);
+    });
+    let _ = uefi::boot::create_event_group_with(
+        EventGroup::ExitBootServices,
+        Tpl::CALLBACK,
+        |_event| {
+            EVENT_SIGNALED.store(true, Ordering::Relaxed);
+        },
+    )
+    .unwrap()
+    .leak();
+
     // Exit boot services as a proof that it works :)
     let mmap = unsafe { uefi::boot::exit_boot_services(None) };
 
+    assert!(HOOK_RAN.load(Ordering::Relaxed));
+    assert!(EVENT_SIGNALED.load(Ordering::Relaxed));
+
     info!("Memory Map:");
     for desc in mmap.entries() {
         info!(
@@ -222,6 +250,25 @@ fn shutdown() -> ! {
         );
     }
 
+    // Switch the firmware to virtual addressing with an identity mapping,
+    // so that runtime services can still be called without new page tables.
+    let mut virtual_map = [MemoryDescriptor::default(); 64];
+    let virtual_map = unsafe {
+        uefi::runtime::set_virtual_address_map_from(
+            &mmap,
+            uefi::runtime::VirtualMapping::Offset(0),
+            &mut virtual_map,
+        )
+    }
+    .unwrap();
+    assert!(
+        virtual_map
+            .iter()
+            .all(|desc| desc.att.contains(MemoryAttribute::RUNTIME))
+    );
+    assert!(virtual_map.len() < mmap.len());
+    uefi::runtime::get_time().unwrap();
+
     info!("Shutting down...");
 
     #[cfg(target_arch = "x86_64")]
@@ -1,9 +1,16 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use uefi::boot::{self, ScopedProtocol, SearchType};
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use uefi::boot::{self, OpenProtocolAttributes, OpenProtocolParams, ScopedProtocol, SearchType};
 use uefi::prelude::*;
-use uefi::proto::driver::{ComponentName, ComponentName2, LanguageError, LanguageIter};
-use uefi::{CStr16, Result};
+use uefi::proto::device_path::DevicePath;
+use uefi::proto::driver::{
+    ComponentName, ComponentName2, DriverBinding, LanguageError, LanguageIter,
+    install_driver_binding,
+};
+use uefi::proto::unsafe_protocol;
+use uefi::{CStr16, Identify, Result};
 
 #[allow(deprecated)]
 use uefi::proto::driver::ComponentName1;
@@ -144,4 +151,93 @@ pub fn test() {
     test_component_name::<ScopedProtocol<ComponentName1>>("eng");
     test_component_name::<ScopedProtocol<ComponentName2>>("en");
     test_component_name::<ComponentName>("en");
+
+    info!("Running driver binding test");
+    test_driver_binding();
+}
+
+/// Dummy protocol installed on the controller managed by `TestDriver`.
+#[unsafe_protocol("5f1a3c2e-7d4b-4e8a-9c61-2b0f8e7d6a53")]
+struct TestController {
+    _data: u32,
+}
+
+#[derive(Default)]
+struct TestDriver {
+    started: Cell<u32>,
+    stopped: Cell<u32>,
+    opened: RefCell<Vec<ScopedProtocol<TestController>>>,
+}
+
+impl TestDriver {
+    fn params(controller: Handle) -> OpenProtocolParams {
+        OpenProtocolParams {
+            handle: controller,
+            agent: boot::image_handle(),
+            controller: Some(controller),
+        }
+    }
+}
+
+impl DriverBinding for TestDriver {
+    fn supported(&self, controller: Handle, _: Option<&DevicePath>) -> Result {
+        if boot::test_protocol::<TestController>(Self::params(controller))? {
+            Ok(())
+        } else {
+            Err(Status::UNSUPPORTED.into())
+        }
+    }
+
+    fn start(&self, controller: Handle, _: Option<&DevicePath>) -> Result {
+        let protocol = unsafe {
+            boot::open_protocol::<TestController>(
+                Self::params(controller),
+                OpenProtocolAttributes::ByDriver,
+            )
+        }?;
+        self.opened.borrow_mut().push(protocol);
+        self.started.set(self.started.get() + 1);
+        Ok(())
+    }
+
+    fn stop(&self, _controller: Handle, children: &[Handle]) -> Result {
+        assert!(children.is_empty());
+        self.opened.borrow_mut().clear();
+        self.stopped.set(self.stopped.get() + 1);
+        Ok(())
+    }
+}
+
+fn test_driver_binding() {
+    static CONTROLLER: TestController = TestController { _data: 1 };
+    let controller = unsafe {
+        boot::install_protocol_interface(
+            None,
+            &TestController::GUID,
+            (&raw const CONTROLLER).cast(),
+        )
+    }
+    .unwrap();
+
+    let driver = install_driver_binding(TestDriver::default()).unwrap();
+    assert_eq!(driver.handle(), boot::image_handle());
+
+    boot::connect_controller(controller, Some(driver.handle()), None, false).unwrap();
+    boot::disconnect_controller(controller, Some(driver.handle()), None).unwrap();
+    boot::connect_controller(controller, Some(driver.handle()), None, false).unwrap();
+
+    // Uninstalling the driver binding disconnects the controller.
+    let driver = driver.uninstall().unwrap();
+    assert_eq!(driver.started.get(), 2);
+    assert_eq!(driver.stopped.get(), 2);
+    assert!(driver.opened.borrow().is_empty());
+
+    unsafe {
+        boot::uninstall_protocol_interface(
+            controller,
+            &TestController::GUID,
+            (&raw const CONTROLLER).cast(),
+        )
+    }
+    .unwrap();
 }
@@ -22,6 +22,7 @@ pub fn test() {
     network::test();
     pci::test();
     pi::test();
+    producer::test();
     rng::test();
     shell_params::test();
     string::test();
@@ -88,6 +89,7 @@ mod network;
 mod nvme;
 mod pci;
 mod pi;
+mod producer;
 mod rng;
 mod scsi;
 #[cfg(any(
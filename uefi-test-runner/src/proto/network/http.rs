@@ -4,9 +4,10 @@ use alloc::vec::Vec;
 
 use uefi::proto::device_path::DevicePath;
 use uefi::proto::device_path::text::{AllowShortcuts, DisplayOnly};
-use uefi::proto::network::http::{HttpBinding, HttpHelper};
+use uefi::proto::driver::ServiceBinding;
+use uefi::proto::network::http::{Http, HttpBinding, HttpHelper};
 use uefi::proto::network::ip4config2::Ip4Config2;
-use uefi::{Handle, boot};
+use uefi::{Handle, Identify, boot};
 
 use uefi_raw::protocol::network::http::HttpStatusCode;
 
@@ -85,6 +86,22 @@ fn fetch_http(handle: Handle, url: &str) -> Option<Vec<u8>> {
     Some(data)
 }
 
+fn test_service_binding(handle: Handle) {
+    let binding = ServiceBinding::<Http>::new(handle);
+    let child = binding.create().expect("failed to create http child");
+    let child_handle = child.handle();
+    assert_ne!(child_handle, handle);
+    assert!(
+        boot::protocols_per_handle(child_handle)
+            .unwrap()
+            .contains(&&Http::GUID)
+    );
+
+    // Dropping the child destroys the child handle.
+    drop(child);
+    assert!(boot::protocols_per_handle(child_handle).is_err());
+}
+
 pub fn test() {
     info!("Testing ip4 config2 + http protocols");
 
@@ -98,6 +115,9 @@ pub fn test() {
         let mut ip4 = Ip4Config2::new(*h).expect("open ip4 config2 protocol");
         ip4.ifup(true).expect("acquire ipv4 address");
 
+        info!("Testing service binding");
+        test_service_binding(*h);
+
         // hard to find web sites which still allow plain http these days ...
         info!("Testing HTTP");
         fetch_http(*h, "http://example.com/").expect("http request failed");
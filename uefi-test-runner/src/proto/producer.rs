@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use uefi::boot::{self, SearchType};
+use uefi::proto::producer::{InstalledProtocol, ProtocolImpl};
+use uefi::proto::unsafe_protocol;
+use uefi::{Guid, Identify, Status};
+
+pub fn test() {
+    info!("Testing protocol producer");
+
+    let counter = InstalledProtocol::install(None, Counter { count: 0 }).unwrap();
+    let handle = counter.handle();
+
+    {
+        let mut protocol = boot::open_protocol_exclusive::<CounterProtocol>(handle).unwrap();
+        assert_eq!(protocol.increment(), Ok(1));
+        assert_eq!(protocol.increment(), Ok(2));
+
+        // Calls fail while the implementation is borrowed.
+        counter.with(|_| assert_eq!(protocol.increment(), Err(Status::ACCESS_DENIED)));
+    }
+
+    assert_eq!(counter.with(|counter| counter.count), Some(2));
+
+    let counter = counter.uninstall().unwrap();
+    assert_eq!(counter.count, 2);
+    assert_eq!(
+        boot::locate_handle_buffer(SearchType::from_proto::<CounterProtocol>())
+            .unwrap_err()
+            .status(),
+        Status::NOT_FOUND
+    );
+}
+
+#[repr(C)]
+struct RawCounterProtocol {
+    increment: unsafe extern "efiapi" fn(this: *mut Self, count: *mut u32) -> Status,
+}
+
+/// Consumer side of the protocol.
+#[repr(transparent)]
+#[unsafe_protocol("6c2a0f0e-8f6b-4d8e-a3b6-f1c0b7a9d5e2")]
+struct CounterProtocol(RawCounterProtocol);
+
+impl CounterProtocol {
+    fn increment(&mut self) -> Result<u32, Status> {
+        let mut count = 0;
+        let status = unsafe { (self.0.increment)(&mut self.0, &mut count) };
+        if status.is_success() {
+            Ok(count)
+        } else {
+            Err(status)
+        }
+    }
+}
+
+/// Producer side of the protocol.
+struct Counter {
+    count: u32,
+}
+
+unsafe impl ProtocolImpl for Counter {
+    type Interface = RawCounterProtocol;
+    const GUID: Guid = CounterProtocol::GUID;
+
+    fn interface(&self) -> RawCounterProtocol {
+        RawCounterProtocol { increment }
+    }
+}
+
+unsafe extern "efiapi" fn increment(this: *mut RawCounterProtocol, count: *mut u32) -> Status {
+    let r = unsafe {
+        Counter::with_this(this, |counter| {
+            counter.count += 1;
+            counter.count
+        })
+    };
+    match r {
+        Ok(value) => {
+            unsafe { count.write(value) };
+            Status::SUCCESS
+        }
+        Err(err) => err.status(),
+    }
+}
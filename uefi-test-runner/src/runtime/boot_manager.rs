@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use alloc::vec::Vec;
+use uefi::cstr16;
+use uefi::runtime::boot_manager::{self, LoadOption, LoadOptionAttributes, LoadOptionType};
+
+pub fn test() {
+    info!("Testing boot manager variables");
+
+    let order = boot_manager::get_order(LoadOptionType::Boot).unwrap();
+    info!("BootOrder: {order:04x?}");
+    info!(
+        "BootCurrent: {:04x}",
+        boot_manager::get_boot_current().unwrap()
+    );
+    info!("Timeout: {:?}", boot_manager::get_timeout().unwrap());
+
+    // Every boot option in the order should be readable.
+    let numbers = boot_manager::option_numbers(LoadOptionType::Boot).unwrap();
+    for number in &order {
+        if !numbers.contains(number) {
+            continue;
+        }
+        let option = boot_manager::get_load_option(LoadOptionType::Boot, *number).unwrap();
+        info!("Boot{number:04X}: {}", option.description);
+    }
+
+    // Create a copy of the first boot option under a new number, without
+    // adding it to `BootOrder`.
+    let first = boot_manager::get_load_option(LoadOptionType::Boot, order[0]).unwrap();
+    let paths: Vec<_> = first.file_paths().collect();
+    let mut option = LoadOption::new(cstr16!("uefi-rs test"), &paths);
+    option.attributes |= LoadOptionAttributes::HIDDEN;
+    option.optional_data = b"test".to_vec();
+
+    let number = boot_manager::next_free_option_number(LoadOptionType::Boot).unwrap();
+    assert!(!numbers.contains(&number));
+    assert!(!order.contains(&number));
+    boot_manager::set_load_option(LoadOptionType::Boot, number, &option).unwrap();
+    assert_eq!(
+        boot_manager::get_load_option(LoadOptionType::Boot, number).unwrap(),
+        option
+    );
+    assert!(
+        boot_manager::option_numbers(LoadOptionType::Boot)
+            .unwrap()
+            .contains(&number)
+    );
+
+    boot_manager::delete_load_option(LoadOptionType::Boot, number).unwrap();
+    assert!(
+        !boot_manager::option_numbers(LoadOptionType::Boot)
+            .unwrap()
+            .contains(&number)
+    );
+}
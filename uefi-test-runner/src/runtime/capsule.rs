@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use alloc::vec::Vec;
+use uefi::fs::{FileSystem, PathBuf};
+use uefi::runtime::capsule::{self, CapsuleBuilder, FmpCapsule, FmpImage};
+use uefi::runtime::{self, CapsuleFlags, CapsuleHeader, OsIndications, VariableVendor};
+use uefi::{Status, boot, cstr16, guid};
+
+pub fn test() {
+    info!("Testing capsule building");
+
+    let mut fmp = FmpCapsule::default();
+    fmp.images.push(FmpImage::new(
+        guid!("1c6f4b1e-8a36-4b49-9c3f-4d3b0a1e2f10"),
+        1,
+        [0xaa; 5000].to_vec(),
+    ));
+
+    // Split the capsule into small blocks so that the scatter-gather list
+    // needs several tables.
+    let result = CapsuleBuilder::new()
+        .fmp_capsule(CapsuleFlags::PERSIST_ACROSS_RESET, &fmp)
+        .max_block_size(16)
+        .build();
+    match result {
+        Ok(update) => {
+            info!("Capsule info: {:?}", update.info());
+            let headers = update.headers();
+            assert_eq!(headers.len(), 1);
+            assert_eq!(
+                headers[0].capsule_guid,
+                uefi::runtime::CapsuleHeader::FIRMWARE_MANAGEMENT_CAPSULE_GUID
+            );
+            // The first table ends with a continuation to the next table.
+            let last = update.block_descriptors().last().unwrap();
+            assert_eq!(last.length, 0);
+            assert_ne!(last.address, 0);
+            // The update is not submitted, so that the firmware does not
+            // try to apply the capsule on the next boot.
+        }
+        // OVMF does not support capsule updates by default.
+        Err(err) => assert_eq!(err.status(), Status::UNSUPPORTED),
+    }
+}
+
+pub fn test_os_indications() {
+    info!("Testing OsIndications");
+
+    let supported = runtime::os_indications_supported().unwrap();
+    let indications = runtime::os_indications().unwrap();
+    info!("OsIndicationsSupported: {supported:?}, OsIndications: {indications:?}");
+
+    // Rewrite the current value without requesting anything. The variable
+    // is non-volatile, so don't leave it behind if it didn't exist.
+    let name = cstr16!("OsIndications");
+    let existed = runtime::variable_exists(name, &VariableVendor::GLOBAL_VARIABLE).unwrap();
+    let updated = runtime::update_os_indications(|flags| {
+        flags - OsIndications::BOOT_TO_FW_UI - OsIndications::FILE_CAPSULE_DELIVERY_SUPPORTED
+    })
+    .unwrap();
+    assert_eq!(runtime::os_indications().unwrap(), updated);
+    if !existed {
+        runtime::delete_variable(name, &VariableVendor::GLOBAL_VARIABLE).unwrap();
+        assert_eq!(runtime::os_indications().unwrap(), OsIndications::empty());
+    }
+}
+
+pub fn test_capsule_file() {
+    info!("Testing capsule files");
+
+    let esp = boot::get_image_file_system(boot::image_handle()).unwrap();
+    let mut fs = FileSystem::new(esp);
+    let payload: Vec<u8> = (0..100).collect();
+    capsule::write_capsule_file(
+        &mut fs,
+        cstr16!("test.cap"),
+        CapsuleHeader::FIRMWARE_MANAGEMENT_CAPSULE_GUID,
+        CapsuleFlags::PERSIST_ACROSS_RESET,
+        &payload,
+    )
+    .unwrap();
+
+    // The file contains the capsule header followed by the payload.
+    let mut path = PathBuf::from(capsule::UPDATE_CAPSULE_DIRECTORY);
+    path.push(cstr16!("test.cap"));
+    let data = fs.read(&path).unwrap();
+    assert_eq!(data.len(), size_of::<CapsuleHeader>() + payload.len());
+    assert_eq!(data[size_of::<CapsuleHeader>()..], payload);
+
+    // Delivery is not requested, so remove the capsule again.
+    fs.remove_file(&path).unwrap();
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use uefi::Status;
+use uefi::runtime::global_variables::{self, ConsoleVariable};
+
+pub fn test() {
+    info!("Testing global variables");
+    test_languages();
+    test_consoles();
+
+    info!(
+        "HwErrRecSupport: {}",
+        global_variables::get_hw_err_rec_support().unwrap()
+    );
+    info!(
+        "VendorKeys: {}",
+        global_variables::get_vendor_keys().unwrap()
+    );
+    info!("Timeout: {:?}", global_variables::get_timeout().unwrap());
+}
+
+fn test_languages() {
+    let lang = match global_variables::get_platform_lang() {
+        Ok(lang) => lang,
+        Err(err) if err.status() == Status::NOT_FOUND => {
+            info!("PlatformLang is not set");
+            return;
+        }
+        Err(err) => panic!("failed to get PlatformLang: {err:?}"),
+    };
+    let codes = global_variables::get_platform_lang_codes().unwrap();
+    info!("PlatformLang: {lang}, supported: {codes:?}");
+    assert!(codes.contains(&lang));
+
+    // Setting the current language again must succeed.
+    global_variables::set_platform_lang(&lang).unwrap();
+    assert_eq!(global_variables::get_platform_lang().unwrap(), lang);
+
+    assert_eq!(
+        global_variables::set_platform_lang("en\0")
+            .unwrap_err()
+            .status(),
+        Status::INVALID_PARAMETER
+    );
+}
+
+fn test_consoles() {
+    for variable in [ConsoleVariable::ConOut, ConsoleVariable::ConOutDev] {
+        match global_variables::get_console(variable) {
+            Ok(path) => info!(
+                "{}: {} instances",
+                variable.name(),
+                path.instance_iter().count()
+            ),
+            Err(err) if err.status() == Status::NOT_FOUND => {
+                info!("{} is not set", variable.name());
+            }
+            Err(err) => panic!("failed to get {}: {err:?}", variable.name()),
+        }
+    }
+
+    let con_out_dev = global_variables::get_console(ConsoleVariable::ConOutDev);
+    if let Ok(path) = con_out_dev {
+        assert_eq!(
+            global_variables::set_console(ConsoleVariable::ConOutDev, &path)
+                .unwrap_err()
+                .status(),
+            Status::WRITE_PROTECTED
+        );
+    }
+}
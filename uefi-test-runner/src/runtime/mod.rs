@@ -1,13 +1,30 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+mod boot_manager;
+mod capsule;
+mod global_variables;
+mod secure_boot;
+mod snapshot;
 mod vars;
 
+use alloc::string::ToString;
+use core::time::Duration;
 use uefi::runtime::{self, Daylight, Time, TimeParams};
+use uefi::{Status, boot};
 
 pub fn test() {
     info!("Testing runtime services");
     vars::test();
+    boot_manager::test();
+    secure_boot::test();
+    capsule::test();
+    capsule::test_os_indications();
+    capsule::test_capsule_file();
+    global_variables::test();
+    snapshot::test();
     test_time();
+    test_wakeup_time();
+    test_monotonic_count();
 }
 
 fn test_time() {
@@ -36,4 +53,51 @@ fn test_time() {
     let now = runtime::get_time().unwrap();
     info!("After setting time: {now}");
     assert_eq!(now.year(), 2020);
+
+    // The firmware clock moves forward.
+    boot::stall(Duration::from_millis(1100));
+    let later = runtime::get_time().unwrap();
+    info!("Time as RFC 3339: {}", later.rfc3339());
+    assert!(later > now);
+    assert!(later.duration_since(&now).unwrap() >= Duration::from_secs(1));
+
+    // Round-trip through RFC 3339 and Unix time.
+    let parsed: Time = later.rfc3339().to_string().parse().unwrap();
+    assert_eq!(parsed.to_unix_nanos(), later.to_unix_nanos());
+    let unix = Time::from_unix_timestamp(later.to_unix_timestamp().unwrap(), later.nanosecond());
+    assert_eq!(unix, later.to_utc());
+}
+
+fn test_wakeup_time() {
+    let wakeup = match runtime::get_wakeup_time() {
+        Ok(wakeup) => wakeup,
+        Err(err) if err.status() == Status::UNSUPPORTED => {
+            info!("Wakeup alarm is not supported");
+            return;
+        }
+        Err(err) => panic!("failed to get the wakeup time: {err:?}"),
+    };
+    info!("Wakeup time: {wakeup:?}");
+
+    // Enable the alarm one hour from now, then disable it again.
+    let time = runtime::get_time().unwrap() + Duration::from_secs(3600);
+    unsafe { runtime::set_wakeup_time(Some(&time)).unwrap() };
+    let enabled = runtime::get_wakeup_time().unwrap();
+    assert!(enabled.enabled);
+    assert_eq!(enabled.time.hour(), time.hour());
+
+    unsafe { runtime::set_wakeup_time(None).unwrap() };
+    assert!(!runtime::get_wakeup_time().unwrap().enabled);
+}
+
+fn test_monotonic_count() {
+    let first = boot::get_next_monotonic_count().unwrap();
+    let second = boot::get_next_monotonic_count().unwrap();
+    assert!(second > first);
+
+    // The runtime counter starts above all boot services counts.
+    let mut counter = runtime::MonotonicCounter::new().unwrap();
+    let third = counter.next_count().unwrap();
+    assert!(third > second);
+    assert!(counter.next_count().unwrap() > third);
 }
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use uefi::runtime::secure_boot::{self, Database};
+
+pub fn test() {
+    info!("Testing Secure Boot variables");
+
+    let enabled = secure_boot::is_enabled().unwrap();
+    let mode = secure_boot::mode().unwrap();
+    info!("Secure Boot enabled: {enabled}, mode: {mode:?}");
+
+    for database in [Database::Pk, Database::Kek, Database::Db, Database::Dbx] {
+        let db = secure_boot::get_database(database).unwrap();
+        info!(
+            "{}: {} lists, {} signatures",
+            database.name(),
+            db.lists.len(),
+            db.signatures().count()
+        );
+        // The database must round-trip through serialization.
+        let reparsed = secure_boot::SignatureDatabase::parse(&db.to_bytes().unwrap()).unwrap();
+        assert_eq!(reparsed, db);
+    }
+}
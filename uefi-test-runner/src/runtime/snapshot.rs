@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use uefi::runtime::snapshot::{
+    RestoreSkipReason, SnapshotVariable, VariableChange, VariableSnapshot,
+};
+use uefi::runtime::{self, VariableAttributes, VariableKey, VariableVendor};
+use uefi::{CStr16, cstr16, guid};
+
+/// Test variable vendor.
+const VENDOR: VariableVendor = VariableVendor(guid!("2b1a8c3e-5f4d-4a6b-9c7e-0d1f2e3a4b5c"));
+
+/// Non-volatile test variable name.
+const NAME: &CStr16 = cstr16!("UefiRsSnapshotTest");
+
+/// Volatile test variable name.
+const VOLATILE_NAME: &CStr16 = cstr16!("UefiRsSnapshotVolatile");
+
+const ATTRS: VariableAttributes = VariableAttributes::NON_VOLATILE
+    .union(VariableAttributes::BOOTSERVICE_ACCESS)
+    .union(VariableAttributes::RUNTIME_ACCESS);
+
+pub fn test() {
+    info!("Testing variable snapshots");
+
+    let key = VariableKey {
+        vendor: VENDOR,
+        name: NAME.into(),
+    };
+    let volatile_key = VariableKey {
+        vendor: VENDOR,
+        name: VOLATILE_NAME.into(),
+    };
+
+    runtime::set_variable(NAME, &VENDOR, ATTRS, b"old").unwrap();
+    runtime::set_variable(
+        VOLATILE_NAME,
+        &VENDOR,
+        VariableAttributes::BOOTSERVICE_ACCESS,
+        b"volatile",
+    )
+    .unwrap();
+
+    let old = VariableSnapshot::capture().unwrap();
+    info!("Captured {} variables", old.len());
+    assert_eq!(old.get(&key).unwrap().data, b"old");
+
+    // The snapshot survives serialization.
+    let bytes = old.to_bytes();
+    assert_eq!(VariableSnapshot::from_bytes(&bytes).unwrap(), old);
+
+    runtime::set_variable(NAME, &VENDOR, ATTRS, b"new").unwrap();
+    let new = VariableSnapshot::capture().unwrap();
+    let changes = old.diff(&new);
+    assert!(changes.iter().any(|change| matches!(
+        change,
+        VariableChange::Modified { old, new }
+            if old.key == key && old.data == b"old" && new.data == b"new"
+    )));
+
+    // Restore only the test variables.
+    let mut partial = VariableSnapshot::new();
+    partial.insert(old.get(&key).unwrap().clone());
+    partial.insert(old.get(&volatile_key).unwrap().clone());
+    partial.insert(SnapshotVariable {
+        key: VariableKey {
+            vendor: VENDOR,
+            name: cstr16!("UefiRsSnapshotAuth").into(),
+        },
+        attributes: ATTRS | VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS,
+        data: b"auth".to_vec(),
+    });
+    let report = partial.restore();
+    info!("Restore report: {report:?}");
+    assert_eq!(report.restored, core::slice::from_ref(&key));
+    assert!(
+        report
+            .skipped
+            .contains(&(volatile_key, RestoreSkipReason::Volatile))
+    );
+    assert!(
+        report
+            .skipped
+            .iter()
+            .any(|(_, reason)| *reason == RestoreSkipReason::Authenticated)
+    );
+    assert!(!report.is_complete());
+
+    let (data, _) = runtime::get_variable_boxed(NAME, &VENDOR).unwrap();
+    assert_eq!(&*data, b"old");
+
+    // Restoring again does not write anything.
+    assert_eq!(partial.restore().unchanged, [key]);
+
+    runtime::delete_variable(NAME, &VENDOR).unwrap();
+    runtime::delete_variable(VOLATILE_NAME, &VENDOR).unwrap();
+}
@@ -2,7 +2,7 @@
 
 use log::info;
 use uefi::prelude::*;
-use uefi::runtime::{VariableAttributes, VariableVendor};
+use uefi::runtime::{VariableAttributes, VariableValue, VariableVendor};
 use uefi::{CStr16, Error, guid, runtime};
 
 /// Test variable name.
@@ -68,6 +68,30 @@ fn test_variables() {
     assert!(!find_by_key());
 }
 
+/// Test `runtime::get` and `runtime::set` with a derived `VariableValue`.
+fn test_typed_variables() {
+    #[derive(Debug, PartialEq, VariableValue)]
+    #[repr(C)]
+    struct Value {
+        a: u8,
+        b: u32,
+    }
+
+    let value = Value { a: 1, b: 2 };
+    runtime::set(NAME, VENDOR, ATTRS, &value).expect("failed to set variable");
+    let (data, _) = runtime::get_variable_boxed(NAME, VENDOR).unwrap();
+    assert_eq!(*data, [1, 0, 0, 0, 2, 0, 0, 0]);
+    assert_eq!(runtime::get::<Value>(NAME, VENDOR).unwrap(), value);
+
+    // A value of the wrong size can't be decoded.
+    assert_eq!(
+        runtime::get::<u32>(NAME, VENDOR).unwrap_err().status(),
+        Status::VOLUME_CORRUPTED
+    );
+
+    runtime::delete_variable(NAME, VENDOR).expect("failed to delete variable");
+}
+
 fn test_variable_info() {
     let attr = VariableAttributes::BOOTSERVICE_ACCESS | VariableAttributes::NON_VOLATILE;
     let info = runtime::query_variable_info(attr).unwrap();
@@ -81,4 +105,5 @@ fn test_variable_info() {
 pub fn test() {
     test_variable_info();
     test_variables();
+    test_typed_variables();
 }
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tests functionality from the `uefi::task` module. See function [`test`].
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+use core::time::Duration;
+use uefi::boot::{self, EventType, TimerTrigger, Tpl};
+use uefi::task::{self, Executor};
+
+pub fn test() {
+    info!("Testing async tasks");
+    test_block_on();
+    test_spawn();
+    test_wait_for_event();
+}
+
+fn test_block_on() {
+    let mut executor = Executor::new().unwrap();
+    let value = executor
+        .block_on(async {
+            task::sleep(Duration::from_millis(1))
+                .unwrap()
+                .await
+                .unwrap();
+            123
+        })
+        .unwrap();
+    assert_eq!(value, 123);
+}
+
+fn test_spawn() {
+    let mut executor = Executor::new().unwrap();
+    let ticks = Rc::new(Cell::new(0));
+    let done = Rc::new(Cell::new(false));
+
+    {
+        let ticks = ticks.clone();
+        executor.spawn(async move {
+            let mut interval = task::interval(Duration::from_millis(1)).unwrap();
+            for _ in 0..3 {
+                interval.tick().await.unwrap();
+                ticks.set(ticks.get() + 1);
+            }
+        });
+    }
+
+    {
+        let done = done.clone();
+        let spawner = executor.spawner();
+        executor.spawn(async move {
+            // Spawn a nested task from within a task.
+            spawner.spawn(async move {
+                task::sleep(Duration::from_millis(2))
+                    .unwrap()
+                    .await
+                    .unwrap();
+                done.set(true);
+            });
+        });
+    }
+
+    executor.run().unwrap();
+    assert_eq!(ticks.get(), 3);
+    assert!(done.get());
+}
+
+fn test_wait_for_event() {
+    let event = unsafe { boot::create_event(EventType::TIMER, Tpl::CALLBACK, None, None) }.unwrap();
+    boot::set_timer(&event, TimerTrigger::Relative(5_0 /*00 ns */)).unwrap();
+
+    let mut executor = Executor::new().unwrap();
+    executor
+        .block_on(task::wait_for_event(&event))
+        .unwrap()
+        .unwrap();
+
+    boot::close_event(event).unwrap();
+}
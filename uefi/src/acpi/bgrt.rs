@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Boot Graphics Resource Table.
+
+use super::{
+    AcpiError, AcpiTable, Sdt, Signature, check_table, read_u8, read_u16, read_u32, read_u64,
+};
+
+/// Size of the table.
+const LENGTH: usize = 56;
+
+/// Boot Graphics Resource Table (BGRT), with the signature `BGRT`.
+///
+/// The BGRT describes the logo that the firmware displayed during boot, so
+/// that the operating system can keep displaying it.
+#[derive(Clone, Copy, Debug)]
+pub struct Bgrt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> Bgrt<'a> {
+    /// The underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Version of the table, currently 1.
+    #[must_use]
+    pub fn version(&self) -> u16 {
+        read_u16(self.sdt.as_bytes(), 36)
+    }
+
+    /// Whether the image is currently displayed on the screen.
+    #[must_use]
+    pub fn is_displayed(&self) -> bool {
+        read_u8(self.sdt.as_bytes(), 38) & 1 != 0
+    }
+
+    /// Clockwise rotation of the image relative to the screen, in degrees:
+    /// 0, 90, 180 or 270.
+    #[must_use]
+    pub fn orientation(&self) -> u16 {
+        u16::from((read_u8(self.sdt.as_bytes(), 38) >> 1) & 0x3) * 90
+    }
+
+    /// Type of the image: 0 for a bitmap.
+    #[must_use]
+    pub fn image_type(&self) -> u8 {
+        read_u8(self.sdt.as_bytes(), 39)
+    }
+
+    /// Physical address of the image, in `EfiBootServicesData` memory.
+    #[must_use]
+    pub fn image_address(&self) -> u64 {
+        read_u64(self.sdt.as_bytes(), 40)
+    }
+
+    /// Position of the upper left corner of the image on the screen, as
+    /// `(x, y)`.
+    #[must_use]
+    pub fn image_offset(&self) -> (u32, u32) {
+        (
+            read_u32(self.sdt.as_bytes(), 48),
+            read_u32(self.sdt.as_bytes(), 52),
+        )
+    }
+}
+
+impl<'a> TryFrom<Sdt<'a>> for Bgrt<'a> {
+    type Error = AcpiError;
+
+    fn try_from(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        check_table(&sdt, Self::SIGNATURE, LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> AcpiTable<'a> for Bgrt<'a> {
+    const SIGNATURE: Signature = Signature::BGRT;
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::acpi::tests::build_table;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_bgrt() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&[1, 0, 0b011, 0]);
+        data.extend_from_slice(&0x7e00_0000u64.to_le_bytes());
+        data.extend_from_slice(&100u32.to_le_bytes());
+        data.extend_from_slice(&200u32.to_le_bytes());
+        let table = build_table(Signature::BGRT, 1, &data);
+        let bgrt = Bgrt::try_from(Sdt::parse(&table).unwrap()).unwrap();
+
+        assert_eq!(bgrt.version(), 1);
+        assert!(bgrt.is_displayed());
+        assert_eq!(bgrt.orientation(), 90);
+        assert_eq!(bgrt.image_type(), 0);
+        assert_eq!(bgrt.image_address(), 0x7e00_0000);
+        assert_eq!(bgrt.image_offset(), (100, 200));
+    }
+}
@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Fixed ACPI Description Table.
+
+use super::{
+    AcpiError, AcpiTable, AddressSpace, GenericAddress, Sdt, Signature, check_table, read_u8,
+    read_u16, read_u32, read_u64,
+};
+use bitflags::bitflags;
+
+/// Size of the ACPI 1.0 FADT.
+const MIN_LENGTH: usize = 116;
+
+bitflags! {
+    /// Fixed feature flags of the [`Fadt`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct FadtFlags: u32 {
+        /// The `WBINVD` instruction works correctly.
+        const WBINVD = 1 << 0;
+        /// The `WBINVD` instruction flushes all caches.
+        const WBINVD_FLUSH = 1 << 1;
+        /// C1 power state is supported on all processors.
+        const PROC_C1 = 1 << 2;
+        /// C2 power state works on multiprocessor systems.
+        const P_LVL2_UP = 1 << 3;
+        /// The power button is a control method device.
+        const PWR_BUTTON = 1 << 4;
+        /// The sleep button is a control method device.
+        const SLP_BUTTON = 1 << 5;
+        /// RTC wake status is not in the fixed register space.
+        const FIX_RTC = 1 << 6;
+        /// The RTC can wake the system from S4.
+        const RTC_S4 = 1 << 7;
+        /// The PM timer is 32 bits wide instead of 24.
+        const TMR_VAL_EXT = 1 << 8;
+        /// The system supports docking.
+        const DCK_CAP = 1 << 9;
+        /// The reset register is supported.
+        const RESET_REG_SUP = 1 << 10;
+        /// The system has no internal expansion capabilities.
+        const SEALED_CASE = 1 << 11;
+        /// The system has no local input or output devices.
+        const HEADLESS = 1 << 12;
+        /// A processor native instruction must be executed after writing
+        /// the sleep type register.
+        const CPU_SW_SLP = 1 << 13;
+        /// The platform supports `PCIEXP_WAKE_STS` and `PCIEXP_WAKE_EN`.
+        const PCI_EXP_WAK = 1 << 14;
+        /// OSPM should use a platform clock instead of the processor clock.
+        const USE_PLATFORM_CLOCK = 1 << 15;
+        /// The RTC status is valid when waking from S4.
+        const S4_RTC_STS_VALID = 1 << 16;
+        /// The platform supports remote power-on.
+        const REMOTE_POWER_ON_CAPABLE = 1 << 17;
+        /// Local APICs must use the cluster destination model.
+        const FORCE_APIC_CLUSTER_MODEL = 1 << 18;
+        /// Local APICs must use the physical destination mode.
+        const FORCE_APIC_PHYSICAL_DESTINATION_MODE = 1 << 19;
+        /// The ACPI hardware interface is not implemented.
+        const HW_REDUCED_ACPI = 1 << 20;
+        /// S0 idle is at least as power efficient as S3.
+        const LOW_POWER_S0_IDLE_CAPABLE = 1 << 21;
+    }
+}
+
+bitflags! {
+    /// IA-PC boot architecture flags of the [`Fadt`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct IaPcBootArch: u16 {
+        /// Legacy devices, such as the serial ports, are present.
+        const LEGACY_DEVICES = 1 << 0;
+        /// An 8042 keyboard controller is present.
+        const I8042 = 1 << 1;
+        /// VGA hardware must not be probed.
+        const VGA_NOT_PRESENT = 1 << 2;
+        /// MSIs must not be enabled.
+        const MSI_NOT_SUPPORTED = 1 << 3;
+        /// OSPM must not enable PCI Express ASPM.
+        const PCIE_ASPM_CONTROLS = 1 << 4;
+        /// The CMOS RTC is not present.
+        const CMOS_RTC_NOT_PRESENT = 1 << 5;
+    }
+}
+
+/// Fixed ACPI Description Table (FADT), with the signature `FACP`.
+///
+/// The FADT describes the fixed ACPI hardware registers, and references the
+/// DSDT and FACS.
+#[derive(Clone, Copy, Debug)]
+pub struct Fadt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> Fadt<'a> {
+    /// The underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    const fn bytes(&self) -> &'a [u8] {
+        self.sdt.as_bytes()
+    }
+
+    /// Physical address of the Firmware ACPI Control Structure (FACS),
+    /// preferring the 64-bit `X_FIRMWARE_CTRL` field.
+    #[must_use]
+    pub fn facs_address(&self) -> Option<u64> {
+        let address = match read_u64(self.bytes(), 132) {
+            0 => read_u32(self.bytes(), 36).into(),
+            address => address,
+        };
+        Some(address).filter(|address| *address != 0)
+    }
+
+    /// Physical address of the DSDT, preferring the 64-bit `X_DSDT` field.
+    #[must_use]
+    pub fn dsdt_address(&self) -> Option<u64> {
+        let address = match read_u64(self.bytes(), 140) {
+            0 => read_u32(self.bytes(), 40).into(),
+            address => address,
+        };
+        Some(address).filter(|address| *address != 0)
+    }
+
+    /// Preferred power management profile, such as 1 for desktop, 2 for
+    /// mobile or 4 for enterprise server.
+    #[must_use]
+    pub fn preferred_pm_profile(&self) -> u8 {
+        read_u8(self.bytes(), 45)
+    }
+
+    /// System vector of the SCI interrupt in 8259 mode, or its global
+    /// system interrupt otherwise.
+    #[must_use]
+    pub fn sci_interrupt(&self) -> u16 {
+        read_u16(self.bytes(), 46)
+    }
+
+    /// I/O port of the SMI command port, or zero if SMM is not supported.
+    #[must_use]
+    pub fn smi_command_port(&self) -> u32 {
+        read_u32(self.bytes(), 48)
+    }
+
+    /// Value to write to the SMI command port to enable ACPI.
+    #[must_use]
+    pub fn acpi_enable(&self) -> u8 {
+        read_u8(self.bytes(), 52)
+    }
+
+    /// Value to write to the SMI command port to disable ACPI.
+    #[must_use]
+    pub fn acpi_disable(&self) -> u8 {
+        read_u8(self.bytes(), 53)
+    }
+
+    /// Power management timer register, preferring the `X_PM_TMR_BLK`
+    /// field.
+    #[must_use]
+    pub fn pm_timer_block(&self) -> Option<GenericAddress> {
+        self.register(208, 76, 91)
+    }
+
+    /// PM1a event register block, preferring the `X_PM1a_EVT_BLK` field.
+    #[must_use]
+    pub fn pm1a_event_block(&self) -> Option<GenericAddress> {
+        self.register(148, 56, 88)
+    }
+
+    /// PM1a control register block, preferring the `X_PM1a_CNT_BLK` field.
+    #[must_use]
+    pub fn pm1a_control_block(&self) -> Option<GenericAddress> {
+        self.register(172, 64, 89)
+    }
+
+    /// Read a register from its extended field at `x_offset`, or from the
+    /// legacy I/O port at `offset` with the length in bytes at
+    /// `length_offset`.
+    fn register(
+        &self,
+        x_offset: usize,
+        offset: usize,
+        length_offset: usize,
+    ) -> Option<GenericAddress> {
+        GenericAddress::read_optional(self.bytes(), x_offset).or_else(|| {
+            let port = read_u32(self.bytes(), offset);
+            (port != 0).then(|| GenericAddress {
+                address_space: AddressSpace::SYSTEM_IO,
+                bit_width: read_u8(self.bytes(), length_offset).wrapping_mul(8),
+                bit_offset: 0,
+                access_size: 0,
+                address: port.into(),
+            })
+        })
+    }
+
+    /// Index of the century in the CMOS RTC, or zero if not supported.
+    #[must_use]
+    pub fn century(&self) -> u8 {
+        read_u8(self.bytes(), 108)
+    }
+
+    /// IA-PC boot architecture flags.
+    #[must_use]
+    pub fn iapc_boot_arch(&self) -> IaPcBootArch {
+        IaPcBootArch::from_bits_retain(read_u16(self.bytes(), 109))
+    }
+
+    /// Fixed feature flags.
+    #[must_use]
+    pub fn flags(&self) -> FadtFlags {
+        FadtFlags::from_bits_retain(read_u32(self.bytes(), 112))
+    }
+
+    /// Reset register and the value to write to it to reset the system, if
+    /// [`FadtFlags::RESET_REG_SUP`] is set.
+    #[must_use]
+    pub fn reset_register(&self) -> Option<(GenericAddress, u8)> {
+        if !self.flags().contains(FadtFlags::RESET_REG_SUP) {
+            return None;
+        }
+        let register = GenericAddress::read_optional(self.bytes(), 116)?;
+        Some((register, read_u8(self.bytes(), 128)))
+    }
+
+    /// ARM boot architecture flags: bit 0 is set if PSCI is implemented,
+    /// and bit 1 if PSCI uses HVC instead of SMC.
+    #[must_use]
+    pub fn arm_boot_arch(&self) -> u16 {
+        read_u16(self.bytes(), 129)
+    }
+
+    /// Version of the FADT as `(major, minor)`. The major version is the
+    /// table revision.
+    #[must_use]
+    pub fn version(&self) -> (u8, u8) {
+        (self.sdt.revision(), read_u8(self.bytes(), 131) & 0xf)
+    }
+
+    /// Identity of the hypervisor vendor, if the platform runs in a
+    /// virtual machine and the FADT is recent enough.
+    #[must_use]
+    pub fn hypervisor_vendor_id(&self) -> Option<u64> {
+        Some(read_u64(self.bytes(), 268)).filter(|id| *id != 0)
+    }
+}
+
+impl<'a> TryFrom<Sdt<'a>> for Fadt<'a> {
+    type Error = AcpiError;
+
+    fn try_from(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        check_table(&sdt, Self::SIGNATURE, MIN_LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> AcpiTable<'a> for Fadt<'a> {
+    const SIGNATURE: Signature = Signature::FADT;
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::acpi::SDT_HEADER_SIZE;
+    use crate::acpi::tests::build_table;
+
+    fn set(data: &mut [u8], offset: usize, value: &[u8]) {
+        let offset = offset - SDT_HEADER_SIZE;
+        data[offset..offset + value.len()].copy_from_slice(value);
+    }
+
+    #[test]
+    fn test_fadt() {
+        let mut data = [0u8; 276 - SDT_HEADER_SIZE];
+        set(&mut data, 36, &0x1000u32.to_le_bytes());
+        set(&mut data, 40, &0x2000u32.to_le_bytes());
+        set(&mut data, 45, &[2]);
+        set(&mut data, 46, &9u16.to_le_bytes());
+        set(&mut data, 76, &0x608u32.to_le_bytes());
+        set(&mut data, 91, &[4]);
+        set(&mut data, 108, &0x32u8.to_le_bytes());
+        set(&mut data, 109, &0x3u16.to_le_bytes());
+        set(&mut data, 112, &(1u32 << 10 | 1 << 8).to_le_bytes());
+        set(&mut data, 116, &[1, 8, 0, 1, 0xf9, 0xc, 0, 0, 0, 0, 0, 0]);
+        set(&mut data, 128, &[6]);
+        set(&mut data, 131, &[5]);
+        set(&mut data, 140, &0x1_0000_0000u64.to_le_bytes());
+        let table = build_table(Signature::FADT, 6, &data);
+        let fadt = Fadt::try_from(Sdt::parse(&table).unwrap()).unwrap();
+
+        assert_eq!(fadt.facs_address(), Some(0x1000));
+        assert_eq!(fadt.dsdt_address(), Some(0x1_0000_0000));
+        assert_eq!(fadt.preferred_pm_profile(), 2);
+        assert_eq!(fadt.sci_interrupt(), 9);
+        assert_eq!(
+            fadt.pm_timer_block(),
+            Some(GenericAddress {
+                address_space: AddressSpace::SYSTEM_IO,
+                bit_width: 32,
+                bit_offset: 0,
+                access_size: 0,
+                address: 0x608,
+            })
+        );
+        assert_eq!(fadt.pm1a_event_block(), None);
+        assert_eq!(fadt.century(), 0x32);
+        assert_eq!(
+            fadt.iapc_boot_arch(),
+            IaPcBootArch::LEGACY_DEVICES | IaPcBootArch::I8042
+        );
+        assert_eq!(
+            fadt.flags(),
+            FadtFlags::RESET_REG_SUP | FadtFlags::TMR_VAL_EXT
+        );
+        let (reset, value) = fadt.reset_register().unwrap();
+        assert_eq!(reset.address, 0xcf9);
+        assert_eq!(value, 6);
+        assert_eq!(fadt.version(), (6, 5));
+        assert_eq!(fadt.hypervisor_vendor_id(), None);
+    }
+
+    #[test]
+    fn test_fadt_acpi_1() {
+        let mut data = [0u8; MIN_LENGTH - SDT_HEADER_SIZE];
+        set(&mut data, 40, &0x2000u32.to_le_bytes());
+        let table = build_table(Signature::FADT, 1, &data);
+        let fadt = Fadt::try_from(Sdt::parse(&table).unwrap()).unwrap();
+        assert_eq!(fadt.dsdt_address(), Some(0x2000));
+        assert_eq!(fadt.facs_address(), None);
+        assert_eq!(fadt.reset_register(), None);
+
+        let table = build_table(Signature::FADT, 1, &data[1..]);
+        assert_eq!(
+            Fadt::try_from(Sdt::parse(&table).unwrap()).unwrap_err(),
+            AcpiError::InvalidLength
+        );
+        let table = build_table(Signature::MADT, 1, &data);
+        assert_eq!(
+            Fadt::try_from(Sdt::parse(&table).unwrap()).unwrap_err(),
+            AcpiError::InvalidSignature
+        );
+    }
+}
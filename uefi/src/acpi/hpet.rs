@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! High Precision Event Timer table.
+
+use super::{
+    AcpiError, AcpiTable, GenericAddress, Sdt, Signature, check_table, read_u8, read_u16, read_u32,
+};
+
+/// Size of the table.
+const LENGTH: usize = 56;
+
+/// High Precision Event Timer table (HPET), with the signature `HPET`.
+#[derive(Clone, Copy, Debug)]
+pub struct Hpet<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> Hpet<'a> {
+    /// The underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Event timer block ID, a copy of the low 32 bits of the capabilities
+    /// register of the timer block.
+    #[must_use]
+    pub fn event_timer_block_id(&self) -> u32 {
+        read_u32(self.sdt.as_bytes(), 36)
+    }
+
+    /// Hardware revision of the timer block.
+    #[must_use]
+    pub fn hardware_revision(&self) -> u8 {
+        self.event_timer_block_id().to_le_bytes()[0]
+    }
+
+    /// Number of comparators of the timer block.
+    #[must_use]
+    pub fn comparator_count(&self) -> u8 {
+        ((self.event_timer_block_id() >> 8) & 0x1f) as u8 + 1
+    }
+
+    /// Whether the main counter is 64 bits wide.
+    #[must_use]
+    pub fn is_counter_64bit(&self) -> bool {
+        self.event_timer_block_id() & (1 << 13) != 0
+    }
+
+    /// Whether the timer block supports legacy replacement interrupt
+    /// routing.
+    #[must_use]
+    pub fn is_legacy_replacement_capable(&self) -> bool {
+        self.event_timer_block_id() & (1 << 15) != 0
+    }
+
+    /// PCI vendor ID of the timer block.
+    #[must_use]
+    pub fn pci_vendor_id(&self) -> u16 {
+        (self.event_timer_block_id() >> 16) as u16
+    }
+
+    /// Address of the timer block registers.
+    #[must_use]
+    pub fn base_address(&self) -> GenericAddress {
+        GenericAddress::read(self.sdt.as_bytes(), 40)
+    }
+
+    /// Sequence number of the timer block.
+    #[must_use]
+    pub fn hpet_number(&self) -> u8 {
+        read_u8(self.sdt.as_bytes(), 52)
+    }
+
+    /// Minimum number of clock ticks for periodic interrupts without
+    /// losing interrupts.
+    #[must_use]
+    pub fn minimum_tick(&self) -> u16 {
+        read_u16(self.sdt.as_bytes(), 53)
+    }
+
+    /// Page protection and OEM attributes.
+    #[must_use]
+    pub fn page_protection(&self) -> u8 {
+        read_u8(self.sdt.as_bytes(), 55)
+    }
+}
+
+impl<'a> TryFrom<Sdt<'a>> for Hpet<'a> {
+    type Error = AcpiError;
+
+    fn try_from(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        check_table(&sdt, Self::SIGNATURE, LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> AcpiTable<'a> for Hpet<'a> {
+    const SIGNATURE: Signature = Signature::HPET;
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::acpi::AddressSpace;
+    use crate::acpi::tests::build_table;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_hpet() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0x8086_a201u32.to_le_bytes());
+        data.extend_from_slice(&[0, 64, 0, 0]);
+        data.extend_from_slice(&0xfed0_0000u64.to_le_bytes());
+        data.extend_from_slice(&[0, 0x80, 0, 0]);
+        let table = build_table(Signature::HPET, 1, &data);
+        let hpet = Hpet::try_from(Sdt::parse(&table).unwrap()).unwrap();
+
+        assert_eq!(hpet.hardware_revision(), 1);
+        assert_eq!(hpet.comparator_count(), 3);
+        assert!(hpet.is_counter_64bit());
+        assert!(hpet.is_legacy_replacement_capable());
+        assert_eq!(hpet.pci_vendor_id(), 0x8086);
+        assert_eq!(
+            hpet.base_address().address_space,
+            AddressSpace::SYSTEM_MEMORY
+        );
+        assert_eq!(hpet.base_address().address, 0xfed0_0000);
+        assert_eq!(hpet.hpet_number(), 0);
+        assert_eq!(hpet.minimum_tick(), 0x80);
+
+        let table = build_table(Signature::HPET, 1, &data[1..]);
+        assert_eq!(
+            Hpet::try_from(Sdt::parse(&table).unwrap()).unwrap_err(),
+            AcpiError::InvalidLength
+        );
+    }
+}
@@ -0,0 +1,511 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Multiple APIC Description Table.
+
+use super::{
+    AcpiError, AcpiTable, Sdt, Signature, check_table, read_u8, read_u16, read_u32, read_u64,
+};
+use bitflags::bitflags;
+
+/// Offset of the first interrupt controller structure.
+const ENTRIES_OFFSET: usize = 44;
+
+bitflags! {
+    /// Flags of the [`Madt`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct MadtFlags: u32 {
+        /// The system has dual 8259 interrupt controllers, which must be
+        /// disabled to use the I/O APICs.
+        const PCAT_COMPAT = 1 << 0;
+    }
+}
+
+bitflags! {
+    /// Flags of a [`LocalApic`], [`LocalX2Apic`] or [`GicSystemInterface`].
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct LocalApicFlags: u32 {
+        /// The processor is ready to use.
+        const ENABLED = 1 << 0;
+        /// The processor can be enabled at runtime, if it is not
+        /// [`ENABLED`](Self::ENABLED).
+        const ONLINE_CAPABLE = 1 << 1;
+    }
+}
+
+/// Multiple APIC Description Table (MADT), with the signature `APIC`.
+///
+/// The MADT describes the interrupt controllers of the platform, and
+/// contains a list of interrupt controller structures, see
+/// [`entries`](Self::entries).
+#[derive(Clone, Copy, Debug)]
+pub struct Madt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> Madt<'a> {
+    /// The underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Physical address of the local APIC of each processor. It may be
+    /// overridden by a [`MadtEntry::LocalApicAddressOverride`].
+    #[must_use]
+    pub fn local_apic_address(&self) -> u32 {
+        read_u32(self.sdt.as_bytes(), 36)
+    }
+
+    /// Flags of the table.
+    #[must_use]
+    pub fn flags(&self) -> MadtFlags {
+        MadtFlags::from_bits_retain(read_u32(self.sdt.as_bytes(), 40))
+    }
+
+    /// Iterator over the interrupt controller structures.
+    #[must_use]
+    pub fn entries(&self) -> MadtEntries<'a> {
+        MadtEntries {
+            bytes: &self.sdt.as_bytes()[ENTRIES_OFFSET..],
+        }
+    }
+}
+
+impl<'a> TryFrom<Sdt<'a>> for Madt<'a> {
+    type Error = AcpiError;
+
+    fn try_from(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        check_table(&sdt, Self::SIGNATURE, ENTRIES_OFFSET)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> AcpiTable<'a> for Madt<'a> {
+    const SIGNATURE: Signature = Signature::MADT;
+}
+
+/// Iterator over the interrupt controller structures of a [`Madt`].
+///
+/// Iteration stops at the first structure whose length is invalid.
+#[derive(Clone, Debug)]
+pub struct MadtEntries<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for MadtEntries<'a> {
+    type Item = MadtEntry<'a>;
+
+    fn next(&mut self) -> Option<MadtEntry<'a>> {
+        let length = usize::from(*self.bytes.get(1)?);
+        if length < 2 || length > self.bytes.len() {
+            self.bytes = &[];
+            return None;
+        }
+        let (entry, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Some(MadtEntry::parse(entry))
+    }
+}
+
+/// Interrupt controller structure of a [`Madt`].
+///
+/// Structures of an unknown type, or too short for their type, are returned
+/// as [`Unknown`](Self::Unknown).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MadtEntry<'a> {
+    /// Processor local APIC (type 0).
+    LocalApic(LocalApic),
+
+    /// I/O APIC (type 1).
+    IoApic(IoApic),
+
+    /// Interrupt source override (type 2).
+    InterruptSourceOverride(InterruptSourceOverride),
+
+    /// Non-maskable interrupt source (type 3).
+    NmiSource(NmiSource),
+
+    /// Local APIC NMI (type 4).
+    LocalApicNmi(LocalApicNmi),
+
+    /// Local APIC address override (type 5).
+    LocalApicAddressOverride(LocalApicAddressOverride),
+
+    /// Processor local x2APIC (type 9).
+    LocalX2Apic(LocalX2Apic),
+
+    /// Local x2APIC NMI (type 10).
+    LocalX2ApicNmi(LocalX2ApicNmi),
+
+    /// GIC CPU interface (type 11).
+    GicSystemInterface(GicSystemInterface),
+
+    /// GIC distributor (type 12).
+    GicDistributor(GicDistributor),
+
+    /// GIC MSI frame (type 13).
+    GicMsiFrame(GicMsiFrame),
+
+    /// GIC redistributor (type 14).
+    GicRedistributor(GicRedistributor),
+
+    /// GIC interrupt translation service (type 15).
+    GicInterruptTranslationService(GicInterruptTranslationService),
+
+    /// Other structure.
+    Unknown {
+        /// Type of the structure.
+        entry_type: u8,
+
+        /// Bytes of the structure, including the type and length.
+        bytes: &'a [u8],
+    },
+}
+
+impl<'a> MadtEntry<'a> {
+    fn parse(bytes: &'a [u8]) -> Self {
+        let entry_type = bytes[0];
+        let min_length = match entry_type {
+            0 | 3 => 8,
+            1 | 5 | 10 => 12,
+            2 => 10,
+            4 => 6,
+            9 | 14 => 16,
+            11 => 40,
+            12 | 13 => 24,
+            15 => 20,
+            _ => usize::MAX,
+        };
+        if bytes.len() < min_length {
+            return Self::Unknown { entry_type, bytes };
+        }
+
+        let u8_at = |offset| read_u8(bytes, offset);
+        let u16_at = |offset| read_u16(bytes, offset);
+        let u32_at = |offset| read_u32(bytes, offset);
+        let u64_at = |offset| read_u64(bytes, offset);
+        match entry_type {
+            0 => Self::LocalApic(LocalApic {
+                processor_uid: u8_at(2),
+                apic_id: u8_at(3),
+                flags: LocalApicFlags::from_bits_retain(u32_at(4)),
+            }),
+            1 => Self::IoApic(IoApic {
+                io_apic_id: u8_at(2),
+                address: u32_at(4),
+                global_system_interrupt_base: u32_at(8),
+            }),
+            2 => Self::InterruptSourceOverride(InterruptSourceOverride {
+                bus: u8_at(2),
+                source: u8_at(3),
+                global_system_interrupt: u32_at(4),
+                flags: u16_at(8),
+            }),
+            3 => Self::NmiSource(NmiSource {
+                flags: u16_at(2),
+                global_system_interrupt: u32_at(4),
+            }),
+            4 => Self::LocalApicNmi(LocalApicNmi {
+                processor_uid: u8_at(2),
+                flags: u16_at(3),
+                lint: u8_at(5),
+            }),
+            5 => Self::LocalApicAddressOverride(LocalApicAddressOverride { address: u64_at(4) }),
+            9 => Self::LocalX2Apic(LocalX2Apic {
+                x2apic_id: u32_at(4),
+                flags: LocalApicFlags::from_bits_retain(u32_at(8)),
+                processor_uid: u32_at(12),
+            }),
+            10 => Self::LocalX2ApicNmi(LocalX2ApicNmi {
+                flags: u16_at(2),
+                processor_uid: u32_at(4),
+                lint: u8_at(8),
+            }),
+            11 => Self::GicSystemInterface(GicSystemInterface {
+                cpu_interface_number: u32_at(4),
+                processor_uid: u32_at(8),
+                flags: LocalApicFlags::from_bits_retain(u32_at(12)),
+                performance_interrupt: u32_at(20),
+                physical_base_address: u64_at(32),
+                gicr_base_address: u64_at(60),
+                mpidr: u64_at(68),
+            }),
+            12 => Self::GicDistributor(GicDistributor {
+                gic_id: u32_at(4),
+                physical_base_address: u64_at(8),
+                gic_version: u8_at(20),
+            }),
+            13 => Self::GicMsiFrame(GicMsiFrame {
+                msi_frame_id: u32_at(4),
+                physical_base_address: u64_at(8),
+                flags: u32_at(16),
+                spi_count: u16_at(20),
+                spi_base: u16_at(22),
+            }),
+            14 => Self::GicRedistributor(GicRedistributor {
+                discovery_range_base_address: u64_at(4),
+                discovery_range_length: u32_at(12),
+            }),
+            15 => Self::GicInterruptTranslationService(GicInterruptTranslationService {
+                its_id: u32_at(4),
+                physical_base_address: u64_at(8),
+            }),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Processor local APIC structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct LocalApic {
+    /// ACPI processor UID.
+    pub processor_uid: u8,
+
+    /// Local APIC ID of the processor.
+    pub apic_id: u8,
+
+    /// Flags of the processor.
+    pub flags: LocalApicFlags,
+}
+
+/// I/O APIC structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct IoApic {
+    /// I/O APIC ID.
+    pub io_apic_id: u8,
+
+    /// Physical address of the I/O APIC.
+    pub address: u32,
+
+    /// First global system interrupt of the I/O APIC.
+    pub global_system_interrupt_base: u32,
+}
+
+/// Interrupt source override structure of the [`Madt`], which maps an ISA
+/// interrupt to a global system interrupt.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct InterruptSourceOverride {
+    /// Bus of the interrupt source, 0 for ISA.
+    pub bus: u8,
+
+    /// Bus-relative interrupt source (IRQ).
+    pub source: u8,
+
+    /// Global system interrupt that the source signals.
+    pub global_system_interrupt: u32,
+
+    /// MPS INTI flags: polarity in bits 0-1 and trigger mode in bits 2-3.
+    pub flags: u16,
+}
+
+/// Non-maskable interrupt source structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct NmiSource {
+    /// MPS INTI flags: polarity in bits 0-1 and trigger mode in bits 2-3.
+    pub flags: u16,
+
+    /// Global system interrupt of the NMI.
+    pub global_system_interrupt: u32,
+}
+
+/// Local APIC NMI structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct LocalApicNmi {
+    /// ACPI processor UID, or `0xff` for all processors.
+    pub processor_uid: u8,
+
+    /// MPS INTI flags: polarity in bits 0-1 and trigger mode in bits 2-3.
+    pub flags: u16,
+
+    /// Local APIC interrupt input (`LINTn`) connected to the NMI.
+    pub lint: u8,
+}
+
+/// Local APIC address override structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct LocalApicAddressOverride {
+    /// 64-bit physical address of the local APIC.
+    pub address: u64,
+}
+
+/// Processor local x2APIC structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct LocalX2Apic {
+    /// x2APIC ID of the processor.
+    pub x2apic_id: u32,
+
+    /// Flags of the processor.
+    pub flags: LocalApicFlags,
+
+    /// ACPI processor UID.
+    pub processor_uid: u32,
+}
+
+/// Local x2APIC NMI structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct LocalX2ApicNmi {
+    /// MPS INTI flags: polarity in bits 0-1 and trigger mode in bits 2-3.
+    pub flags: u16,
+
+    /// ACPI processor UID, or `0xffffffff` for all processors.
+    pub processor_uid: u32,
+
+    /// Local x2APIC interrupt input (`LINTn`) connected to the NMI.
+    pub lint: u8,
+}
+
+/// GIC CPU interface (GICC) structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct GicSystemInterface {
+    /// GIC CPU interface number.
+    pub cpu_interface_number: u32,
+
+    /// ACPI processor UID.
+    pub processor_uid: u32,
+
+    /// Flags of the processor.
+    pub flags: LocalApicFlags,
+
+    /// GSIV of the performance monitoring interrupt.
+    pub performance_interrupt: u32,
+
+    /// Physical address of the GIC CPU interface, for GICv2.
+    pub physical_base_address: u64,
+
+    /// Physical address of the GIC redistributor, for GICv3 and later.
+    /// Zero in structures from before ACPI 5.1.
+    pub gicr_base_address: u64,
+
+    /// MPIDR of the processor. Zero in structures from before ACPI 5.1.
+    pub mpidr: u64,
+}
+
+/// GIC distributor (GICD) structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct GicDistributor {
+    /// GIC ID.
+    pub gic_id: u32,
+
+    /// Physical address of the distributor.
+    pub physical_base_address: u64,
+
+    /// GIC version, or 0 to detect it from the hardware.
+    pub gic_version: u8,
+}
+
+/// GIC MSI frame structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct GicMsiFrame {
+    /// Identifier of the MSI frame.
+    pub msi_frame_id: u32,
+
+    /// Physical address of the MSI frame.
+    pub physical_base_address: u64,
+
+    /// Flags: bit 0 is set if `spi_count` and `spi_base` override the
+    /// values of the `MSI_TYPER` register.
+    pub flags: u32,
+
+    /// Number of SPIs of the frame.
+    pub spi_count: u16,
+
+    /// First SPI of the frame.
+    pub spi_base: u16,
+}
+
+/// GIC redistributor (GICR) structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct GicRedistributor {
+    /// Physical address of the redistributor discovery range.
+    pub discovery_range_base_address: u64,
+
+    /// Length of the discovery range.
+    pub discovery_range_length: u32,
+}
+
+/// GIC interrupt translation service (ITS) structure of the [`Madt`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct GicInterruptTranslationService {
+    /// Identifier of the ITS.
+    pub its_id: u32,
+
+    /// Physical address of the ITS.
+    pub physical_base_address: u64,
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::acpi::tests::build_table;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_madt() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0xfee0_0000u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        // Local APIC.
+        data.extend_from_slice(&[0, 8, 1, 2, 1, 0, 0, 0]);
+        // I/O APIC.
+        data.extend_from_slice(&[1, 12, 3, 0, 0, 0, 0xc0, 0xfe, 0, 0, 0, 0]);
+        // Interrupt source override.
+        data.extend_from_slice(&[2, 10, 0, 0, 2, 0, 0, 0, 5, 0]);
+        // Local APIC NMI.
+        data.extend_from_slice(&[4, 6, 0xff, 5, 0, 1]);
+        // Local x2APIC.
+        data.extend_from_slice(&[9, 16, 0, 0, 0x10, 0, 0, 0, 3, 0, 0, 0, 7, 0, 0, 0]);
+        // Unknown structure.
+        data.extend_from_slice(&[0x80, 3, 0xaa]);
+        // Local APIC that is too short.
+        data.extend_from_slice(&[0, 4, 1, 2]);
+        // Structure with an invalid length.
+        data.extend_from_slice(&[0, 0, 1, 2]);
+        let table = build_table(Signature::MADT, 5, &data);
+        let madt = Madt::try_from(Sdt::parse(&table).unwrap()).unwrap();
+
+        assert_eq!(madt.local_apic_address(), 0xfee0_0000);
+        assert_eq!(madt.flags(), MadtFlags::PCAT_COMPAT);
+        let entries: Vec<_> = madt.entries().collect();
+        assert_eq!(
+            entries,
+            [
+                MadtEntry::LocalApic(LocalApic {
+                    processor_uid: 1,
+                    apic_id: 2,
+                    flags: LocalApicFlags::ENABLED,
+                }),
+                MadtEntry::IoApic(IoApic {
+                    io_apic_id: 3,
+                    address: 0xfec0_0000,
+                    global_system_interrupt_base: 0,
+                }),
+                MadtEntry::InterruptSourceOverride(InterruptSourceOverride {
+                    bus: 0,
+                    source: 0,
+                    global_system_interrupt: 2,
+                    flags: 5,
+                }),
+                MadtEntry::LocalApicNmi(LocalApicNmi {
+                    processor_uid: 0xff,
+                    flags: 5,
+                    lint: 1,
+                }),
+                MadtEntry::LocalX2Apic(LocalX2Apic {
+                    x2apic_id: 0x10,
+                    flags: LocalApicFlags::ENABLED | LocalApicFlags::ONLINE_CAPABLE,
+                    processor_uid: 7,
+                }),
+                MadtEntry::Unknown {
+                    entry_type: 0x80,
+                    bytes: &[0x80, 3, 0xaa],
+                },
+                MadtEntry::Unknown {
+                    entry_type: 0,
+                    bytes: &[0, 4, 1, 2],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! PCI Express memory-mapped configuration space table.
+
+use super::{AcpiError, AcpiTable, Sdt, Signature, check_table, read_u8, read_u16, read_u64};
+use core::slice;
+
+/// Offset of the first configuration space allocation.
+const ENTRIES_OFFSET: usize = 44;
+
+/// Size of a configuration space allocation.
+const ENTRY_SIZE: usize = 16;
+
+/// PCI Express memory-mapped configuration space table (MCFG), with the
+/// signature `MCFG`.
+///
+/// The table lists the Enhanced Configuration Access Mechanism (ECAM)
+/// regions of the PCI segment groups, see [`entries`](Self::entries).
+#[derive(Clone, Copy, Debug)]
+pub struct Mcfg<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> Mcfg<'a> {
+    /// The underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Iterator over the configuration space allocations.
+    #[must_use]
+    pub fn entries(&self) -> McfgEntries<'a> {
+        McfgEntries {
+            entries: self.sdt.as_bytes()[ENTRIES_OFFSET..].chunks_exact(ENTRY_SIZE),
+        }
+    }
+}
+
+impl<'a> TryFrom<Sdt<'a>> for Mcfg<'a> {
+    type Error = AcpiError;
+
+    fn try_from(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        check_table(&sdt, Self::SIGNATURE, ENTRIES_OFFSET)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> AcpiTable<'a> for Mcfg<'a> {
+    const SIGNATURE: Signature = Signature::MCFG;
+}
+
+/// Iterator over the configuration space allocations of an [`Mcfg`].
+#[derive(Clone, Debug)]
+pub struct McfgEntries<'a> {
+    entries: slice::ChunksExact<'a, u8>,
+}
+
+impl Iterator for McfgEntries<'_> {
+    type Item = McfgEntry;
+
+    fn next(&mut self) -> Option<McfgEntry> {
+        let entry = self.entries.next()?;
+        Some(McfgEntry {
+            base_address: read_u64(entry, 0),
+            segment_group: read_u16(entry, 8),
+            start_bus: read_u8(entry, 10),
+            end_bus: read_u8(entry, 11),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl ExactSizeIterator for McfgEntries<'_> {}
+
+/// Configuration space allocation of an [`Mcfg`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct McfgEntry {
+    /// Physical address of the configuration space of bus 0, even if
+    /// `start_bus` is not 0.
+    pub base_address: u64,
+
+    /// PCI segment group number.
+    pub segment_group: u16,
+
+    /// First bus number decoded by the host bridge.
+    pub start_bus: u8,
+
+    /// Last bus number decoded by the host bridge.
+    pub end_bus: u8,
+}
+
+impl McfgEntry {
+    /// Physical address of the 4 KiB configuration space of a PCI function,
+    /// or `None` if `bus` is not in this allocation, `device` or `function`
+    /// is out of range, or the address overflows because the base address
+    /// is corrupt.
+    #[must_use]
+    pub fn config_address(&self, bus: u8, device: u8, function: u8) -> Option<u64> {
+        if !(self.start_bus..=self.end_bus).contains(&bus) || device >= 32 || function >= 8 {
+            return None;
+        }
+        let offset =
+            (u64::from(bus) << 20) | (u64::from(device) << 15) | (u64::from(function) << 12);
+        self.base_address.checked_add(offset)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::acpi::tests::build_table;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_mcfg() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&0xb000_0000u64.to_le_bytes());
+        data.extend_from_slice(&[1, 0, 0, 0xff, 0, 0, 0, 0]);
+        // Incomplete entry.
+        data.extend_from_slice(&[0; 8]);
+        let table = build_table(Signature::MCFG, 1, &data);
+        let mcfg = Mcfg::try_from(Sdt::parse(&table).unwrap()).unwrap();
+
+        let entries: Vec<_> = mcfg.entries().collect();
+        let entry = McfgEntry {
+            base_address: 0xb000_0000,
+            segment_group: 1,
+            start_bus: 0,
+            end_bus: 0xff,
+        };
+        assert_eq!(entries, [entry]);
+        assert_eq!(entry.config_address(0, 0, 0), Some(0xb000_0000));
+        assert_eq!(entry.config_address(1, 2, 3), Some(0xb011_3000));
+        assert_eq!(entry.config_address(0, 32, 0), None);
+
+        let entry = McfgEntry {
+            start_bus: 0x10,
+            end_bus: 0x1f,
+            ..entry
+        };
+        assert_eq!(entry.config_address(0x20, 0, 0), None);
+
+        let entry = McfgEntry {
+            base_address: u64::MAX - 0xfff,
+            ..entry
+        };
+        assert_eq!(entry.config_address(0x10, 0, 0), None);
+    }
+}
@@ -0,0 +1,985 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! ACPI table discovery and parsing.
+//!
+//! The firmware publishes the ACPI tables through the Root System
+//! Description Pointer ([`Rsdp`]), which is found in the configuration table
+//! under [`ConfigTableEntry::ACPI2_GUID`] or [`ConfigTableEntry::ACPI_GUID`].
+//! The RSDP points to the XSDT (or the RSDT on ACPI 1.0 systems), which lists
+//! the addresses of all other tables.
+//!
+//! [`AcpiTables::from_config_table`] finds and validates the RSDP and root
+//! table. The other tables can then be iterated with [`AcpiTables::iter`],
+//! looked up with [`AcpiTables::find`] by [`Signature`], or accessed through
+//! typed views with [`AcpiTables::get`]:
+//!
+//! * [`Fadt`]: Fixed ACPI Description Table.
+//! * [`Madt`]: Multiple APIC Description Table, with the interrupt
+//!   controller structures as [`MadtEntry`].
+//! * [`Mcfg`]: PCI Express memory-mapped configuration space.
+//! * [`Hpet`]: High Precision Event Timer.
+//! * [`Spcr`]: Serial Port Console Redirection table.
+//! * [`Bgrt`]: Boot Graphics Resource Table.
+//!
+//! All tables are checked to have a valid length and checksum before they
+//! are returned. The views read the fields from the table bytes, so the
+//! tables do not have to be aligned. Fields beyond the length of an older,
+//! shorter version of a table read as zero.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::acpi::{AcpiTables, Madt, MadtEntry};
+//!
+//! # fn test() -> Result<(), uefi::acpi::AcpiError> {
+//! let tables = AcpiTables::from_config_table()?;
+//! for table in tables.iter() {
+//!     let table = table?;
+//!     log::info!("{} from {:?}", table.signature(), table.oem_id());
+//! }
+//!
+//! let madt = tables.get::<Madt>()?;
+//! for entry in madt.entries() {
+//!     if let MadtEntry::LocalApic(apic) = entry {
+//!         log::info!("CPU with APIC ID {}", apic.apic_id);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ConfigTableEntry::ACPI2_GUID`]: crate::table::cfg::ConfigTableEntry::ACPI2_GUID
+//! [`ConfigTableEntry::ACPI_GUID`]: crate::table::cfg::ConfigTableEntry::ACPI_GUID
+
+mod bgrt;
+mod fadt;
+mod hpet;
+mod madt;
+mod mcfg;
+mod spcr;
+
+pub use bgrt::Bgrt;
+pub use fadt::{Fadt, FadtFlags, IaPcBootArch};
+pub use hpet::Hpet;
+pub use madt::{
+    GicDistributor, GicInterruptTranslationService, GicMsiFrame, GicRedistributor,
+    GicSystemInterface, InterruptSourceOverride, IoApic, LocalApic, LocalApicAddressOverride,
+    LocalApicFlags, LocalApicNmi, LocalX2Apic, LocalX2ApicNmi, Madt, MadtEntries, MadtEntry,
+    MadtFlags, NmiSource,
+};
+pub use mcfg::{Mcfg, McfgEntries, McfgEntry};
+pub use spcr::Spcr;
+
+use crate::system;
+use crate::table::cfg::ConfigTableEntry;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::slice;
+
+/// Size of the header common to all system description tables.
+const SDT_HEADER_SIZE: usize = 36;
+
+/// Size of an ACPI 1.0 RSDP.
+const RSDP_V1_SIZE: usize = 20;
+
+/// Size of an ACPI 2.0 RSDP.
+const RSDP_V2_SIZE: usize = 36;
+
+/// Four-character signature of a system description table.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(transparent)]
+pub struct Signature(pub [u8; 4]);
+
+impl Signature {
+    /// Boot Graphics Resource Table.
+    pub const BGRT: Self = Self(*b"BGRT");
+    /// Differentiated System Description Table.
+    pub const DSDT: Self = Self(*b"DSDT");
+    /// Fixed ACPI Description Table.
+    pub const FADT: Self = Self(*b"FACP");
+    /// High Precision Event Timer table.
+    pub const HPET: Self = Self(*b"HPET");
+    /// Multiple APIC Description Table.
+    pub const MADT: Self = Self(*b"APIC");
+    /// PCI Express memory-mapped configuration space table.
+    pub const MCFG: Self = Self(*b"MCFG");
+    /// Root System Description Table.
+    pub const RSDT: Self = Self(*b"RSDT");
+    /// Serial Port Console Redirection table.
+    pub const SPCR: Self = Self(*b"SPCR");
+    /// Secondary System Description Table.
+    pub const SSDT: Self = Self(*b"SSDT");
+    /// Extended System Description Table.
+    pub const XSDT: Self = Self(*b"XSDT");
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for c in self.0 {
+            let c = if c.is_ascii_graphic() || c == b' ' {
+                char::from(c)
+            } else {
+                '?'
+            };
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Signature(\"{self}\")")
+    }
+}
+
+/// Error returned when an ACPI table cannot be found or is invalid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum AcpiError {
+    /// The RSDP is not in the configuration table, or there is no table
+    /// with the requested signature.
+    NotFound,
+
+    /// The table does not have the expected signature.
+    InvalidSignature,
+
+    /// The sum of the bytes of the table is not zero.
+    InvalidChecksum,
+
+    /// The length of the table is too small for its contents.
+    InvalidLength,
+
+    /// The table address is null or not addressable.
+    InvalidAddress,
+}
+
+impl Display for AcpiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::NotFound => "the ACPI table was not found",
+            Self::InvalidSignature => "the ACPI table has an unexpected signature",
+            Self::InvalidChecksum => "the ACPI table has an invalid checksum",
+            Self::InvalidLength => "the ACPI table has an invalid length",
+            Self::InvalidAddress => "the ACPI table address is invalid",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for AcpiError {}
+
+/// Typed view of a system description table.
+///
+/// The conversion from [`Sdt`] fails with [`AcpiError::InvalidSignature`]
+/// if the signature is not [`SIGNATURE`], and with
+/// [`AcpiError::InvalidLength`] if the table is too short.
+///
+/// [`SIGNATURE`]: Self::SIGNATURE
+pub trait AcpiTable<'a>: TryFrom<Sdt<'a>, Error = AcpiError> {
+    /// Signature of the table.
+    const SIGNATURE: Signature;
+}
+
+/// Check that `sdt` has the `signature` of a table and is at least
+/// `min_length` bytes long.
+fn check_table(sdt: &Sdt<'_>, signature: Signature, min_length: usize) -> Result<(), AcpiError> {
+    if sdt.signature() != signature {
+        Err(AcpiError::InvalidSignature)
+    } else if sdt.as_bytes().len() < min_length {
+        Err(AcpiError::InvalidLength)
+    } else {
+        Ok(())
+    }
+}
+
+/// Read the `N` bytes at `offset` in `bytes`, or zeros if they are beyond the
+/// end of `bytes`.
+fn field<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    bytes
+        .get(offset..offset + N)
+        .and_then(|field| field.try_into().ok())
+        .unwrap_or([0; N])
+}
+
+fn read_u8(bytes: &[u8], offset: usize) -> u8 {
+    field::<1>(bytes, offset)[0]
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(field(bytes, offset))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(field(bytes, offset))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(field(bytes, offset))
+}
+
+/// Check that the bytes of a table sum to zero.
+fn check_checksum(bytes: &[u8]) -> Result<(), AcpiError> {
+    let sum = bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+    if sum == 0 {
+        Ok(())
+    } else {
+        Err(AcpiError::InvalidChecksum)
+    }
+}
+
+/// Convert a table address to a pointer.
+fn address_to_ptr(address: u64) -> Result<*const u8, AcpiError> {
+    match usize::try_from(address) {
+        Ok(0) | Err(_) => Err(AcpiError::InvalidAddress),
+        Ok(address) => Ok(address as *const u8),
+    }
+}
+
+newtype_enum! {
+    /// Address space of a [`GenericAddress`].
+    pub enum AddressSpace: u8 => {
+        /// System memory.
+        SYSTEM_MEMORY = 0x00,
+        /// System I/O ports.
+        SYSTEM_IO = 0x01,
+        /// PCI configuration space.
+        PCI_CONFIGURATION = 0x02,
+        /// Embedded controller.
+        EMBEDDED_CONTROLLER = 0x03,
+        /// System Management Bus.
+        SMBUS = 0x04,
+        /// System CMOS.
+        SYSTEM_CMOS = 0x05,
+        /// PCI BAR target.
+        PCI_BAR_TARGET = 0x06,
+        /// Intelligent Platform Management Infrastructure.
+        IPMI = 0x07,
+        /// General purpose I/O.
+        GENERAL_PURPOSE_IO = 0x08,
+        /// Generic serial bus.
+        GENERIC_SERIAL_BUS = 0x09,
+        /// Platform Communications Channel.
+        PLATFORM_COMMUNICATIONS_CHANNEL = 0x0a,
+        /// Functional fixed hardware.
+        FUNCTIONAL_FIXED_HARDWARE = 0x7f,
+    }
+}
+
+/// Generic Address Structure, the location of a register.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct GenericAddress {
+    /// Address space of the register.
+    pub address_space: AddressSpace,
+
+    /// Size of the register in bits.
+    pub bit_width: u8,
+
+    /// Offset of the register in bits.
+    pub bit_offset: u8,
+
+    /// Access size: 0 for undefined, 1 for byte, 2 for word, 3 for double
+    /// word and 4 for quad word access.
+    pub access_size: u8,
+
+    /// Address of the register in the address space.
+    pub address: u64,
+}
+
+impl GenericAddress {
+    /// Size of the structure in a table.
+    const SIZE: usize = 12;
+
+    fn read(bytes: &[u8], offset: usize) -> Self {
+        Self {
+            address_space: AddressSpace(read_u8(bytes, offset)),
+            bit_width: read_u8(bytes, offset + 1),
+            bit_offset: read_u8(bytes, offset + 2),
+            access_size: read_u8(bytes, offset + 3),
+            address: read_u64(bytes, offset + 4),
+        }
+    }
+
+    /// Read the structure at `offset`, or `None` if it is beyond the end of
+    /// `bytes` or its address is zero.
+    fn read_optional(bytes: &[u8], offset: usize) -> Option<Self> {
+        if bytes.len() < offset + Self::SIZE {
+            return None;
+        }
+        Some(Self::read(bytes, offset)).filter(|gas| gas.address != 0)
+    }
+}
+
+/// Root System Description Pointer.
+#[derive(Clone, Copy)]
+pub struct Rsdp<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Rsdp<'a> {
+    /// Signature at the start of the RSDP.
+    pub const SIGNATURE: [u8; 8] = *b"RSD PTR ";
+
+    /// Parse and validate an RSDP.
+    ///
+    /// # Errors
+    ///
+    /// * [`AcpiError::InvalidSignature`]: `bytes` does not start with
+    ///   [`SIGNATURE`](Self::SIGNATURE).
+    /// * [`AcpiError::InvalidLength`]: `bytes` is shorter than the RSDP.
+    /// * [`AcpiError::InvalidChecksum`]: the ACPI 1.0 or the extended
+    ///   checksum is invalid.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, AcpiError> {
+        let v1 = bytes.get(..RSDP_V1_SIZE).ok_or(AcpiError::InvalidLength)?;
+        if v1[..8] != Self::SIGNATURE {
+            return Err(AcpiError::InvalidSignature);
+        }
+        check_checksum(v1)?;
+
+        let revision = read_u8(bytes, 15);
+        if revision < 2 {
+            return Ok(Self { bytes: v1 });
+        }
+        let length = read_u32(bytes, 20) as usize;
+        if length < RSDP_V2_SIZE {
+            return Err(AcpiError::InvalidLength);
+        }
+        let bytes = bytes.get(..length).ok_or(AcpiError::InvalidLength)?;
+        check_checksum(bytes)?;
+        Ok(Self { bytes })
+    }
+
+    /// Read and validate the RSDP at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AcpiError::InvalidAddress`] if `ptr` is null, or the errors
+    /// of [`parse`](Self::parse).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to an RSDP that is valid for reads for the lifetime
+    /// `'a`.
+    pub unsafe fn from_ptr(ptr: *const u8) -> Result<Self, AcpiError> {
+        if ptr.is_null() {
+            return Err(AcpiError::InvalidAddress);
+        }
+        // Read the ACPI 1.0 part first to get the revision, then the length
+        // of the ACPI 2.0 structure.
+        let v1 = unsafe { slice::from_raw_parts(ptr, RSDP_V1_SIZE) };
+        let mut length = RSDP_V1_SIZE;
+        if v1[..8] == Self::SIGNATURE && read_u8(v1, 15) >= 2 {
+            let v2 = unsafe { slice::from_raw_parts(ptr, RSDP_V1_SIZE + 4) };
+            length = (read_u32(v2, 20) as usize).max(RSDP_V1_SIZE);
+        }
+        Self::parse(unsafe { slice::from_raw_parts(ptr, length) })
+    }
+
+    /// Revision of the RSDP: 0 for ACPI 1.0, and 2 for later versions.
+    #[must_use]
+    pub fn revision(&self) -> u8 {
+        read_u8(self.bytes, 15)
+    }
+
+    /// OEM identifier.
+    #[must_use]
+    pub fn oem_id(&self) -> [u8; 6] {
+        field(self.bytes, 9)
+    }
+
+    /// Physical address of the RSDT.
+    #[must_use]
+    pub fn rsdt_address(&self) -> u32 {
+        read_u32(self.bytes, 16)
+    }
+
+    /// Physical address of the XSDT, or `None` for an ACPI 1.0 RSDP.
+    #[must_use]
+    pub fn xsdt_address(&self) -> Option<u64> {
+        Some(read_u64(self.bytes, 24)).filter(|address| *address != 0)
+    }
+
+    /// Bytes of the RSDP.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl Debug for Rsdp<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rsdp")
+            .field("revision", &self.revision())
+            .field("oem_id", &self.oem_id())
+            .field("rsdt_address", &self.rsdt_address())
+            .field("xsdt_address", &self.xsdt_address())
+            .finish()
+    }
+}
+
+/// System description table with a standard header.
+///
+/// This is the untyped view of any table. It can be converted to the typed
+/// views of the [module](self) with `TryFrom`.
+#[derive(Clone, Copy)]
+pub struct Sdt<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Sdt<'a> {
+    /// Parse and validate a table. Bytes after the length in the table
+    /// header are ignored.
+    ///
+    /// # Errors
+    ///
+    /// * [`AcpiError::InvalidLength`]: `bytes` is shorter than the table, or
+    ///   the table is shorter than its header.
+    /// * [`AcpiError::InvalidChecksum`]: the checksum of the table is
+    ///   invalid.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, AcpiError> {
+        let length = read_u32(bytes, 4) as usize;
+        if bytes.len() < SDT_HEADER_SIZE || length < SDT_HEADER_SIZE {
+            return Err(AcpiError::InvalidLength);
+        }
+        let bytes = bytes.get(..length).ok_or(AcpiError::InvalidLength)?;
+        check_checksum(bytes)?;
+        Ok(Self { bytes })
+    }
+
+    /// Read and validate the table at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AcpiError::InvalidAddress`] if `ptr` is null, or the errors
+    /// of [`parse`](Self::parse).
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a table that is valid for reads for the lifetime
+    /// `'a`.
+    pub unsafe fn from_ptr(ptr: *const u8) -> Result<Self, AcpiError> {
+        if ptr.is_null() {
+            return Err(AcpiError::InvalidAddress);
+        }
+        let header = unsafe { slice::from_raw_parts(ptr, SDT_HEADER_SIZE) };
+        let length = (read_u32(header, 4) as usize).max(SDT_HEADER_SIZE);
+        Self::parse(unsafe { slice::from_raw_parts(ptr, length) })
+    }
+
+    /// Signature of the table.
+    #[must_use]
+    pub fn signature(&self) -> Signature {
+        Signature(field(self.bytes, 0))
+    }
+
+    /// Length of the table in bytes, including the header.
+    #[must_use]
+    pub fn length(&self) -> u32 {
+        read_u32(self.bytes, 4)
+    }
+
+    /// Revision of the table structure.
+    #[must_use]
+    pub fn revision(&self) -> u8 {
+        read_u8(self.bytes, 8)
+    }
+
+    /// OEM identifier.
+    #[must_use]
+    pub fn oem_id(&self) -> [u8; 6] {
+        field(self.bytes, 10)
+    }
+
+    /// OEM identifier of the table.
+    #[must_use]
+    pub fn oem_table_id(&self) -> [u8; 8] {
+        field(self.bytes, 16)
+    }
+
+    /// OEM revision of the table.
+    #[must_use]
+    pub fn oem_revision(&self) -> u32 {
+        read_u32(self.bytes, 24)
+    }
+
+    /// Vendor identifier of the tool that created the table.
+    #[must_use]
+    pub fn creator_id(&self) -> [u8; 4] {
+        field(self.bytes, 28)
+    }
+
+    /// Revision of the tool that created the table.
+    #[must_use]
+    pub fn creator_revision(&self) -> u32 {
+        read_u32(self.bytes, 32)
+    }
+
+    /// Bytes of the table, including the header.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Bytes of the table after the header.
+    #[must_use]
+    pub fn data(&self) -> &'a [u8] {
+        &self.bytes[SDT_HEADER_SIZE..]
+    }
+}
+
+impl Debug for Sdt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sdt")
+            .field("signature", &self.signature())
+            .field("length", &self.length())
+            .field("revision", &self.revision())
+            .field("oem_id", &self.oem_id())
+            .field("oem_table_id", &self.oem_table_id())
+            .field("oem_revision", &self.oem_revision())
+            .finish()
+    }
+}
+
+/// Read and validate the table at the physical `address`.
+///
+/// # Safety
+///
+/// See [`Sdt::from_ptr`].
+unsafe fn table_at<'a>(address: u64) -> Result<Sdt<'a>, AcpiError> {
+    unsafe { Sdt::from_ptr(address_to_ptr(address)?) }
+}
+
+/// The ACPI tables of the platform, found through the RSDP.
+#[derive(Clone, Copy, Debug)]
+pub struct AcpiTables<'a> {
+    rsdp: Rsdp<'a>,
+    root: Sdt<'a>,
+}
+
+impl AcpiTables<'static> {
+    /// Find the ACPI tables with the RSDP in the configuration table,
+    /// preferring the ACPI 2.0 entry.
+    ///
+    /// The firmware places the ACPI tables in `ACPI_RECLAIM` and
+    /// `ACPI_NON_VOLATILE` memory, which remains identity-mapped after
+    /// exiting boot services. An operating system that reclaims that memory
+    /// or changes the mapping must stop using the tables first.
+    ///
+    /// # Errors
+    ///
+    /// * [`AcpiError::NotFound`]: the configuration table does not contain
+    ///   an RSDP.
+    /// * See [`from_rsdp`](Self::from_rsdp) for the other errors.
+    pub fn from_config_table() -> Result<Self, AcpiError> {
+        let rsdp = system::with_config_table(|entries| {
+            let find = |guid| entries.iter().find(|entry| entry.guid == guid);
+            find(ConfigTableEntry::ACPI2_GUID)
+                .or_else(|| find(ConfigTableEntry::ACPI_GUID))
+                .map(|entry| entry.address.cast::<u8>())
+        })
+        .ok_or(AcpiError::NotFound)?;
+        // SAFETY: the firmware provides a valid RSDP and tables, see above.
+        unsafe { Self::from_rsdp(rsdp) }
+    }
+}
+
+impl<'a> AcpiTables<'a> {
+    /// Read the ACPI tables from the RSDP at `rsdp`. The XSDT is used if
+    /// the RSDP has one, otherwise the RSDT.
+    ///
+    /// # Errors
+    ///
+    /// * [`AcpiError::InvalidSignature`]: `rsdp` does not point to an RSDP,
+    ///   or the root table is not an XSDT or RSDT.
+    /// * [`AcpiError::InvalidChecksum`], [`AcpiError::InvalidLength`]: the
+    ///   RSDP or the root table is invalid.
+    /// * [`AcpiError::InvalidAddress`]: `rsdp` or the root table address is
+    ///   null or not addressable.
+    ///
+    /// # Safety
+    ///
+    /// `rsdp` must point to an RSDP, and the RSDP and all the tables it
+    /// references must be valid for reads at their physical address for the
+    /// lifetime `'a`.
+    pub unsafe fn from_rsdp(rsdp: *const u8) -> Result<Self, AcpiError> {
+        let rsdp = unsafe { Rsdp::from_ptr(rsdp) }?;
+        let (root, signature) = match rsdp.xsdt_address() {
+            Some(xsdt) => (unsafe { table_at(xsdt) }?, Signature::XSDT),
+            None => (
+                unsafe { table_at(rsdp.rsdt_address().into()) }?,
+                Signature::RSDT,
+            ),
+        };
+        if root.signature() != signature {
+            return Err(AcpiError::InvalidSignature);
+        }
+        Ok(Self { rsdp, root })
+    }
+
+    /// The RSDP.
+    #[must_use]
+    pub const fn rsdp(&self) -> Rsdp<'a> {
+        self.rsdp
+    }
+
+    /// The root table, either the XSDT or the RSDT.
+    #[must_use]
+    pub const fn root(&self) -> Sdt<'a> {
+        self.root
+    }
+
+    /// Iterator over the physical addresses of the tables listed in the
+    /// root table.
+    #[must_use]
+    pub fn addresses(&self) -> TableAddresses<'a> {
+        let entry_size = if self.root.signature() == Signature::XSDT {
+            8
+        } else {
+            4
+        };
+        TableAddresses {
+            entries: self.root.data().chunks_exact(entry_size),
+        }
+    }
+
+    /// Iterator over the tables listed in the root table. Each table is
+    /// validated, and invalid tables are returned as errors.
+    #[must_use]
+    pub fn iter(&self) -> AcpiTableIter<'a> {
+        AcpiTableIter {
+            addresses: self.addresses(),
+        }
+    }
+
+    /// Get the first table with `signature`.
+    ///
+    /// # Errors
+    ///
+    /// * [`AcpiError::NotFound`]: no table has `signature`.
+    /// * See [`Sdt::parse`] for the errors if the table is invalid.
+    pub fn find(&self, signature: Signature) -> Result<Sdt<'a>, AcpiError> {
+        for address in self.addresses() {
+            let Ok(ptr) = address_to_ptr(address) else {
+                continue;
+            };
+            // SAFETY: the tables are valid per the requirements of
+            // `from_rsdp`. Only the signature is read before validating the
+            // table.
+            let table_signature = Signature(unsafe { ptr.cast::<[u8; 4]>().read_unaligned() });
+            if table_signature == signature {
+                return unsafe { Sdt::from_ptr(ptr) };
+            }
+        }
+        Err(AcpiError::NotFound)
+    }
+
+    /// Get the first table of type `T`.
+    ///
+    /// # Errors
+    ///
+    /// See [`find`](Self::find), and [`AcpiTable`] for the errors of the
+    /// conversion.
+    pub fn get<T: AcpiTable<'a>>(&self) -> Result<T, AcpiError> {
+        T::try_from(self.find(T::SIGNATURE)?)
+    }
+
+    /// Get the DSDT, which is referenced by the FADT rather than the root
+    /// table.
+    ///
+    /// # Errors
+    ///
+    /// * [`AcpiError::NotFound`]: there is no FADT, or it does not reference
+    ///   a DSDT.
+    /// * [`AcpiError::InvalidSignature`]: the table referenced by the FADT
+    ///   is not a DSDT.
+    /// * See [`Sdt::parse`] for the errors if a table is invalid.
+    pub fn dsdt(&self) -> Result<Sdt<'a>, AcpiError> {
+        let fadt = self.get::<Fadt<'a>>()?;
+        let address = fadt.dsdt_address().ok_or(AcpiError::NotFound)?;
+        // SAFETY: the tables are valid per the requirements of `from_rsdp`.
+        let dsdt = unsafe { table_at(address) }?;
+        if dsdt.signature() != Signature::DSDT {
+            return Err(AcpiError::InvalidSignature);
+        }
+        Ok(dsdt)
+    }
+}
+
+impl<'a> IntoIterator for &AcpiTables<'a> {
+    type Item = Result<Sdt<'a>, AcpiError>;
+    type IntoIter = AcpiTableIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the physical addresses of the tables listed in the root
+/// table. See [`AcpiTables::addresses`].
+#[derive(Clone, Debug)]
+pub struct TableAddresses<'a> {
+    entries: slice::ChunksExact<'a, u8>,
+}
+
+impl Iterator for TableAddresses<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let entry = self.entries.next()?;
+        Some(if entry.len() == 8 {
+            read_u64(entry, 0)
+        } else {
+            read_u32(entry, 0).into()
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TableAddresses<'_> {}
+
+/// Iterator over the tables listed in the root table. See
+/// [`AcpiTables::iter`].
+#[derive(Clone, Debug)]
+pub struct AcpiTableIter<'a> {
+    addresses: TableAddresses<'a>,
+}
+
+impl<'a> Iterator for AcpiTableIter<'a> {
+    type Item = Result<Sdt<'a>, AcpiError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let address = self.addresses.next()?;
+        // SAFETY: the tables are valid per the requirements of
+        // `AcpiTables::from_rsdp`.
+        Some(unsafe { table_at(address) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.addresses.size_hint()
+    }
+}
+
+impl ExactSizeIterator for AcpiTableIter<'_> {}
+
+#[cfg(all(test, feature = "alloc"))]
+pub(crate) mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    /// Build a table with a valid checksum from its signature and the data
+    /// after the header.
+    pub(crate) fn build_table(signature: Signature, revision: u8, data: &[u8]) -> Vec<u8> {
+        let mut table = Vec::new();
+        table.extend_from_slice(&signature.0);
+        let length = u32::try_from(SDT_HEADER_SIZE + data.len()).unwrap();
+        table.extend_from_slice(&length.to_le_bytes());
+        table.push(revision);
+        table.push(0); // Checksum.
+        table.extend_from_slice(b"UEFIRS");
+        table.extend_from_slice(b"TESTTABL");
+        table.extend_from_slice(&1u32.to_le_bytes());
+        table.extend_from_slice(b"RUST");
+        table.extend_from_slice(&2u32.to_le_bytes());
+        table.extend_from_slice(data);
+        fix_checksum(&mut table, 9);
+        table
+    }
+
+    /// Set the checksum byte at `index` so that `bytes` sum to zero.
+    pub(crate) fn fix_checksum(bytes: &mut [u8], index: usize) {
+        bytes[index] = 0;
+        let sum = bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+        bytes[index] = 0u8.wrapping_sub(sum);
+    }
+
+    fn build_rsdp(revision: u8, rsdt: u32, xsdt: u64) -> Vec<u8> {
+        let mut rsdp = Vec::new();
+        rsdp.extend_from_slice(&Rsdp::SIGNATURE);
+        rsdp.push(0); // Checksum.
+        rsdp.extend_from_slice(b"UEFIRS");
+        rsdp.push(revision);
+        rsdp.extend_from_slice(&rsdt.to_le_bytes());
+        fix_checksum(&mut rsdp, 8);
+        if revision >= 2 {
+            rsdp.extend_from_slice(&36u32.to_le_bytes());
+            rsdp.extend_from_slice(&xsdt.to_le_bytes());
+            rsdp.push(0); // Extended checksum.
+            rsdp.extend_from_slice(&[0; 3]);
+            fix_checksum(&mut rsdp, 32);
+        }
+        rsdp
+    }
+
+    fn address(table: &[u8]) -> u64 {
+        table.as_ptr() as u64
+    }
+
+    #[test]
+    fn test_sdt_parse() {
+        let mut table = build_table(Signature::SSDT, 2, &[1, 2, 3]);
+        let sdt = Sdt::parse(&table).unwrap();
+        assert_eq!(sdt.signature(), Signature::SSDT);
+        assert_eq!(sdt.length(), 39);
+        assert_eq!(sdt.revision(), 2);
+        assert_eq!(&sdt.oem_id(), b"UEFIRS");
+        assert_eq!(&sdt.oem_table_id(), b"TESTTABL");
+        assert_eq!(sdt.oem_revision(), 1);
+        assert_eq!(&sdt.creator_id(), b"RUST");
+        assert_eq!(sdt.creator_revision(), 2);
+        assert_eq!(sdt.data(), [1, 2, 3]);
+
+        // Trailing bytes are ignored.
+        table.push(0xff);
+        assert_eq!(Sdt::parse(&table).unwrap().as_bytes().len(), 39);
+        table.pop();
+
+        assert_eq!(
+            Sdt::parse(&table[..38]).unwrap_err(),
+            AcpiError::InvalidLength
+        );
+        table[36] = 2;
+        assert_eq!(Sdt::parse(&table).unwrap_err(), AcpiError::InvalidChecksum);
+    }
+
+    #[test]
+    fn test_signature_display() {
+        assert_eq!(Signature::MADT.to_string(), "APIC");
+        assert_eq!(Signature(*b"A\0B\xff").to_string(), "A?B?");
+        assert_eq!(format!("{:?}", Signature::FADT), "Signature(\"FACP\")");
+    }
+
+    #[test]
+    fn test_rsdp_parse() {
+        let v1 = build_rsdp(0, 0x1234, 0);
+        let rsdp = Rsdp::parse(&v1).unwrap();
+        assert_eq!(rsdp.revision(), 0);
+        assert_eq!(&rsdp.oem_id(), b"UEFIRS");
+        assert_eq!(rsdp.rsdt_address(), 0x1234);
+        assert_eq!(rsdp.xsdt_address(), None);
+
+        let mut v2 = build_rsdp(2, 0x1234, 0x5678);
+        let rsdp = Rsdp::parse(&v2).unwrap();
+        assert_eq!(rsdp.revision(), 2);
+        assert_eq!(rsdp.xsdt_address(), Some(0x5678));
+        assert_eq!(rsdp.as_bytes().len(), 36);
+
+        v2[24] = 0;
+        assert_eq!(Rsdp::parse(&v2).unwrap_err(), AcpiError::InvalidChecksum);
+        assert_eq!(
+            Rsdp::parse(&v2[..30]).unwrap_err(),
+            AcpiError::InvalidLength
+        );
+        v2[0] = b'X';
+        assert_eq!(Rsdp::parse(&v2).unwrap_err(), AcpiError::InvalidSignature);
+    }
+
+    #[test]
+    fn test_acpi_tables() {
+        let hpet = build_table(Signature::HPET, 1, &[0; 20]);
+        let ssdt = build_table(Signature::SSDT, 2, &[]);
+        let mut invalid = build_table(Signature::SPCR, 2, &[0; 44]);
+        invalid[9] = invalid[9].wrapping_add(1);
+
+        let mut entries = Vec::new();
+        for table in [&hpet, &ssdt, &invalid] {
+            entries.extend_from_slice(&address(table).to_le_bytes());
+        }
+        let xsdt = build_table(Signature::XSDT, 1, &entries);
+        let rsdp = build_rsdp(2, 0, address(&xsdt));
+
+        let tables = unsafe { AcpiTables::from_rsdp(rsdp.as_ptr()) }.unwrap();
+        assert_eq!(tables.root().signature(), Signature::XSDT);
+        assert_eq!(tables.addresses().len(), 3);
+
+        let signatures: Vec<_> = tables
+            .iter()
+            .map(|table| table.map(|table| table.signature()))
+            .collect();
+        assert_eq!(
+            signatures,
+            [
+                Ok(Signature::HPET),
+                Ok(Signature::SSDT),
+                Err(AcpiError::InvalidChecksum)
+            ]
+        );
+
+        assert_eq!(
+            tables.find(Signature::SSDT).unwrap().as_bytes().as_ptr(),
+            ssdt.as_ptr()
+        );
+        assert_eq!(
+            tables.find(Signature::SPCR).unwrap_err(),
+            AcpiError::InvalidChecksum
+        );
+        assert_eq!(
+            tables.find(Signature::MCFG).unwrap_err(),
+            AcpiError::NotFound
+        );
+        assert!(tables.get::<Hpet>().is_ok());
+        assert_eq!(tables.get::<Fadt>().unwrap_err(), AcpiError::NotFound);
+    }
+
+    #[test]
+    fn test_acpi_tables_rsdt() {
+        // Addresses in an RSDT are 32-bit, so only test the root table.
+        let rsdt = build_table(Signature::RSDT, 1, &[]);
+        let Ok(rsdt_address) = u32::try_from(address(&rsdt)) else {
+            return;
+        };
+        let rsdp = build_rsdp(0, rsdt_address, 0);
+        let tables = unsafe { AcpiTables::from_rsdp(rsdp.as_ptr()) }.unwrap();
+        assert_eq!(tables.root().signature(), Signature::RSDT);
+        assert_eq!(tables.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_acpi_tables_invalid_root() {
+        let ssdt = build_table(Signature::SSDT, 1, &[]);
+        let rsdp = build_rsdp(2, 0, address(&ssdt));
+        assert_eq!(
+            unsafe { AcpiTables::from_rsdp(rsdp.as_ptr()) }.unwrap_err(),
+            AcpiError::InvalidSignature
+        );
+
+        let rsdp = build_rsdp(0, 0, 0);
+        assert_eq!(
+            unsafe { AcpiTables::from_rsdp(rsdp.as_ptr()) }.unwrap_err(),
+            AcpiError::InvalidAddress
+        );
+    }
+
+    #[test]
+    fn test_dsdt() {
+        let dsdt = build_table(Signature::DSDT, 2, &[0x10; 4]);
+        let mut fadt_data = [0u8; 276 - SDT_HEADER_SIZE];
+        fadt_data[140 - SDT_HEADER_SIZE..148 - SDT_HEADER_SIZE]
+            .copy_from_slice(&address(&dsdt).to_le_bytes());
+        let fadt = build_table(Signature::FADT, 6, &fadt_data);
+        let xsdt = build_table(Signature::XSDT, 1, &address(&fadt).to_le_bytes());
+        let rsdp = build_rsdp(2, 0, address(&xsdt));
+
+        let tables = unsafe { AcpiTables::from_rsdp(rsdp.as_ptr()) }.unwrap();
+        assert_eq!(tables.dsdt().unwrap().data(), [0x10; 4]);
+    }
+
+    #[test]
+    fn test_generic_address() {
+        let bytes = [0, 1, 8, 0, 1, 0x60, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            GenericAddress::read_optional(&bytes, 1),
+            Some(GenericAddress {
+                address_space: AddressSpace::SYSTEM_IO,
+                bit_width: 8,
+                bit_offset: 0,
+                access_size: 1,
+                address: 0x60,
+            })
+        );
+        assert_eq!(GenericAddress::read_optional(&bytes, 2), None);
+        assert_eq!(GenericAddress::read_optional(&[0; 12], 0), None);
+    }
+}
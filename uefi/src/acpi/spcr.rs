@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Serial Port Console Redirection table.
+
+use super::{
+    AcpiError, AcpiTable, GenericAddress, Sdt, Signature, check_table, read_u8, read_u16, read_u32,
+};
+
+/// Size of the revision 1 table.
+const MIN_LENGTH: usize = 80;
+
+/// Serial Port Console Redirection table (SPCR), with the signature `SPCR`.
+///
+/// The SPCR describes the serial port that the firmware uses as its
+/// console, which the operating system can keep using.
+#[derive(Clone, Copy, Debug)]
+pub struct Spcr<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> Spcr<'a> {
+    /// The underlying table.
+    #[must_use]
+    pub const fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    const fn bytes(&self) -> &'a [u8] {
+        self.sdt.as_bytes()
+    }
+
+    /// Interface type of the serial port, such as 0 for a 16550-compatible
+    /// UART or 3 for an ARM PL011 UART. See the Debug Port Table 2
+    /// specification for the full list.
+    #[must_use]
+    pub fn interface_type(&self) -> u8 {
+        read_u8(self.bytes(), 36)
+    }
+
+    /// Address of the serial port registers.
+    #[must_use]
+    pub fn base_address(&self) -> GenericAddress {
+        GenericAddress::read(self.bytes(), 40)
+    }
+
+    /// Interrupt types supported by the serial port: bit 0 for a PC-AT
+    /// compatible IRQ, bit 1 for an I/O APIC interrupt, bit 2 for an I/O
+    /// SAPIC interrupt, bit 3 for an ARMH GIC interrupt and bit 4 for a
+    /// RISC-V PLIC/APLIC interrupt.
+    #[must_use]
+    pub fn interrupt_type(&self) -> u8 {
+        read_u8(self.bytes(), 52)
+    }
+
+    /// PC-AT compatible IRQ of the serial port.
+    #[must_use]
+    pub fn irq(&self) -> u8 {
+        read_u8(self.bytes(), 53)
+    }
+
+    /// Global system interrupt of the serial port.
+    #[must_use]
+    pub fn global_system_interrupt(&self) -> u32 {
+        read_u32(self.bytes(), 54)
+    }
+
+    /// Baud rate of the serial port, or `None` if the firmware relies on
+    /// the current configuration of the port.
+    #[must_use]
+    pub fn baud_rate(&self) -> Option<u32> {
+        if self.sdt.revision() >= 4 {
+            let precise = read_u32(self.bytes(), 80);
+            if precise != 0 {
+                return Some(precise);
+            }
+        }
+        match read_u8(self.bytes(), 58) {
+            3 => Some(9600),
+            4 => Some(19200),
+            6 => Some(57600),
+            7 => Some(115_200),
+            _ => None,
+        }
+    }
+
+    /// Parity: 0 for no parity.
+    #[must_use]
+    pub fn parity(&self) -> u8 {
+        read_u8(self.bytes(), 59)
+    }
+
+    /// Stop bits: 1 for one stop bit.
+    #[must_use]
+    pub fn stop_bits(&self) -> u8 {
+        read_u8(self.bytes(), 60)
+    }
+
+    /// Flow control flags: bit 0 for DCD, bit 1 for RTS/CTS and bit 2 for
+    /// XON/XOFF.
+    #[must_use]
+    pub fn flow_control(&self) -> u8 {
+        read_u8(self.bytes(), 61)
+    }
+
+    /// Terminal type: 0 for VT100, 1 for extended VT100, 2 for VT-UTF8 and
+    /// 3 for ANSI.
+    #[must_use]
+    pub fn terminal_type(&self) -> u8 {
+        read_u8(self.bytes(), 62)
+    }
+
+    /// PCI device ID of the serial port, or `0xffff` if it is not a PCI
+    /// device.
+    #[must_use]
+    pub fn pci_device_id(&self) -> u16 {
+        read_u16(self.bytes(), 64)
+    }
+
+    /// PCI vendor ID of the serial port, or `0xffff` if it is not a PCI
+    /// device.
+    #[must_use]
+    pub fn pci_vendor_id(&self) -> u16 {
+        read_u16(self.bytes(), 66)
+    }
+
+    /// PCI location of the serial port as `(segment, bus, device,
+    /// function)`, or `None` if it is not a PCI device.
+    #[must_use]
+    pub fn pci_location(&self) -> Option<(u8, u8, u8, u8)> {
+        if self.pci_device_id() == 0xffff && self.pci_vendor_id() == 0xffff {
+            return None;
+        }
+        Some((
+            read_u8(self.bytes(), 75),
+            read_u8(self.bytes(), 68),
+            read_u8(self.bytes(), 69),
+            read_u8(self.bytes(), 70),
+        ))
+    }
+
+    /// Frequency of the UART clock in Hz, or `None` if unknown. Present
+    /// from revision 3 of the table.
+    #[must_use]
+    pub fn uart_clock_frequency(&self) -> Option<u32> {
+        if self.sdt.revision() < 3 {
+            return None;
+        }
+        Some(read_u32(self.bytes(), 76)).filter(|frequency| *frequency != 0)
+    }
+}
+
+impl<'a> TryFrom<Sdt<'a>> for Spcr<'a> {
+    type Error = AcpiError;
+
+    fn try_from(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        check_table(&sdt, Self::SIGNATURE, MIN_LENGTH)?;
+        Ok(Self { sdt })
+    }
+}
+
+impl<'a> AcpiTable<'a> for Spcr<'a> {
+    const SIGNATURE: Signature = Signature::SPCR;
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::acpi::AddressSpace;
+    use crate::acpi::tests::build_table;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_spcr() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(&[1, 8, 0, 1]);
+        data.extend_from_slice(&0x3f8u64.to_le_bytes());
+        data.extend_from_slice(&[1, 4, 4, 0, 0, 0, 7, 0, 1, 2, 0, 0]);
+        data.extend_from_slice(&[0xff; 4]);
+        data.extend_from_slice(&[0; 12]);
+        let table = build_table(Signature::SPCR, 2, &data);
+        let spcr = Spcr::try_from(Sdt::parse(&table).unwrap()).unwrap();
+
+        assert_eq!(spcr.interface_type(), 0);
+        assert_eq!(spcr.base_address().address_space, AddressSpace::SYSTEM_IO);
+        assert_eq!(spcr.base_address().address, 0x3f8);
+        assert_eq!(spcr.interrupt_type(), 1);
+        assert_eq!(spcr.irq(), 4);
+        assert_eq!(spcr.global_system_interrupt(), 4);
+        assert_eq!(spcr.baud_rate(), Some(115_200));
+        assert_eq!(spcr.stop_bits(), 1);
+        assert_eq!(spcr.flow_control(), 2);
+        assert_eq!(spcr.pci_location(), None);
+        assert_eq!(spcr.uart_clock_frequency(), None);
+    }
+}
@@ -21,6 +21,16 @@
 //!
 //! [`proto`]: crate::proto
 
+mod handle_database;
+
+pub use handle_database::protocol_name;
+#[cfg(feature = "alloc")]
+pub use handle_database::{
+    HandleDatabase, HandleDatabaseParseError, HandleInfo, OpenProtocolInfo, ProtocolInfo,
+    handle_database,
+};
+/// Attributes of an open protocol, as reported by [`open_protocol_information`].
+pub use uefi_raw::table::boot::OpenProtocolAttributes as OpenProtocolAttributeFlags;
 pub use uefi_raw::table::boot::{
     EventType, MemoryAttribute, MemoryDescriptor, MemoryType, PAGE_SIZE, Tpl,
 };
@@ -37,7 +47,7 @@ use crate::proto::{BootPolicy, Protocol, ProtocolPointer};
 use crate::runtime::{self, ResetType};
 use crate::table::Revision;
 use crate::util::opt_nonnull_to_ptr;
-use crate::{Char16, Error, Event, Guid, Handle, Result, Status, StatusExt, table};
+use crate::{Char16, Error, Event, Guid, Handle, Result, ResultExt, Status, StatusExt, table};
 use core::ffi::c_void;
 use core::mem::MaybeUninit;
 use core::ops::{Deref, DerefMut};
@@ -47,7 +57,13 @@ use core::time::Duration;
 use core::{mem, slice};
 use uefi_raw::table::boot::{AllocateType as RawAllocateType, InterfaceType, TimerDelay};
 #[cfg(feature = "alloc")]
-use {alloc::vec::Vec, uefi::ResultExt};
+use {
+    crate::proto::device_path::build::{self, DevicePathBuilder},
+    crate::sync::TplMutex,
+    crate::{CStr16, CString16},
+    alloc::boxed::Box,
+    alloc::vec::Vec,
+};
 
 /// Global image handle. This is only set by [`set_image_handle`], and it is
 /// only read by [`image_handle`].
@@ -125,6 +141,8 @@ pub unsafe fn raise_tpl(tpl: Tpl) -> TplGuard {
 ///
 /// The buffer will be [`PAGE_SIZE`] aligned. Callers are responsible for
 /// freeing the memory using [`free_pages`].
+/// [`Pages`] and [`PageBox`] wrap this function, and free the memory
+/// automatically.
 ///
 /// # Arguments
 /// - `allocation_type`: The [`AllocateType`] to choose the allocation strategy.
@@ -145,6 +163,9 @@ pub unsafe fn raise_tpl(tpl: Tpl) -> TplGuard {
 /// * [`Status::INVALID_PARAMETER`]: `mem_ty` is [`MemoryType::PERSISTENT_MEMORY`],
 ///   [`MemoryType::UNACCEPTED`], or in the range [`MemoryType::MAX`]`..=0x6fff_ffff`.
 /// * [`Status::NOT_FOUND`]: the requested pages could not be found.
+///
+/// [`Pages`]: crate::mem::Pages
+/// [`PageBox`]: crate::mem::PageBox
 pub fn allocate_pages(
     allocation_type: AllocateType,
     memory_type: MemoryType,
@@ -506,6 +527,263 @@ pub unsafe fn create_event_ex(
     )
 }
 
+/// Creates an event whose notification function is a Rust closure.
+///
+/// This is a safe alternative to [`create_event`]. The closure is boxed and
+/// passed to the firmware as the notification context, so it can capture any
+/// state it needs instead of recovering it from a raw pointer.
+///
+/// The closure is invoked at the `notify_tpl` priority level, in the same way
+/// as a raw notification function. It is only called for event types that
+/// include [`EventType::NOTIFY_WAIT`] or [`EventType::NOTIFY_SIGNAL`].
+///
+/// The returned [`OwnedEvent`] closes the event and frees the closure when
+/// dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// use uefi::boot::{self, EventType, Tpl};
+///
+/// static SIGNALED: AtomicBool = AtomicBool::new(false);
+///
+/// # fn test() -> uefi::Result {
+/// let event = boot::create_event_with(EventType::NOTIFY_SIGNAL, Tpl::CALLBACK, |_event| {
+///     SIGNALED.store(true, Ordering::Relaxed);
+/// })?;
+/// boot::signal_event(&event)?;
+/// assert!(SIGNALED.load(Ordering::Relaxed));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: an invalid combination of parameters was provided.
+/// * [`Status::OUT_OF_RESOURCES`]: the event could not be allocated.
+#[cfg(feature = "alloc")]
+pub fn create_event_with<F>(
+    event_ty: EventType,
+    notify_tpl: Tpl,
+    notify_fn: F,
+) -> Result<OwnedEvent>
+where
+    F: FnMut(Event) + 'static,
+{
+    create_event_with_closure(event_ty, notify_tpl, notify_fn, None)
+}
+
+/// Shared implementation of [`create_event_with`] and
+/// [`create_event_group_with`].
+#[cfg(feature = "alloc")]
+fn create_event_with_closure<F>(
+    event_ty: EventType,
+    notify_tpl: Tpl,
+    notify_fn: F,
+    event_group: Option<&'static Guid>,
+) -> Result<OwnedEvent>
+where
+    F: FnMut(Event) + 'static,
+{
+    let closure = OwnedEventClosure::new(notify_fn);
+
+    // SAFETY: the context pointer is valid for the trampoline, and remains
+    // valid until the event has been closed. The event group is a valid GUID.
+    let event = unsafe {
+        match event_group {
+            Some(group) => create_event_ex(
+                event_ty,
+                notify_tpl,
+                Some(closure.trampoline),
+                Some(closure.ctx()),
+                Some(NonNull::from(group)),
+            ),
+            None => create_event(
+                event_ty,
+                notify_tpl,
+                Some(closure.trampoline),
+                Some(closure.ctx()),
+            ),
+        }
+    };
+
+    match event {
+        Ok(event) => Ok(OwnedEvent {
+            event,
+            closure: Some(closure),
+        }),
+        Err(err) => {
+            // SAFETY: the firmware did not create the event, so nothing else
+            // refers to the closure.
+            unsafe { closure.free() };
+            Err(err)
+        }
+    }
+}
+
+/// An event group, for use with [`create_event_group_with`] and
+/// [`signal_event_group`].
+///
+/// When an event in a group is signaled, all events in the group are
+/// signaled. The UEFI Specification defines groups for a number of points in
+/// the platform's lifecycle, which are signaled by the firmware.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum EventGroup {
+    /// Signaled when [`exit_boot_services`] is called, after
+    /// [`BeforeExitBootServices`].
+    ///
+    /// Notification functions run while the firmware is exiting boot
+    /// services, so they must not allocate or free memory, and must not use
+    /// any protocols or boot services other than event services.
+    ///
+    /// [`BeforeExitBootServices`]: Self::BeforeExitBootServices
+    ExitBootServices,
+
+    /// Signaled when [`exit_boot_services`] is called, before
+    /// [`ExitBootServices`]. Notification functions may use boot services,
+    /// but must not allocate or free memory.
+    ///
+    /// This group was added in UEFI 2.8, and is not signaled by older
+    /// firmware.
+    ///
+    /// [`ExitBootServices`]: Self::ExitBootServices
+    BeforeExitBootServices,
+
+    /// Signaled when [`runtime::set_virtual_address_map`] is called, after
+    /// boot services have been exited. Notification functions may only call
+    /// [`runtime::convert_pointer`] to convert their pointers to virtual
+    /// addresses.
+    ///
+    /// This is only useful for runtime drivers: the notification function
+    /// and all the data it uses, including the closure passed to
+    /// [`create_event_group_with`], must be in runtime services memory.
+    VirtualAddressChange,
+
+    /// Signaled when the memory map changes.
+    MemoryMapChange,
+
+    /// Signaled by the boot manager before it attempts to boot a boot
+    /// option.
+    ReadyToBoot,
+
+    /// Signaled by the boot manager after [`ReadyToBoot`].
+    ///
+    /// [`ReadyToBoot`]: Self::ReadyToBoot
+    AfterReadyToBoot,
+
+    /// Signaled when [`runtime::reset`] is called, before the platform is
+    /// reset.
+    ResetSystem,
+
+    /// A group defined by a driver or application.
+    Custom(&'static Guid),
+}
+
+impl EventGroup {
+    /// The GUID identifying the group.
+    #[must_use]
+    pub const fn guid(self) -> &'static Guid {
+        use uefi_raw::table::boot as raw;
+
+        match self {
+            Self::ExitBootServices => &raw::EVENT_GROUP_EXIT_BOOT_SERVICES,
+            Self::BeforeExitBootServices => &raw::EVENT_GROUP_BEFORE_EXIT_BOOT_SERVICES,
+            Self::VirtualAddressChange => &raw::EVENT_GROUP_VIRTUAL_ADDRESS_CHANGE,
+            Self::MemoryMapChange => &raw::EVENT_GROUP_MEMORY_MAP_CHANGE,
+            Self::ReadyToBoot => &raw::EVENT_GROUP_READY_TO_BOOT,
+            Self::AfterReadyToBoot => &raw::EVENT_GROUP_AFTER_READY_TO_BOOT,
+            Self::ResetSystem => &raw::EVENT_GROUP_RESET_SYSTEM,
+            Self::Custom(guid) => guid,
+        }
+    }
+}
+
+/// Creates an event in an event group, with a Rust closure as its
+/// notification function.
+///
+/// This is a safe wrapper around [`create_event_ex`], in the same way as
+/// [`create_event_with`]. The closure is called with `notify_tpl` whenever
+/// the group is signaled. See [`EventGroup`] for restrictions on what the
+/// closure may do for the groups signaled by the firmware.
+///
+/// The event is removed from the group and closed when the returned
+/// [`OwnedEvent`] is dropped. For groups that are signaled while exiting
+/// boot services or later, the event must be kept alive with
+/// [`OwnedEvent::leak`].
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::boot::{self, EventGroup, Tpl};
+///
+/// # fn test() -> uefi::Result {
+/// let _ = boot::create_event_group_with(EventGroup::ReadyToBoot, Tpl::CALLBACK, |_event| {
+///     log::info!("about to boot");
+/// })?
+/// .leak();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `notify_tpl` is not a valid task priority level.
+/// * [`Status::OUT_OF_RESOURCES`]: the event could not be allocated.
+/// * [`Status::UNSUPPORTED`]: the firmware does not support event groups.
+#[cfg(feature = "alloc")]
+pub fn create_event_group_with<F>(
+    group: EventGroup,
+    notify_tpl: Tpl,
+    notify_fn: F,
+) -> Result<OwnedEvent>
+where
+    F: FnMut(Event) + 'static,
+{
+    create_event_with_closure(
+        EventType::NOTIFY_SIGNAL,
+        notify_tpl,
+        notify_fn,
+        Some(group.guid()),
+    )
+}
+
+/// Signals all events in `group`.
+///
+/// This creates a temporary event in the group, signals it, and closes it
+/// again.
+///
+/// # Safety
+///
+/// Signaling a group runs the notification functions of all its events. The
+/// caller must ensure that the members of the group expect to be signaled
+/// at this point. In particular, the groups signaled by the firmware itself,
+/// such as [`EventGroup::ExitBootServices`], must not be signaled manually.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: the event could not be allocated.
+/// * [`Status::UNSUPPORTED`]: the firmware does not support event groups.
+pub unsafe fn signal_event_group(group: EventGroup) -> Result {
+    const extern "efiapi" fn noop(_event: Event, _ctx: Option<NonNull<c_void>>) {}
+
+    // SAFETY: the group is a valid GUID, and the notification function does
+    // not use its context.
+    let event = unsafe {
+        create_event_ex(
+            EventType::NOTIFY_SIGNAL,
+            Tpl::CALLBACK,
+            Some(noop),
+            None,
+            Some(NonNull::from(group.guid())),
+        )
+    }?;
+
+    let result = signal_event(&event);
+    close_event(event)?;
+    result
+}
+
 /// Checks to see if an event is signaled, without blocking execution to wait for it.
 ///
 /// Returns `Ok(true)` if the event is in the signaled state or `Ok(false)`
@@ -592,6 +870,12 @@ pub fn set_timer(event: &Event, trigger_time: TimerTrigger) -> Result {
     unsafe { (bt.set_timer)(event.as_ptr(), ty, time) }.to_result()
 }
 
+/// Convert a [`Duration`] to the 100ns units used by [`TimerTrigger`],
+/// saturating at `u64::MAX`.
+pub(crate) fn duration_to_timer_units(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos() / 100).unwrap_or(u64::MAX)
+}
+
 /// Stops execution until an event is signaled.
 ///
 /// This function must be called at priority level [`Tpl::APPLICATION`].
@@ -645,6 +929,42 @@ pub fn wait_for_event(events: &mut [Event]) -> Result<usize, Option<usize>> {
     )
 }
 
+/// Waits until `event` is signaled, or until `timeout` has elapsed.
+///
+/// Returns `Ok(true)` if the event was signaled, and `Ok(false)` if the
+/// timeout elapsed first. Like [`wait_for_event`], this clears the signaled
+/// state of the event.
+///
+/// This is a shortcut for creating a one-shot [`Timer`] and calling
+/// [`Timer::race`].
+///
+/// # Example
+///
+/// ```no_run
+/// use core::time::Duration;
+/// use uefi::{boot, system};
+///
+/// # fn test() -> uefi::Result {
+/// let key_event = system::with_stdin(|stdin| stdin.wait_for_key_event()).unwrap();
+/// if boot::wait_for_event_with_timeout(&key_event, Duration::from_secs(5))? {
+///     // A key was pressed.
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `event` is of type [`NOTIFY_SIGNAL`].
+/// * [`Status::UNSUPPORTED`]: the current TPL is not [`Tpl::APPLICATION`].
+/// * [`Status::OUT_OF_RESOURCES`]: the timer event could not be allocated.
+///
+/// [`NOTIFY_SIGNAL`]: EventType::NOTIFY_SIGNAL
+pub fn wait_for_event_with_timeout(event: &Event, timeout: Duration) -> Result<bool> {
+    let mut timer = Timer::one_shot(timeout)?;
+    Ok(timer.race(event)? == RaceOutcome::Event)
+}
+
 /// Connect one or more drivers to a controller.
 ///
 /// Usually one disconnects and then reconnects certain drivers
@@ -803,6 +1123,179 @@ pub unsafe fn uninstall_protocol_interface(
     unsafe { (bt.uninstall_protocol_interface)(handle.as_ptr(), protocol, interface).to_result() }
 }
 
+/// Installs several protocol interfaces on a device handle at once.
+///
+/// Either all of the `interfaces` are installed, or none of them are. In
+/// addition, the firmware checks that no other handle already has the same
+/// device path installed, if a [`DevicePath`] interface is one of the
+/// `interfaces`.
+///
+/// If `handle` is `None`, a new handle will be created.
+///
+/// On success, an [`InstalledProtocolInterfaces`] guard is returned that
+/// uninstalls all of the interfaces together when dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::ffi::c_void;
+/// use uefi::boot::{self, ProtocolInterfaces};
+/// use uefi::proto::device_path::DevicePath;
+/// use uefi::proto::media::block::BlockIO;
+///
+/// # fn test(device_path: &'static DevicePath, block_io: *const BlockIO) -> uefi::Result {
+/// let interfaces = unsafe {
+///     ProtocolInterfaces::new()
+///         .with_protocol::<DevicePath>(device_path)
+///         .with_protocol::<BlockIO>(block_io)
+/// };
+/// let installed = unsafe { boot::install_multiple_protocol_interfaces(None, &interfaces) }?;
+/// let handle = installed.handle();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// The caller must ensure that each interface is valid for its protocol and
+/// stays valid until it has been uninstalled.
+///
+/// # Errors
+///
+/// * [`Status::ALREADY_STARTED`]: a device path is already installed on
+///   another handle.
+/// * [`Status::OUT_OF_RESOURCES`]: failed to allocate a new handle.
+/// * [`Status::INVALID_PARAMETER`]: a protocol is already installed on the
+///   handle, or `interfaces` is empty.
+pub unsafe fn install_multiple_protocol_interfaces(
+    handle: Option<Handle>,
+    interfaces: &ProtocolInterfaces,
+) -> Result<InstalledProtocolInterfaces> {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    if interfaces.len == 0 {
+        return Err(Status::INVALID_PARAMETER.into());
+    }
+
+    let mut handle = Handle::opt_to_ptr(handle);
+    let [
+        a0,
+        a1,
+        a2,
+        a3,
+        a4,
+        a5,
+        a6,
+        a7,
+        a8,
+        a9,
+        a10,
+        a11,
+        a12,
+        a13,
+        a14,
+        a15,
+        a16,
+    ] = interfaces.ffi_args();
+    unsafe {
+        (bt.install_multiple_protocol_interfaces)(
+            &mut handle,
+            a0,
+            a1,
+            a2,
+            a3,
+            a4,
+            a5,
+            a6,
+            a7,
+            a8,
+            a9,
+            a10,
+            a11,
+            a12,
+            a13,
+            a14,
+            a15,
+            a16,
+        )
+    }
+    .to_result_with_val(|| InstalledProtocolInterfaces {
+        // OK to unwrap: handle is non-null for Status::SUCCESS.
+        handle: unsafe { Handle::from_ptr(handle) }.unwrap(),
+        interfaces: *interfaces,
+    })
+}
+
+/// Removes several protocol interfaces from a device handle at once.
+///
+/// Either all of the `interfaces` are uninstalled, or none of them are.
+///
+/// Usually it's simpler to drop the [`InstalledProtocolInterfaces`] returned
+/// by [`install_multiple_protocol_interfaces`], or to call
+/// [`InstalledProtocolInterfaces::uninstall`].
+///
+/// # Safety
+///
+/// The caller is responsible for ensuring that there are no references to the
+/// protocol interfaces that are being removed.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: one of the interfaces was not found on
+///   the handle, or could not be uninstalled. None of the interfaces were
+///   uninstalled.
+pub unsafe fn uninstall_multiple_protocol_interfaces(
+    handle: Handle,
+    interfaces: &ProtocolInterfaces,
+) -> Result {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    let [
+        a0,
+        a1,
+        a2,
+        a3,
+        a4,
+        a5,
+        a6,
+        a7,
+        a8,
+        a9,
+        a10,
+        a11,
+        a12,
+        a13,
+        a14,
+        a15,
+        a16,
+    ] = interfaces.ffi_args();
+    unsafe {
+        (bt.uninstall_multiple_protocol_interfaces)(
+            handle.as_ptr(),
+            a0,
+            a1,
+            a2,
+            a3,
+            a4,
+            a5,
+            a6,
+            a7,
+            a8,
+            a9,
+            a10,
+            a11,
+            a12,
+            a13,
+            a14,
+            a15,
+            a16,
+        )
+    }
+    .to_result()
+}
+
 /// Registers `event` to be signaled whenever a protocol interface is registered for
 /// `protocol` by [`install_protocol_interface`] or [`reinstall_protocol_interface`].
 ///
@@ -853,6 +1346,53 @@ pub fn protocols_per_handle(handle: Handle) -> Result<ProtocolsPerHandle> {
         })
 }
 
+/// Get information about the agents that have opened `protocol` on `handle`.
+///
+/// Each [`OpenProtocolInformationEntry`] describes one agent (an image or a
+/// driver) that has the protocol open, along with the controller it was
+/// opened for and the attributes it was opened with. This is useful for
+/// diagnosing why opening a protocol fails with [`Status::ACCESS_DENIED`].
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: `handle` does not support `protocol`.
+/// * [`Status::OUT_OF_RESOURCES`]: out of memory.
+pub fn open_protocol_information(
+    handle: Handle,
+    protocol: &Guid,
+) -> Result<OpenProtocolInformation> {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+
+    let mut entries = ptr::null();
+    let mut count = 0;
+
+    unsafe { (bt.open_protocol_information)(handle.as_ptr(), protocol, &mut entries, &mut count) }
+        .to_result_with_val(|| OpenProtocolInformation {
+            entries: NonNull::new(entries.cast_mut().cast()),
+            count,
+        })
+}
+
+/// Get information about the agents that have opened each protocol
+/// installed on `handle`.
+///
+/// This combines [`protocols_per_handle`] and [`open_protocol_information`].
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `handle` is invalid.
+/// * [`Status::OUT_OF_RESOURCES`]: out of memory.
+#[cfg(feature = "alloc")]
+pub fn open_protocol_information_per_handle(
+    handle: Handle,
+) -> Result<Vec<(Guid, OpenProtocolInformation)>> {
+    protocols_per_handle(handle)?
+        .iter()
+        .map(|&&guid| Ok((guid, open_protocol_information(handle, &guid)?)))
+        .collect()
+}
+
 /// Locates the handle of a device on the [`DevicePath`] that supports the
 /// specified [`Protocol`].
 ///
@@ -1190,13 +1730,28 @@ pub fn test_protocol<P: ProtocolPointer + ?Sized>(params: OpenProtocolParams) ->
 /// * [`Status::SECURITY_VIOLATION`]: a security policy specifies that the image
 ///   should not be started.
 pub fn load_image(parent_image_handle: Handle, source: LoadImageSource) -> Result<Handle> {
+    let (status, image_handle) = load_image_raw(parent_image_handle, &source);
+    status.to_result_with_val(
+        // OK to unwrap: image handle is non-null for Status::SUCCESS.
+        || image_handle.unwrap(),
+    )
+}
+
+/// Call `LoadImage`. Returns the status and the image handle, which is also
+/// set if the status is [`Status::SECURITY_VIOLATION`]. In that case, the
+/// image is loaded but must not be started, and must be unloaded with
+/// [`unload_image`].
+fn load_image_raw(
+    parent_image_handle: Handle,
+    source: &LoadImageSource,
+) -> (Status, Option<Handle>) {
     let bt = boot_services_raw_panicking();
     let bt = unsafe { bt.as_ref() };
 
     let (boot_policy, device_path, source_buffer, source_size) = source.to_ffi_params();
 
     let mut image_handle = ptr::null_mut();
-    unsafe {
+    let status = unsafe {
         (bt.load_image)(
             boot_policy.into(),
             parent_image_handle.as_ptr(),
@@ -1205,11 +1760,8 @@ pub fn load_image(parent_image_handle: Handle, source: LoadImageSource) -> Resul
             source_size,
             &mut image_handle,
         )
-        .to_result_with_val(
-            // OK to unwrap: image handle is non-null for Status::SUCCESS.
-            || Handle::from_ptr(image_handle).unwrap(),
-        )
-    }
+    };
+    (status, unsafe { Handle::from_ptr(image_handle) })
 }
 
 /// Unloads a UEFI image.
@@ -1233,16 +1785,92 @@ pub fn unload_image(image_handle: Handle) -> Result {
 ///   has already been initialized with `start_image`.
 /// * [`Status::SECURITY_VIOLATION`]: a security policy specifies that the image
 ///   should not be started.
+///
+/// Any exit data returned by the image is discarded. Use
+/// [`start_image_with_exit_data`] to get it.
 pub fn start_image(image_handle: Handle) -> Result {
+    let (status, exit_data) = unsafe { start_image_raw(image_handle) };
+    if let Some(exit_data) = exit_data {
+        let _ = unsafe { free_pool(exit_data.0.cast()) };
+    }
+    status.to_result()
+}
+
+/// Transfers control to a loaded image's entry point, and returns the exit
+/// data of the image.
+///
+/// If the image exits with an error, the error data contains the string
+/// passed by the image to [`exit`] as exit data, if any. Binary data
+/// following the string is discarded.
+///
+/// See also [`chainload`], which loads and starts an image in one call.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `image_handle` is not valid, or the image
+///   has already been initialized with `start_image`.
+/// * [`Status::SECURITY_VIOLATION`]: a security policy specifies that the image
+///   should not be started.
+/// * Any other error status returned by the image.
+#[cfg(feature = "alloc")]
+pub fn start_image_with_exit_data(image_handle: Handle) -> Result<(), Option<CString16>> {
+    let (status, exit_data) = unsafe { start_image_raw(image_handle) };
+    let exit_data = exit_data.and_then(|(ptr, size)| {
+        let codes = unsafe { slice::from_raw_parts(ptr.as_ptr(), size / size_of::<u16>()) };
+        let string = CStr16::from_u16_until_nul(codes).ok().map(CString16::from);
+        let _ = unsafe { free_pool(ptr.cast()) };
+        string
+    });
+    status.to_result_with_err(|_| exit_data)
+}
+
+/// Call `StartImage`. Returns the status and the exit data, which must be
+/// freed with [`free_pool`].
+unsafe fn start_image_raw(image_handle: Handle) -> (Status, Option<(NonNull<u16>, usize)>) {
     let bt = boot_services_raw_panicking();
     let bt = unsafe { bt.as_ref() };
 
-    // TODO: implement returning exit data to the caller.
     let mut exit_data_size: usize = 0;
     let mut exit_data: *mut u16 = ptr::null_mut();
 
-    unsafe {
-        (bt.start_image)(image_handle.as_ptr(), &mut exit_data_size, &mut exit_data).to_result()
+    let status =
+        unsafe { (bt.start_image)(image_handle.as_ptr(), &mut exit_data_size, &mut exit_data) };
+    (
+        status,
+        NonNull::new(exit_data).map(|ptr| (ptr, exit_data_size)),
+    )
+}
+
+/// Loads an image and starts it, see [`Chainload`].
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::boot::{self, ChainloadSource};
+/// use uefi::proto::loaded_image::LoadedImage;
+/// use uefi::{cstr16, ResultExt, Status};
+///
+/// # fn test() -> uefi::Result {
+/// // Start the shell from the volume that this image was loaded from.
+/// let loaded_image = boot::open_protocol_exclusive::<LoadedImage>(boot::image_handle())?;
+/// let volume = loaded_image.device().ok_or(Status::NOT_FOUND)?;
+/// boot::chainload(ChainloadSource::File {
+///     volume,
+///     path: cstr16!(r"\EFI\BOOT\shell.efi"),
+/// })
+/// .load_options(cstr16!("shell.efi -nostartup"))
+/// .start()
+/// .discard_errdata()?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub const fn chainload(source: ChainloadSource<'_>) -> Chainload<'_> {
+    Chainload {
+        source,
+        load_options: None,
+        parent: None,
+        boot_policy: BootPolicy::ExactMatch,
     }
 }
 
@@ -1319,6 +1947,10 @@ unsafe fn get_memory_map_and_exit_boot_services(buf: &mut [u8]) -> Result<Memory
 /// `global_allocator` feature is enabled, attempting to use the allocator
 /// after exiting boot services will panic.
 ///
+/// If the `alloc` feature is enabled, the hooks registered with
+/// `register_exit_boot_services_hook` are run first, before any of the
+/// above happens.
+///
 /// # Arguments
 /// - `custom_memory_type`: The [`MemoryType`] for the UEFI allocation that will
 ///   store the final memory map. If you pass `None`, this defaults to the
@@ -1360,6 +1992,10 @@ pub unsafe fn exit_boot_services(custom_memory_type: Option<MemoryType>) -> Memo
     // LOADER_DATA is the default and also used by the Linux kernel:
     // https://elixir.bootlin.com/linux/v6.13.7/source/drivers/firmware/efi/libstub/mem.c#L24
     let memory_type = custom_memory_type.unwrap_or(MemoryType::LOADER_DATA);
+
+    #[cfg(feature = "alloc")]
+    run_exit_boot_services_hooks();
+
     crate::helpers::exit();
 
     let mut buf = MemoryMapBackingMemory::new(memory_type).expect("Failed to allocate memory");
@@ -1386,6 +2022,67 @@ pub unsafe fn exit_boot_services(custom_memory_type: Option<MemoryType>) -> Memo
     runtime::reset(ResetType::COLD, status, None);
 }
 
+/// Hooks registered with [`register_exit_boot_services_hook`].
+#[cfg(feature = "alloc")]
+#[allow(clippy::type_complexity)]
+static EXIT_BOOT_SERVICES_HOOKS: TplMutex<Vec<Box<dyn FnOnce() + Send>>> =
+    // SAFETY: the mutex is only locked at or below `Tpl::NOTIFY`, since
+    // memory allocation is not allowed above that.
+    unsafe { TplMutex::new(Tpl::NOTIFY, Vec::new()) };
+
+/// Registers a hook that [`exit_boot_services`] runs before it retrieves the
+/// final memory map.
+///
+/// Hooks are run once, in the order they were registered, while boot services
+/// are still fully usable. This is the place to flush logs, hand resources
+/// such as the framebuffer over to the next stage, or free memory that would
+/// otherwise be leaked. Hooks may register further hooks, which are run as
+/// well.
+///
+/// Unlike [`EventGroup::BeforeExitBootServices`], hooks run before the
+/// [`logger`] is disabled, and may allocate memory. However, they are only
+/// run by [`exit_boot_services`], not when another image exits boot
+/// services.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::boot;
+///
+/// boot::register_exit_boot_services_hook(|| {
+///     log::info!("exiting boot services");
+/// });
+/// ```
+///
+/// [`logger`]: crate::helpers
+#[cfg(feature = "alloc")]
+pub fn register_exit_boot_services_hook<F>(hook: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    EXIT_BOOT_SERVICES_HOOKS.lock().push(Box::new(hook));
+}
+
+/// Runs and removes the hooks registered with
+/// [`register_exit_boot_services_hook`].
+#[cfg(feature = "alloc")]
+fn run_exit_boot_services_hooks() {
+    loop {
+        // Don't hold the lock while running the hook, so that it can register
+        // further hooks.
+        let hook = {
+            let mut hooks = EXIT_BOOT_SERVICES_HOOKS.lock();
+            if hooks.is_empty() {
+                // Free the list while the allocator can still be used.
+                *hooks = Vec::new();
+                return;
+            }
+            hooks.remove(0)
+        };
+        hook();
+    }
+}
+
 /// Adds, updates, or removes a configuration table entry
 /// from the EFI System Table.
 ///
@@ -1499,6 +2196,24 @@ pub fn get_image_file_system(image_handle: Handle) -> Result<ScopedProtocol<Simp
     open_protocol_exclusive(device_handle)
 }
 
+/// Get the next value of the platform's 64-bit monotonic counter, and
+/// increment it.
+///
+/// The high 32 bits are persisted across resets. After exiting boot
+/// services, use [`runtime::MonotonicCounter`] instead.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: the counter could not be read due to a
+///   hardware error.
+pub fn get_next_monotonic_count() -> Result<u64> {
+    let bt = boot_services_raw_panicking();
+    let bt = unsafe { bt.as_ref() };
+    let mut count = 0u64;
+
+    unsafe { (bt.get_next_monotonic_count)(&mut count) }.to_result_with_val(|| count)
+}
+
 /// Calculates the 32-bit CRC32 for the provided slice.
 ///
 /// # Errors
@@ -1544,12 +2259,75 @@ impl Deref for ProtocolsPerHandle {
     }
 }
 
-/// A buffer returned by [`locate_handle_buffer`] that contains an array of
-/// [`Handle`]s that support the requested [`Protocol`].
-#[derive(Debug, Eq, PartialEq)]
-pub struct HandleBuffer {
+/// Information about the agents that have opened a protocol, as returned by
+/// [`open_protocol_information`].
+#[derive(Debug)]
+pub struct OpenProtocolInformation {
+    entries: Option<NonNull<OpenProtocolInformationEntry>>,
     count: usize,
-    buffer: NonNull<Handle>,
+}
+
+impl Drop for OpenProtocolInformation {
+    fn drop(&mut self) {
+        if let Some(entries) = self.entries {
+            let _ = unsafe { free_pool(entries.cast::<u8>()) };
+        }
+    }
+}
+
+impl Deref for OpenProtocolInformation {
+    type Target = [OpenProtocolInformationEntry];
+
+    fn deref(&self) -> &Self::Target {
+        match self.entries {
+            // SAFETY: the firmware is assumed to provide a correctly-aligned
+            // pointer and array length.
+            Some(entries) => unsafe { slice::from_raw_parts(entries.as_ptr(), self.count) },
+            None => &[],
+        }
+    }
+}
+
+/// An agent that has opened a protocol. See [`open_protocol_information`].
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct OpenProtocolInformationEntry(uefi_raw::table::boot::OpenProtocolInformationEntry);
+
+impl OpenProtocolInformationEntry {
+    /// The handle of the agent that opened the protocol. For drivers, this is
+    /// the handle with the driver binding protocol; for applications, it is
+    /// the image handle.
+    #[must_use]
+    pub fn agent_handle(&self) -> Option<Handle> {
+        unsafe { Handle::from_ptr(self.0.agent_handle) }
+    }
+
+    /// The controller handle the protocol was opened for, if any.
+    #[must_use]
+    pub fn controller_handle(&self) -> Option<Handle> {
+        unsafe { Handle::from_ptr(self.0.controller_handle) }
+    }
+
+    /// The attributes the protocol was opened with.
+    #[must_use]
+    pub const fn attributes(&self) -> OpenProtocolAttributeFlags {
+        OpenProtocolAttributeFlags::from_bits_retain(self.0.attributes)
+    }
+
+    /// The number of times the agent has opened the protocol with these
+    /// parameters.
+    #[must_use]
+    pub const fn open_count(&self) -> u32 {
+        self.0.open_count
+    }
+}
+
+/// A buffer returned by [`locate_handle_buffer`] that contains an array of
+/// [`Handle`]s that support the requested [`Protocol`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct HandleBuffer {
+    count: usize,
+    buffer: NonNull<Handle>,
 }
 
 impl Drop for HandleBuffer {
@@ -1649,6 +2427,151 @@ impl<P: Protocol + ?Sized> ScopedProtocol<P> {
     }
 }
 
+/// Protocol interfaces to install or uninstall together. See
+/// [`install_multiple_protocol_interfaces`].
+///
+/// Up to [`ProtocolInterfaces::MAX_LEN`] interfaces can be added.
+#[derive(Clone, Copy, Debug)]
+pub struct ProtocolInterfaces {
+    entries: [(Guid, *const c_void); Self::MAX_LEN],
+    len: usize,
+}
+
+impl ProtocolInterfaces {
+    /// Maximum number of interfaces.
+    pub const MAX_LEN: usize = 8;
+
+    /// Create an empty list of interfaces.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: [(Guid::ZERO, ptr::null()); Self::MAX_LEN],
+            len: 0,
+        }
+    }
+
+    /// Add an `interface` for `protocol`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `interface` is valid for `protocol`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list already contains [`MAX_LEN`] interfaces.
+    ///
+    /// [`MAX_LEN`]: Self::MAX_LEN
+    #[must_use]
+    pub const unsafe fn with(mut self, protocol: Guid, interface: *const c_void) -> Self {
+        assert!(self.len < Self::MAX_LEN, "too many protocol interfaces");
+        self.entries[self.len] = (protocol, interface);
+        self.len += 1;
+        self
+    }
+
+    /// Add an `interface` for the protocol `P`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `interface` is valid for `P`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list already contains [`MAX_LEN`] interfaces.
+    ///
+    /// [`MAX_LEN`]: Self::MAX_LEN
+    #[must_use]
+    pub const unsafe fn with_protocol<P: Protocol + ?Sized>(self, interface: *const P) -> Self {
+        unsafe { self.with(P::GUID, interface.cast()) }
+    }
+
+    /// Returns an iterator over the protocol GUIDs and interfaces.
+    pub fn iter(&self) -> impl Iterator<Item = (&Guid, *const c_void)> {
+        self.entries[..self.len]
+            .iter()
+            .map(|(guid, interface)| (guid, *interface))
+    }
+
+    /// Get the variadic arguments of `InstallMultipleProtocolInterfaces` and
+    /// `UninstallMultipleProtocolInterfaces`: pairs of GUID and interface
+    /// pointers, followed by null. The firmware stops reading arguments at the
+    /// first null GUID, so unused pairs are set to null as well.
+    fn ffi_args(&self) -> [*const c_void; 2 * Self::MAX_LEN + 1] {
+        let mut args = [ptr::null(); 2 * Self::MAX_LEN + 1];
+        for (i, (guid, interface)) in self.iter().enumerate() {
+            args[2 * i] = ptr::from_ref(guid).cast();
+            args[2 * i + 1] = interface;
+        }
+        args
+    }
+}
+
+impl Default for ProtocolInterfaces {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Protocol interfaces installed with [`install_multiple_protocol_interfaces`].
+///
+/// All of the interfaces are uninstalled together when this guard is dropped.
+/// Use [`uninstall`] to handle errors, or [`leak`] to keep the interfaces
+/// installed.
+///
+/// [`uninstall`]: Self::uninstall
+/// [`leak`]: Self::leak
+#[derive(Debug)]
+pub struct InstalledProtocolInterfaces {
+    handle: Handle,
+    interfaces: ProtocolInterfaces,
+}
+
+impl InstalledProtocolInterfaces {
+    /// The handle the interfaces are installed on.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// The installed interfaces.
+    #[must_use]
+    pub const fn interfaces(&self) -> &ProtocolInterfaces {
+        &self.interfaces
+    }
+
+    /// Uninstall all of the interfaces.
+    ///
+    /// If this fails, the interfaces remain installed and are no longer
+    /// tracked.
+    ///
+    /// # Errors
+    ///
+    /// See [`uninstall_multiple_protocol_interfaces`].
+    pub fn uninstall(self) -> Result {
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: the interfaces were installed on the handle by
+        // `install_multiple_protocol_interfaces`.
+        unsafe { uninstall_multiple_protocol_interfaces(this.handle, &this.interfaces) }
+    }
+
+    /// Keep the interfaces installed, returning the handle.
+    #[must_use]
+    pub fn leak(self) -> Handle {
+        mem::ManuallyDrop::new(self).handle
+    }
+}
+
+impl Drop for InstalledProtocolInterfaces {
+    fn drop(&mut self) {
+        // SAFETY: the interfaces were installed on the handle by
+        // `install_multiple_protocol_interfaces`.
+        let r = unsafe { uninstall_multiple_protocol_interfaces(self.handle, &self.interfaces) };
+        if let Err(err) = r {
+            log::error!("Failed to uninstall protocol interfaces: {err}");
+        }
+    }
+}
+
 /// RAII guard for task priority level changes.
 ///
 /// Will automatically restore the former task priority level when dropped.
@@ -1676,6 +2599,332 @@ impl Drop for TplGuard {
     }
 }
 
+/// An [`Event`] that is closed automatically when dropped.
+///
+/// Created by [`create_event_with`], in which case the notification closure
+/// is owned by the `OwnedEvent` as well and freed after the event has been
+/// closed. An existing event can be wrapped with [`OwnedEvent::from_event`].
+///
+/// `OwnedEvent` implements [`Deref`] to [`Event`], so it can be passed to
+/// functions such as [`signal_event`] and [`set_timer`].
+///
+/// If boot services are no longer active when an `OwnedEvent` is dropped, the
+/// event and closure are leaked instead.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct OwnedEvent {
+    event: Event,
+    closure: Option<OwnedEventClosure>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedEvent {
+    /// Takes ownership of an existing `event`, closing it when dropped.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `event` is valid, and that it is not
+    /// closed by any other means while the `OwnedEvent` exists.
+    #[must_use]
+    pub const unsafe fn from_event(event: Event) -> Self {
+        Self {
+            event,
+            closure: None,
+        }
+    }
+
+    /// Consumes the `OwnedEvent` without closing the event, returning the
+    /// underlying [`Event`]. The notification closure, if any, is leaked
+    /// so that it stays valid for as long as the event exists.
+    ///
+    /// This is useful for events that must outlive their creator, such as
+    /// events that are notified when boot services are exited.
+    #[must_use]
+    pub fn leak(self) -> Event {
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so the event is not closed.
+        unsafe { this.event.unsafe_clone() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for OwnedEvent {
+    type Target = Event;
+
+    fn deref(&self) -> &Event {
+        &self.event
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Drop for OwnedEvent {
+    fn drop(&mut self) {
+        if !are_boot_services_active() {
+            return;
+        }
+
+        // SAFETY: the event is owned by `self` and is not used after this.
+        let event = unsafe { self.event.unsafe_clone() };
+        if close_event(event).is_err() {
+            // The firmware may still call the closure, so don't free it.
+            return;
+        }
+
+        if let Some(closure) = self.closure.take() {
+            // SAFETY: the event has been closed, so the firmware no longer
+            // refers to the closure.
+            unsafe { closure.free() };
+        }
+    }
+}
+
+/// A timer event, which is closed when dropped.
+///
+/// This wraps [`create_event`], [`set_timer`] and [`wait_for_event`] for
+/// timer events, using [`Duration`]s instead of 100ns units. The timer
+/// resolution depends on the platform, and is usually a few milliseconds.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::time::Duration;
+/// use uefi::boot::Timer;
+///
+/// # fn test() -> uefi::Result {
+/// let mut timer = Timer::periodic(Duration::from_secs(1))?;
+/// for (remaining, tick) in (1..=5).rev().zip(timer.ticks()) {
+///     tick?;
+///     log::info!("Booting in {remaining} seconds...");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Timer {
+    event: Event,
+}
+
+impl Timer {
+    /// Creates a timer that is not set.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::OUT_OF_RESOURCES`]: the timer event could not be allocated.
+    pub fn new() -> Result<Self> {
+        // SAFETY: the event has no notification function.
+        let event = unsafe { create_event(EventType::TIMER, Tpl::CALLBACK, None, None) }?;
+        Ok(Self { event })
+    }
+
+    /// Creates a timer that fires once, after `delay`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::OUT_OF_RESOURCES`]: the timer event could not be allocated.
+    pub fn one_shot(delay: Duration) -> Result<Self> {
+        let timer = Self::new()?;
+        timer.set_one_shot(delay)?;
+        Ok(timer)
+    }
+
+    /// Creates a timer that fires every `period`, starting one `period` from
+    /// now.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::OUT_OF_RESOURCES`]: the timer event could not be allocated.
+    pub fn periodic(period: Duration) -> Result<Self> {
+        let timer = Self::new()?;
+        timer.set_periodic(period)?;
+        Ok(timer)
+    }
+
+    /// Sets the timer to fire once, after `delay`. This replaces the
+    /// previous setting.
+    ///
+    /// # Errors
+    ///
+    /// The specification does not list any errors for a valid timer event.
+    pub fn set_one_shot(&self, delay: Duration) -> Result {
+        set_timer(
+            &self.event,
+            TimerTrigger::Relative(duration_to_timer_units(delay)),
+        )
+    }
+
+    /// Sets the timer to fire every `period`, starting one `period` from
+    /// now. This replaces the previous setting.
+    ///
+    /// # Errors
+    ///
+    /// The specification does not list any errors for a valid timer event.
+    pub fn set_periodic(&self, period: Duration) -> Result {
+        set_timer(
+            &self.event,
+            TimerTrigger::Periodic(duration_to_timer_units(period)),
+        )
+    }
+
+    /// Cancels the timer, so that it no longer fires.
+    ///
+    /// # Errors
+    ///
+    /// The specification does not list any errors for a valid timer event.
+    pub fn cancel(&self) -> Result {
+        set_timer(&self.event, TimerTrigger::Cancel)
+    }
+
+    /// The timer's event, for use with [`wait_for_event`] or
+    /// [`check_event`].
+    #[must_use]
+    pub const fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Checks whether the timer has fired, without waiting.
+    ///
+    /// If the timer has fired, this returns `true` and resets the signaled
+    /// state, so a periodic timer returns `true` again once it fires the next
+    /// time.
+    ///
+    /// # Errors
+    ///
+    /// The specification does not list any errors for a valid timer event.
+    pub fn has_fired(&mut self) -> Result<bool> {
+        // SAFETY: the clone is only used for the duration of the call.
+        check_event(unsafe { self.event.unsafe_clone() })
+    }
+
+    /// Waits until the timer fires.
+    ///
+    /// If the timer has already fired since it was last waited for, this
+    /// returns immediately. If the timer is not set, this waits forever.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the current TPL is not [`Tpl::APPLICATION`].
+    pub fn wait(&mut self) -> Result {
+        // SAFETY: the clone is only used for the duration of the call.
+        let mut events = [unsafe { self.event.unsafe_clone() }];
+        wait_for_event(&mut events).discard_errdata()?;
+        Ok(())
+    }
+
+    /// Waits until either `event` is signaled or the timer fires, whichever
+    /// happens first.
+    ///
+    /// If both have happened already, [`RaceOutcome::Event`] is returned.
+    /// The signaled state of whichever one is returned is cleared.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `event` is of type [`NOTIFY_SIGNAL`].
+    /// * [`Status::UNSUPPORTED`]: the current TPL is not [`Tpl::APPLICATION`].
+    ///
+    /// [`NOTIFY_SIGNAL`]: EventType::NOTIFY_SIGNAL
+    pub fn race(&mut self, event: &Event) -> Result<RaceOutcome> {
+        // SAFETY: the clones are only used for the duration of the call.
+        let mut events = unsafe { [event.unsafe_clone(), self.event.unsafe_clone()] };
+        match wait_for_event(&mut events).discard_errdata()? {
+            0 => Ok(RaceOutcome::Event),
+            _ => Ok(RaceOutcome::Timer),
+        }
+    }
+
+    /// Returns an iterator that waits for the timer to fire on each
+    /// iteration, see [`wait`].
+    ///
+    /// This is intended for periodic timers. The iterator never ends, so for
+    /// a one-shot timer, it only yields once before waiting forever.
+    ///
+    /// [`wait`]: Self::wait
+    pub const fn ticks(&mut self) -> Ticks<'_> {
+        Ticks { timer: self }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if !are_boot_services_active() {
+            return;
+        }
+        // SAFETY: the event is owned by `self` and is not used after this.
+        let _ = close_event(unsafe { self.event.unsafe_clone() });
+    }
+}
+
+/// Iterator returned by [`Timer::ticks`].
+#[derive(Debug)]
+pub struct Ticks<'a> {
+    timer: &'a mut Timer,
+}
+
+impl Iterator for Ticks<'_> {
+    type Item = Result;
+
+    fn next(&mut self) -> Option<Result> {
+        Some(self.timer.wait())
+    }
+}
+
+/// Which event was signaled first, returned by [`Timer::race`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RaceOutcome {
+    /// The event passed to [`Timer::race`] was signaled.
+    Event,
+
+    /// The timer fired.
+    Timer,
+}
+
+/// A boxed notification closure along with the trampoline that calls it.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+struct OwnedEventClosure {
+    closure: NonNull<dyn FnMut(Event)>,
+    trampoline: EventNotifyFn,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedEventClosure {
+    fn new<F: FnMut(Event) + 'static>(f: F) -> Self {
+        let closure: Box<dyn FnMut(Event)> = Box::new(f);
+        Self {
+            closure: NonNull::from(Box::leak(closure)),
+            trampoline: event_closure_trampoline::<F>,
+        }
+    }
+
+    /// Get the context pointer to pass to the firmware.
+    fn ctx(&self) -> NonNull<c_void> {
+        self.closure.cast()
+    }
+
+    /// Free the closure.
+    ///
+    /// # Safety
+    ///
+    /// The firmware must no longer be able to call the trampoline with this
+    /// closure's context.
+    unsafe fn free(self) {
+        drop(unsafe { Box::from_raw(self.closure.as_ptr()) });
+    }
+}
+
+/// Notification function for events created by [`create_event_with`].
+#[cfg(feature = "alloc")]
+unsafe extern "efiapi" fn event_closure_trampoline<F: FnMut(Event)>(
+    event: Event,
+    ctx: Option<NonNull<c_void>>,
+) {
+    // SAFETY: the context was created from a `Box<F>` by
+    // `OwnedEventClosure::new`, and remains valid until the event is closed.
+    // The firmware does not call an event's notification function
+    // reentrantly, so the mutable reference is unique.
+    let f = unsafe { ctx.expect("missing closure context").cast::<F>().as_mut() };
+    f(event);
+}
+
 // OpenProtocolAttributes is safe to model as a regular enum because it
 // is only used as an input. The attributes are bitflags, but all valid
 // combinations are listed in the spec and only ByDriver and Exclusive
@@ -1742,6 +2991,182 @@ pub struct OpenProtocolParams {
     pub controller: Option<Handle>,
 }
 
+/// Location of the image to load with [`chainload`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug)]
+pub enum ChainloadSource<'a> {
+    /// A file on a volume.
+    File {
+        /// Handle of the volume, which must support the [`DevicePath`] and
+        /// [`SimpleFileSystem`] protocols. For example, the volume that the
+        /// current image was loaded from is [`LoadedImage::device`].
+        volume: Handle,
+
+        /// Path of the file on the volume, for example
+        /// `\\EFI\\BOOT\\BOOTX64.EFI`.
+        path: &'a CStr16,
+    },
+
+    /// A full device path, see [`LoadImageSource::FromDevicePath`].
+    DevicePath(&'a DevicePath),
+}
+
+/// Builder for loading and starting an image. Created with [`chainload`].
+///
+/// This loads the image with [`load_image`], sets its load options, and
+/// starts it with [`start_image_with_exit_data`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct Chainload<'a> {
+    source: ChainloadSource<'a>,
+    load_options: Option<&'a [u8]>,
+    parent: Option<Handle>,
+    boot_policy: BootPolicy,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Chainload<'a> {
+    /// Set the load options of the image to a string. By convention, this is
+    /// the command line of the image, starting with the image's name.
+    ///
+    /// The load options must remain valid while the image uses them. For
+    /// drivers that stay loaded after their entry point returns, this may be
+    /// longer than the lifetime `'a`.
+    pub const fn load_options(mut self, load_options: &'a CStr16) -> Self {
+        self.load_options = Some(load_options.as_bytes());
+        self
+    }
+
+    /// Set the load options of the image to arbitrary bytes.
+    ///
+    /// See [`load_options`] for lifetime considerations.
+    ///
+    /// [`load_options`]: Self::load_options
+    pub const fn load_options_bytes(mut self, load_options: &'a [u8]) -> Self {
+        self.load_options = Some(load_options);
+        self
+    }
+
+    /// Set the parent image. The default is the current image.
+    pub const fn parent(mut self, parent: Handle) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Set the boot policy used to load the image. The default is
+    /// [`BootPolicy::ExactMatch`].
+    pub const fn boot_policy(mut self, boot_policy: BootPolicy) -> Self {
+        self.boot_policy = boot_policy;
+        self
+    }
+
+    /// Load the image and set its load options, without starting it.
+    ///
+    /// The image can be started with [`start_image`] or
+    /// [`start_image_with_exit_data`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: the file path could not be converted
+    ///   to a device path, or the load options are too large.
+    /// * [`Status::UNSUPPORTED`]: the volume does not support the
+    ///   [`DevicePath`] protocol.
+    /// * Errors of [`load_image`]. If a security policy specifies that the
+    ///   image should not be started, it is unloaded again before
+    ///   [`Status::SECURITY_VIOLATION`] is returned.
+    pub fn load(&self) -> Result<Handle> {
+        let load_options_size = self
+            .load_options
+            .map(|options| u32::try_from(options.len()))
+            .transpose()
+            .map_err(|_| Status::INVALID_PARAMETER)?;
+
+        let mut storage = Vec::new();
+        let device_path = match self.source {
+            ChainloadSource::File { volume, path } => file_device_path(volume, path, &mut storage)?,
+            ChainloadSource::DevicePath(device_path) => device_path,
+        };
+
+        let (status, image) = load_image_raw(
+            self.parent.unwrap_or_else(image_handle),
+            &LoadImageSource::FromDevicePath {
+                device_path,
+                boot_policy: self.boot_policy,
+            },
+        );
+        if status == Status::SECURITY_VIOLATION {
+            // The image was loaded, but must not be started.
+            if let Some(image) = image {
+                let _ = unload_image(image);
+            }
+        }
+        status.to_result()?;
+        // OK to unwrap: image handle is non-null for Status::SUCCESS.
+        let image = image.unwrap();
+
+        if let (Some(options), Some(size)) = (self.load_options, load_options_size) {
+            match open_protocol_exclusive::<LoadedImage>(image) {
+                Ok(mut loaded_image) => unsafe {
+                    loaded_image.set_load_options(options.as_ptr(), size);
+                },
+                Err(err) => {
+                    let _ = unload_image(image);
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Load the image and start it.
+    ///
+    /// This returns when the image exits, or when a driver's entry point
+    /// returns. If the image returns an error, the firmware unloads it.
+    ///
+    /// # Errors
+    ///
+    /// * Errors of [`load`]. The error data is `None`.
+    /// * Errors of [`start_image_with_exit_data`]. The error data is the exit
+    ///   data of the image, if any.
+    ///
+    /// [`load`]: Self::load
+    pub fn start(self) -> Result<(), Option<CString16>> {
+        let image = self.load().map_err(|err| Error::new(err.status(), None))?;
+        start_image_with_exit_data(image)
+    }
+}
+
+/// Build the full device path of the file `path` on `volume` in `storage`.
+#[cfg(feature = "alloc")]
+fn file_device_path<'a>(
+    volume: Handle,
+    path: &CStr16,
+    storage: &'a mut Vec<u8>,
+) -> Result<&'a DevicePath> {
+    // SAFETY: the device path is not used after the protocol is closed.
+    let volume_path = unsafe {
+        open_protocol::<DevicePath>(
+            OpenProtocolParams {
+                handle: volume,
+                agent: image_handle(),
+                controller: None,
+            },
+            OpenProtocolAttributes::GetProtocol,
+        )
+    }?;
+
+    let mut builder = DevicePathBuilder::with_vec(storage);
+    for node in volume_path.node_iter() {
+        builder = builder.push(&node).map_err(|_| Status::INVALID_PARAMETER)?;
+    }
+    builder
+        .push(&build::media::FilePath { path_name: path })
+        .and_then(DevicePathBuilder::finalize)
+        .map_err(|_| Status::INVALID_PARAMETER.into())
+}
+
 /// Used as a parameter of [`load_image`] to provide the image source.
 #[derive(Debug)]
 pub enum LoadImageSource<'a> {
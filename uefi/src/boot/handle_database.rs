@@ -0,0 +1,947 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Snapshot of the handle database, see [`handle_database`].
+
+use crate::Guid;
+#[cfg(doc)]
+use crate::Status;
+#[cfg(feature = "alloc")]
+use {
+    super::{
+        OpenProtocolAttributeFlags, OpenProtocolAttributes, OpenProtocolInformationEntry,
+        OpenProtocolParams, SearchType, image_handle, locate_handle_buffer, open_protocol,
+        open_protocol_information, protocols_per_handle,
+    },
+    crate::proto::device_path::DevicePath,
+    crate::proto::device_path::text::{AllowShortcuts, DisplayOnly},
+    crate::proto::driver::{ComponentName, ComponentName2},
+    crate::{CStr16, CString16, Handle, Result},
+    alloc::borrow::ToOwned,
+    alloc::vec::Vec,
+    core::ffi::c_void,
+    core::fmt::{self, Display, Formatter},
+};
+
+/// Take a snapshot of the handle database.
+///
+/// This enumerates every handle, and collects the protocols installed on it
+/// along with the agents that have opened them, similar to the `dh` command
+/// of the UEFI Shell. Protocols are resolved to names with
+/// [`protocol_name`]. If available, the handle's device path is converted
+/// to text, and driver and controller names are retrieved from the
+/// [`ComponentName`] protocols of the drivers.
+///
+/// The snapshot implements [`Display`] to dump the whole database.
+///
+/// Protocols are opened with [`OpenProtocolAttributes::GetProtocol`] to
+/// retrieve this information, so taking a snapshot does not disconnect any
+/// drivers.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::{boot, println};
+///
+/// # fn test() -> uefi::Result {
+/// let db = boot::handle_database()?;
+/// println!("{db}");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: out of memory.
+///
+/// [`ComponentName`]: crate::proto::driver::ComponentName
+#[cfg(feature = "alloc")]
+pub fn handle_database() -> Result<HandleDatabase> {
+    let handles = locate_handle_buffer(SearchType::AllHandles)?;
+
+    let mut infos = Vec::with_capacity(handles.len());
+    for &handle in handles.iter() {
+        // Handles may be removed while the snapshot is taken, for example
+        // by a notification function, so skip handles that are no longer
+        // valid.
+        let Ok(protocols) = protocols_per_handle(handle) else {
+            continue;
+        };
+        let protocols = protocols
+            .iter()
+            .map(|&&guid| {
+                let open_info = open_protocol_information(handle, &guid)
+                    .map(|info| info.iter().map(OpenProtocolInfo::from).collect())
+                    .unwrap_or_default();
+                ProtocolInfo {
+                    guid,
+                    name: protocol_name(&guid),
+                    open_info,
+                }
+            })
+            .collect();
+
+        infos.push(HandleInfo {
+            handle,
+            protocols,
+            device_path: None,
+            driver_name: None,
+            controller_name: None,
+        });
+    }
+
+    // Only open protocols once the open protocol information of all handles
+    // has been collected, so that the snapshot doesn't include them.
+    for info in &mut infos {
+        info.device_path = device_path_text(info.handle);
+        info.driver_name = component_driver_name(info.handle);
+    }
+    for i in 0..infos.len() {
+        infos[i].controller_name = component_controller_name(&infos, infos[i].handle);
+    }
+
+    Ok(HandleDatabase { handles: infos })
+}
+
+/// Get the name of a protocol that is known to this crate, such as
+/// `"LoadedImage"` for the [`LoadedImage`] protocol.
+///
+/// Returns `None` if the protocol is unknown.
+///
+/// [`LoadedImage`]: crate::proto::loaded_image::LoadedImage
+#[must_use]
+pub fn protocol_name(guid: &Guid) -> Option<&'static str> {
+    PROTOCOL_NAMES
+        .iter()
+        .find(|(known, _)| known == guid)
+        .map(|(_, name)| *name)
+}
+
+/// Convert the device path of `handle` to text, for [`handle_database`].
+#[cfg(feature = "alloc")]
+fn device_path_text(handle: Handle) -> Option<CString16> {
+    // SAFETY: the device path is only used while the handle database is
+    // being collected.
+    let device_path = unsafe {
+        open_protocol::<DevicePath>(
+            OpenProtocolParams {
+                handle,
+                agent: image_handle(),
+                controller: None,
+            },
+            OpenProtocolAttributes::GetProtocol,
+        )
+    }
+    .ok()?;
+    device_path
+        .get()?
+        .to_string(DisplayOnly(false), AllowShortcuts(false))
+        .ok()
+}
+
+/// Open the component name protocol of a driver without disconnecting it,
+/// unlike [`ComponentName::open`].
+#[cfg(feature = "alloc")]
+fn open_component_name(driver: Handle) -> Option<ComponentName> {
+    let params = OpenProtocolParams {
+        handle: driver,
+        agent: image_handle(),
+        controller: None,
+    };
+    // SAFETY: the protocol is only used while the handle database is being
+    // collected.
+    unsafe { open_protocol::<ComponentName2>(params, OpenProtocolAttributes::GetProtocol) }
+        .map(ComponentName::V2)
+        .or_else(|_| unsafe {
+            #[allow(deprecated)]
+            open_protocol::<crate::proto::driver::ComponentName1>(
+                params,
+                OpenProtocolAttributes::GetProtocol,
+            )
+            .map(ComponentName::V1)
+        })
+        .ok()
+}
+
+/// Pick the language to use for a component name protocol: English if
+/// supported, otherwise the first supported language.
+#[cfg(feature = "alloc")]
+fn component_name_language(component_name: &ComponentName) -> Option<&str> {
+    let english = match component_name {
+        ComponentName::V1(_) => "eng",
+        ComponentName::V2(_) => "en",
+    };
+    let mut languages = component_name.supported_languages().ok()?;
+    let first = languages.next()?;
+    Some(
+        core::iter::once(first)
+            .chain(languages)
+            .find(|language| *language == english)
+            .unwrap_or(first),
+    )
+}
+
+/// Get the name of the driver on `driver`, for [`handle_database`].
+#[cfg(feature = "alloc")]
+fn component_driver_name(driver: Handle) -> Option<CString16> {
+    let component_name = open_component_name(driver)?;
+    let language = component_name_language(&component_name)?;
+    component_name
+        .driver_name(language)
+        .ok()
+        .map(CString16::from)
+}
+
+/// Get the name of `controller` from the driver managing it, for
+/// [`handle_database`].
+///
+/// The managing driver has opened a protocol on the controller `BY_DRIVER`.
+/// If the controller is a child created by a bus driver, the bus driver has
+/// opened a protocol on the parent `BY_CHILD_CONTROLLER` instead.
+#[cfg(feature = "alloc")]
+fn component_controller_name(handles: &[HandleInfo], controller: Handle) -> Option<CString16> {
+    let opened_by = |info: &HandleInfo, attribute, child: Option<Handle>| {
+        info.protocols
+            .iter()
+            .flat_map(|protocol| &protocol.open_info)
+            .filter(move |open| {
+                open.attributes.contains(attribute) && (child.is_none() || open.controller == child)
+            })
+            .filter_map(|open| open.agent)
+            .collect::<Vec<_>>()
+    };
+
+    let mut candidates = Vec::new();
+    for info in handles {
+        if info.handle == controller {
+            for driver in opened_by(info, OpenProtocolAttributeFlags::BY_DRIVER, None) {
+                candidates.push((driver, controller, None));
+            }
+        }
+    }
+    for info in handles {
+        if info.handle != controller {
+            for driver in opened_by(
+                info,
+                OpenProtocolAttributeFlags::BY_CHILD_CONTROLLER,
+                Some(controller),
+            ) {
+                candidates.push((driver, info.handle, Some(controller)));
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .find_map(|(driver, controller, child)| {
+            let component_name = open_component_name(driver)?;
+            let language = component_name_language(&component_name)?;
+            component_name
+                .controller_name(controller, child, language)
+                .ok()
+                .map(CString16::from)
+        })
+}
+
+/// Names of the protocols known to this crate, for [`protocol_name`].
+static PROTOCOL_NAMES: &[(Guid, &str)] = {
+    use crate::Identify;
+    use crate::proto::debug::{DebugPort, DebugSupport};
+    use crate::proto::device_path::LoadedImageDevicePath;
+    use crate::proto::media::partition::PartitionInfo;
+    use crate::proto::pi::mp::MpServices;
+    use crate::proto::shim::ShimLock;
+    use uefi_raw::protocol::ata::AtaPassThruProtocol;
+    use uefi_raw::protocol::block::BlockIoProtocol;
+    use uefi_raw::protocol::console::serial::SerialIoProtocol;
+    use uefi_raw::protocol::console::{
+        AbsolutePointerProtocol, GraphicsOutputProtocol, SimplePointerProtocol,
+        SimpleTextInputProtocol, SimpleTextOutputProtocol,
+    };
+    use uefi_raw::protocol::device_path::{
+        DevicePathFromTextProtocol, DevicePathProtocol, DevicePathToTextProtocol,
+        DevicePathUtilitiesProtocol,
+    };
+    use uefi_raw::protocol::disk::{DiskInfoProtocol, DiskIo2Protocol, DiskIoProtocol};
+    use uefi_raw::protocol::driver::{ComponentName2Protocol, DriverBindingProtocol};
+    use uefi_raw::protocol::file_system::SimpleFileSystemProtocol;
+    use uefi_raw::protocol::firmware_volume::{
+        FirmwareVolume2Protocol, FirmwareVolumeBlock2Protocol,
+    };
+    use uefi_raw::protocol::hii::config::{ConfigKeywordHandlerProtocol, HiiConfigAccessProtocol};
+    use uefi_raw::protocol::hii::database::HiiDatabaseProtocol;
+    use uefi_raw::protocol::loaded_image::LoadedImageProtocol;
+    use uefi_raw::protocol::media::{LoadFile2Protocol, LoadFileProtocol};
+    use uefi_raw::protocol::memory_protection::MemoryAttributeProtocol;
+    use uefi_raw::protocol::misc::{ResetNotificationProtocol, TimestampProtocol};
+    use uefi_raw::protocol::network::dhcp4::Dhcp4Protocol;
+    use uefi_raw::protocol::network::http::HttpProtocol;
+    use uefi_raw::protocol::network::ip4_config2::Ip4Config2Protocol;
+    use uefi_raw::protocol::network::pxe::PxeBaseCodeProtocol;
+    use uefi_raw::protocol::network::snp::SimpleNetworkProtocol;
+    use uefi_raw::protocol::network::tls::TlsConfigurationProtocol;
+    use uefi_raw::protocol::nvme::NvmExpressPassThruProtocol;
+    use uefi_raw::protocol::pci::root_bridge::PciRootBridgeIoProtocol;
+    use uefi_raw::protocol::rng::RngProtocol;
+    use uefi_raw::protocol::scsi::{ExtScsiPassThruProtocol, ScsiIoProtocol};
+    use uefi_raw::protocol::shell::ShellProtocol;
+    use uefi_raw::protocol::shell_params::ShellParametersProtocol;
+    use uefi_raw::protocol::string::UnicodeCollationProtocol;
+    use uefi_raw::protocol::tcg::v1::TcgProtocol;
+    use uefi_raw::protocol::tcg::v2::Tcg2Protocol;
+    use uefi_raw::protocol::usb::host_controller::Usb2HostControllerProtocol;
+    use uefi_raw::protocol::usb::io::UsbIoProtocol;
+
+    &[
+        (AbsolutePointerProtocol::GUID, "AbsolutePointer"),
+        (AtaPassThruProtocol::GUID, "AtaPassThru"),
+        (BlockIoProtocol::GUID, "BlockIo"),
+        (
+            ComponentName2Protocol::DEPRECATED_COMPONENT_NAME_GUID,
+            "ComponentName",
+        ),
+        (ComponentName2Protocol::GUID, "ComponentName2"),
+        (DebugPort::GUID, "DebugPort"),
+        (DebugSupport::GUID, "DebugSupport"),
+        (DevicePathFromTextProtocol::GUID, "DevicePathFromText"),
+        (DevicePathProtocol::GUID, "DevicePath"),
+        (DevicePathToTextProtocol::GUID, "DevicePathToText"),
+        (DevicePathUtilitiesProtocol::GUID, "DevicePathUtilities"),
+        (Dhcp4Protocol::GUID, "Dhcp4"),
+        (Dhcp4Protocol::SERVICE_BINDING_GUID, "Dhcp4ServiceBinding"),
+        (DiskInfoProtocol::GUID, "DiskInfo"),
+        (DiskIo2Protocol::GUID, "DiskIo2"),
+        (DiskIoProtocol::GUID, "DiskIo"),
+        (DriverBindingProtocol::GUID, "DriverBinding"),
+        (FirmwareVolume2Protocol::GUID, "FirmwareVolume2"),
+        (FirmwareVolumeBlock2Protocol::GUID, "FirmwareVolumeBlock2"),
+        (GraphicsOutputProtocol::GUID, "GraphicsOutput"),
+        (HiiConfigAccessProtocol::GUID, "HiiConfigAccess"),
+        (
+            ConfigKeywordHandlerProtocol::GUID,
+            "HiiConfigKeywordHandler",
+        ),
+        (HiiDatabaseProtocol::GUID, "HiiDatabase"),
+        (HttpProtocol::GUID, "Http"),
+        (HttpProtocol::SERVICE_BINDING_GUID, "HttpServiceBinding"),
+        (Ip4Config2Protocol::GUID, "Ip4Config2"),
+        (LoadFile2Protocol::GUID, "LoadFile2"),
+        (LoadFileProtocol::GUID, "LoadFile"),
+        (LoadedImageDevicePath::GUID, "LoadedImageDevicePath"),
+        (LoadedImageProtocol::GUID, "LoadedImage"),
+        (MemoryAttributeProtocol::GUID, "MemoryAttribute"),
+        (MpServices::GUID, "MpServices"),
+        (NvmExpressPassThruProtocol::GUID, "NvmExpressPassThru"),
+        (PartitionInfo::GUID, "PartitionInfo"),
+        (PciRootBridgeIoProtocol::GUID, "PciRootBridgeIo"),
+        (PxeBaseCodeProtocol::GUID, "PxeBaseCode"),
+        (ResetNotificationProtocol::GUID, "ResetNotification"),
+        (RngProtocol::GUID, "Rng"),
+        (ExtScsiPassThruProtocol::GUID, "ExtScsiPassThru"),
+        (ScsiIoProtocol::GUID, "ScsiIo"),
+        (SerialIoProtocol::GUID, "SerialIo"),
+        (ShellParametersProtocol::GUID, "ShellParameters"),
+        (ShellProtocol::GUID, "Shell"),
+        (ShimLock::GUID, "ShimLock"),
+        (SimpleFileSystemProtocol::GUID, "SimpleFileSystem"),
+        (SimpleNetworkProtocol::GUID, "SimpleNetwork"),
+        (SimplePointerProtocol::GUID, "SimplePointer"),
+        (SimpleTextInputProtocol::GUID, "SimpleTextInput"),
+        (SimpleTextOutputProtocol::GUID, "SimpleTextOutput"),
+        (Tcg2Protocol::GUID, "Tcg2"),
+        (TcgProtocol::GUID, "Tcg"),
+        (TimestampProtocol::GUID, "Timestamp"),
+        (TlsConfigurationProtocol::GUID, "TlsConfiguration"),
+        (
+            TlsConfigurationProtocol::SERVICE_BINDING_GUID,
+            "TlsServiceBinding",
+        ),
+        (UnicodeCollationProtocol::GUID, "UnicodeCollation2"),
+        (Usb2HostControllerProtocol::GUID, "Usb2HostController"),
+        (UsbIoProtocol::GUID, "UsbIo"),
+    ]
+};
+
+/// A snapshot of the handle database, returned by [`handle_database`].
+///
+/// The snapshot is plain owned data with public fields, so it can be kept
+/// after the handles have changed or compared with a later snapshot.
+///
+/// # Binary format
+///
+/// [`to_bytes`] serializes a snapshot so that it can be stored in a file,
+/// and [`from_bytes`] parses it again. All integers are little-endian, and
+/// there is no padding. The snapshot starts with a header:
+///
+/// | Offset | Size | Field                                  |
+/// |--------|------|----------------------------------------|
+/// | 0      | 8    | Magic, the ASCII string `UEFIHNDB`     |
+/// | 8      | 4    | Format version, currently `1`          |
+/// | 12     | 4    | Number of handles                      |
+///
+/// The handles follow, in the order of [`handles`]:
+///
+/// | Offset       | Size | Field                                  |
+/// |--------------|------|----------------------------------------|
+/// | 0            | 8    | Handle                                 |
+/// | 8            | 4    | Size `P` of the device path text       |
+/// | 12           | 4    | Size `D` of the driver name            |
+/// | 16           | 4    | Size `C` of the controller name        |
+/// | 20           | 4    | Number of protocols                    |
+/// | 24           | `P`  | Device path text                       |
+/// | 24+`P`       | `D`  | Driver name                            |
+/// | 24+`P`+`D`   | `C`  | Controller name                        |
+///
+/// Strings are null-terminated UCS-2 strings, and their size is in bytes.
+/// A size of `0` means that the string is not present.
+///
+/// The protocols of the handle follow:
+///
+/// | Offset | Size | Field                                  |
+/// |--------|------|----------------------------------------|
+/// | 0      | 16   | Protocol GUID, in the UEFI byte order  |
+/// | 16     | 4    | Number of open protocol entries        |
+///
+/// Each protocol is followed by its open protocol entries:
+///
+/// | Offset | Size | Field                                  |
+/// |--------|------|----------------------------------------|
+/// | 0      | 8    | Agent handle, or `0` if none           |
+/// | 8      | 8    | Controller handle, or `0` if none      |
+/// | 16     | 4    | [`OpenProtocolAttributeFlags`]         |
+/// | 20     | 4    | Open count                             |
+///
+/// Protocol names are not serialized. [`from_bytes`] resolves them again
+/// with [`protocol_name`].
+///
+/// [`to_bytes`]: Self::to_bytes
+/// [`from_bytes`]: Self::from_bytes
+/// [`handles`]: Self::handles
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HandleDatabase {
+    /// All handles, in the order returned by the firmware.
+    pub handles: Vec<HandleInfo>,
+}
+
+#[cfg(feature = "alloc")]
+impl HandleDatabase {
+    /// Get the information about `handle`, if it was part of the snapshot.
+    #[must_use]
+    pub fn get(&self, handle: Handle) -> Option<&HandleInfo> {
+        self.handles.iter().find(|info| info.handle == handle)
+    }
+
+    /// Serialize the snapshot to the [binary format](Self#binary-format).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `u32::MAX` handles, protocols on a
+    /// handle or open protocol entries on a protocol.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&u32_len(self.handles.len()).to_le_bytes());
+
+        for info in &self.handles {
+            let strings = [&info.device_path, &info.driver_name, &info.controller_name]
+                .map(|s| s.as_ref().map_or(&[][..], |s| s.to_u16_slice_with_nul()));
+            bytes.extend_from_slice(&(info.handle.as_ptr() as u64).to_le_bytes());
+            for s in strings {
+                bytes.extend_from_slice(&u32_len(size_of_val(s)).to_le_bytes());
+            }
+            bytes.extend_from_slice(&u32_len(info.protocols.len()).to_le_bytes());
+            for c in strings.into_iter().flatten() {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+
+            for protocol in &info.protocols {
+                bytes.extend_from_slice(&protocol.guid.to_bytes());
+                bytes.extend_from_slice(&u32_len(protocol.open_info.len()).to_le_bytes());
+                for open in &protocol.open_info {
+                    for handle in [open.agent, open.controller] {
+                        bytes.extend_from_slice(&(Handle::opt_to_ptr(handle) as u64).to_le_bytes());
+                    }
+                    bytes.extend_from_slice(&open.attributes.bits().to_le_bytes());
+                    bytes.extend_from_slice(&open.open_count.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Parse a snapshot from the [binary format](Self#binary-format).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HandleDatabaseParseError`] if `bytes` is not a valid
+    /// snapshot.
+    ///
+    /// # Safety
+    ///
+    /// The handles in the snapshot are only valid in the boot they were
+    /// taken in. If `bytes` were written in another boot, or the handles
+    /// have been removed since, the handles of the parsed snapshot must
+    /// only be used as identifiers, and not be passed to the firmware.
+    pub unsafe fn from_bytes(bytes: &[u8]) -> core::result::Result<Self, HandleDatabaseParseError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(HandleDatabaseParseError::InvalidMagic);
+        }
+        let version = reader.u32()?;
+        if version != VERSION {
+            return Err(HandleDatabaseParseError::UnsupportedVersion(version));
+        }
+
+        // Do not trust the counts for the allocation sizes.
+        let count = reader.count(HANDLE_HEADER_SIZE)?;
+        let mut handles = Vec::with_capacity(count);
+        for _ in 0..count {
+            // SAFETY: the caller is responsible for the use of the handles.
+            let handle =
+                unsafe { reader.handle()? }.ok_or(HandleDatabaseParseError::InvalidHandle)?;
+            let sizes = [reader.u32()?, reader.u32()?, reader.u32()?];
+            let protocol_count = reader.count(PROTOCOL_HEADER_SIZE)?;
+            let device_path = reader.string(sizes[0])?;
+            let driver_name = reader.string(sizes[1])?;
+            let controller_name = reader.string(sizes[2])?;
+
+            let mut protocols = Vec::with_capacity(protocol_count);
+            for _ in 0..protocol_count {
+                let guid = Guid::from_bytes(reader.take(16)?.try_into().unwrap());
+                let open_count = reader.count(OPEN_INFO_SIZE)?;
+                let mut open_info = Vec::with_capacity(open_count);
+                for _ in 0..open_count {
+                    // SAFETY: the caller is responsible for the use of the
+                    // handles.
+                    let (agent, controller) = unsafe { (reader.handle()?, reader.handle()?) };
+                    open_info.push(OpenProtocolInfo {
+                        agent,
+                        controller,
+                        attributes: OpenProtocolAttributeFlags::from_bits_retain(reader.u32()?),
+                        open_count: reader.u32()?,
+                    });
+                }
+                protocols.push(ProtocolInfo {
+                    guid,
+                    name: protocol_name(&guid),
+                    open_info,
+                });
+            }
+
+            handles.push(HandleInfo {
+                handle,
+                protocols,
+                device_path,
+                driver_name,
+                controller_name,
+            });
+        }
+
+        if !reader.0.is_empty() {
+            return Err(HandleDatabaseParseError::TrailingData);
+        }
+        Ok(Self { handles })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for HandleDatabase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for info in &self.handles {
+            write!(f, "{info}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Magic at the start of a serialized [`HandleDatabase`].
+#[cfg(feature = "alloc")]
+const MAGIC: [u8; 8] = *b"UEFIHNDB";
+
+/// Current version of the binary format.
+#[cfg(feature = "alloc")]
+const VERSION: u32 = 1;
+
+/// Size of the fixed part of a serialized handle.
+#[cfg(feature = "alloc")]
+const HANDLE_HEADER_SIZE: usize = 24;
+
+/// Size of the fixed part of a serialized protocol.
+#[cfg(feature = "alloc")]
+const PROTOCOL_HEADER_SIZE: usize = 20;
+
+/// Size of a serialized open protocol entry.
+#[cfg(feature = "alloc")]
+const OPEN_INFO_SIZE: usize = 24;
+
+/// Convert a count to `u32` for the binary format.
+#[cfg(feature = "alloc")]
+fn u32_len(len: usize) -> u32 {
+    u32::try_from(len).expect("handle database field is larger than u32::MAX")
+}
+
+/// Reads the fields of a serialized [`HandleDatabase`].
+#[cfg(feature = "alloc")]
+struct Reader<'a>(&'a [u8]);
+
+#[cfg(feature = "alloc")]
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> core::result::Result<&'a [u8], HandleDatabaseParseError> {
+        let (head, tail) = self
+            .0
+            .split_at_checked(len)
+            .ok_or(HandleDatabaseParseError::TooShort)?;
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> core::result::Result<u32, HandleDatabaseParseError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Read the number of items of at least `item_size` bytes that follow,
+    /// checking that the remaining data is large enough for them.
+    fn count(&mut self, item_size: usize) -> core::result::Result<usize, HandleDatabaseParseError> {
+        let count = self.u32()? as usize;
+        if count > self.0.len() / item_size {
+            return Err(HandleDatabaseParseError::TooShort);
+        }
+        Ok(count)
+    }
+
+    /// Read a handle, which is `None` if it is zero.
+    ///
+    /// # Safety
+    ///
+    /// See [`HandleDatabase::from_bytes`].
+    unsafe fn handle(&mut self) -> core::result::Result<Option<Handle>, HandleDatabaseParseError> {
+        let value = u64::from_le_bytes(self.take(8)?.try_into().unwrap());
+        let addr = usize::try_from(value).map_err(|_| HandleDatabaseParseError::InvalidHandle)?;
+        Ok(unsafe { Handle::from_ptr(addr as *mut c_void) })
+    }
+
+    /// Read a string of `size` bytes, which is `None` if `size` is zero.
+    fn string(
+        &mut self,
+        size: u32,
+    ) -> core::result::Result<Option<CString16>, HandleDatabaseParseError> {
+        let bytes = self.take(size as usize)?;
+        if size == 0 {
+            return Ok(None);
+        }
+        if size % 2 != 0 {
+            return Err(HandleDatabaseParseError::InvalidString);
+        }
+        let chars: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        CStr16::from_u16_with_nul(&chars)
+            .map(|s| Some(s.to_owned()))
+            .map_err(|_| HandleDatabaseParseError::InvalidString)
+    }
+}
+
+/// Error returned by [`HandleDatabase::from_bytes`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HandleDatabaseParseError {
+    /// The data does not start with the handle database magic.
+    InvalidMagic,
+
+    /// The format version is not supported.
+    UnsupportedVersion(u32),
+
+    /// The data ends before the end of the handle database.
+    TooShort,
+
+    /// A handle is null, or does not fit in a pointer.
+    InvalidHandle,
+
+    /// A string is not a null-terminated UCS-2 string.
+    InvalidString,
+
+    /// There is data after the last handle.
+    TrailingData,
+}
+
+#[cfg(feature = "alloc")]
+impl Display for HandleDatabaseParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => f.write_str("the data is not a handle database"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported handle database version {version}")
+            }
+            Self::TooShort => f.write_str("the handle database data is too short"),
+            Self::InvalidHandle => f.write_str("a handle is not a valid pointer"),
+            Self::InvalidString => f.write_str("a string is not a valid UCS-2 string"),
+            Self::TrailingData => f.write_str("the handle database has trailing data"),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for HandleDatabaseParseError {}
+
+/// A handle in a [`HandleDatabase`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandleInfo {
+    /// The handle.
+    pub handle: Handle,
+
+    /// The protocols installed on the handle.
+    pub protocols: Vec<ProtocolInfo>,
+
+    /// Text representation of the handle's device path, if it has one and
+    /// the firmware supports converting it to text.
+    pub device_path: Option<CString16>,
+
+    /// Name of the driver, if the handle has a component name protocol.
+    pub driver_name: Option<CString16>,
+
+    /// Name of the controller, as reported by the driver that manages it.
+    pub controller_name: Option<CString16>,
+}
+
+#[cfg(feature = "alloc")]
+impl HandleInfo {
+    /// Get the information about `protocol`, if it is installed on the
+    /// handle.
+    #[must_use]
+    pub fn protocol(&self, protocol: &Guid) -> Option<&ProtocolInfo> {
+        self.protocols.iter().find(|info| info.guid == *protocol)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for HandleInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Handle {:p}", self.handle.as_ptr())?;
+        if let Some(device_path) = &self.device_path {
+            writeln!(f, "  Device path: {device_path}")?;
+        }
+        if let Some(name) = &self.driver_name {
+            writeln!(f, "  Driver name: {name}")?;
+        }
+        if let Some(name) = &self.controller_name {
+            writeln!(f, "  Controller name: {name}")?;
+        }
+        for protocol in &self.protocols {
+            write!(f, "{protocol}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A protocol installed on a handle in a [`HandleDatabase`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolInfo {
+    /// The protocol GUID.
+    pub guid: Guid,
+
+    /// The name of the protocol, see [`protocol_name`].
+    pub name: Option<&'static str>,
+
+    /// The agents that have opened the protocol.
+    pub open_info: Vec<OpenProtocolInfo>,
+}
+
+#[cfg(feature = "alloc")]
+impl Display for ProtocolInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.name {
+            Some(name) => writeln!(f, "  {name} ({})", self.guid)?,
+            None => writeln!(f, "  {}", self.guid)?,
+        }
+        for open in &self.open_info {
+            writeln!(f, "    {open}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An agent that has opened a protocol, in a [`HandleDatabase`]. This is an
+/// owned copy of an [`OpenProtocolInformationEntry`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OpenProtocolInfo {
+    /// The handle of the agent that opened the protocol.
+    pub agent: Option<Handle>,
+
+    /// The controller handle the protocol was opened for, if any.
+    pub controller: Option<Handle>,
+
+    /// The attributes the protocol was opened with.
+    pub attributes: OpenProtocolAttributeFlags,
+
+    /// The number of times the agent has opened the protocol with these
+    /// parameters.
+    pub open_count: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl From<&OpenProtocolInformationEntry> for OpenProtocolInfo {
+    fn from(entry: &OpenProtocolInformationEntry) -> Self {
+        Self {
+            agent: entry.agent_handle(),
+            controller: entry.controller_handle(),
+            attributes: entry.attributes(),
+            open_count: entry.open_count(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for OpenProtocolInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Opened by {:p}", Handle::opt_to_ptr(self.agent))?;
+        if let Some(controller) = self.controller {
+            write!(f, " for {:p}", controller.as_ptr())?;
+        }
+        write!(f, ": {:?}, count {}", self.attributes, self.open_count)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{cstr16, guid};
+    use alloc::vec;
+
+    fn handle(addr: usize) -> Handle {
+        unsafe { Handle::from_ptr(addr as *mut c_void) }.unwrap()
+    }
+
+    fn database() -> HandleDatabase {
+        let loaded_image = guid!("5b1b31a1-9562-11d2-8e3f-00a0c969723b");
+        HandleDatabase {
+            handles: vec![
+                HandleInfo {
+                    handle: handle(0x1000),
+                    protocols: vec![ProtocolInfo {
+                        guid: loaded_image,
+                        name: protocol_name(&loaded_image),
+                        open_info: vec![OpenProtocolInfo {
+                            agent: Some(handle(0x2000)),
+                            controller: None,
+                            attributes: OpenProtocolAttributeFlags::GET_PROTOCOL,
+                            open_count: 2,
+                        }],
+                    }],
+                    device_path: Some(cstr16!("PciRoot(0x0)").into()),
+                    driver_name: None,
+                    controller_name: Some(cstr16!("Disk").into()),
+                },
+                HandleInfo {
+                    handle: handle(0x2000),
+                    protocols: vec![ProtocolInfo {
+                        guid: guid!("01234567-89ab-cdef-0123-456789abcdef"),
+                        name: None,
+                        open_info: Vec::new(),
+                    }],
+                    device_path: None,
+                    driver_name: Some(cstr16!("Driver").into()),
+                    controller_name: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let db = HandleDatabase {
+            handles: vec![HandleInfo {
+                handle: handle(0x1000),
+                protocols: vec![ProtocolInfo {
+                    guid: guid!("01234567-89ab-cdef-0123-456789abcdef"),
+                    name: None,
+                    open_info: vec![OpenProtocolInfo {
+                        agent: Some(handle(0x2000)),
+                        controller: None,
+                        attributes: OpenProtocolAttributeFlags::BY_DRIVER,
+                        open_count: 1,
+                    }],
+                }],
+                device_path: None,
+                driver_name: Some(cstr16!("A").into()),
+                controller_name: None,
+            }],
+        };
+        let mut expected = b"UEFIHNDB".to_vec();
+        expected.extend([1, 0, 0, 0, 1, 0, 0, 0]);
+        expected.extend([0x00, 0x10, 0, 0, 0, 0, 0, 0]);
+        expected.extend([0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+        expected.extend([b'A', 0, 0, 0]);
+        expected.extend(guid!("01234567-89ab-cdef-0123-456789abcdef").to_bytes());
+        expected.extend([1, 0, 0, 0]);
+        expected.extend([0x00, 0x20, 0, 0, 0, 0, 0, 0]);
+        expected.extend([0; 8]);
+        expected.extend([0x10, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(db.to_bytes(), expected);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let db = database();
+        let bytes = db.to_bytes();
+        assert_eq!(unsafe { HandleDatabase::from_bytes(&bytes) }, Ok(db));
+
+        let empty = HandleDatabase::default();
+        assert_eq!(
+            unsafe { HandleDatabase::from_bytes(&empty.to_bytes()) },
+            Ok(empty)
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_errors() {
+        let bytes = database().to_bytes();
+        let parse = |bytes: &[u8]| unsafe { HandleDatabase::from_bytes(bytes) };
+
+        let mut invalid = bytes.clone();
+        invalid[0] = b'X';
+        assert_eq!(parse(&invalid), Err(HandleDatabaseParseError::InvalidMagic));
+
+        let mut invalid = bytes.clone();
+        invalid[8] = 2;
+        assert_eq!(
+            parse(&invalid),
+            Err(HandleDatabaseParseError::UnsupportedVersion(2))
+        );
+
+        assert_eq!(
+            parse(&bytes[..bytes.len() - 1]),
+            Err(HandleDatabaseParseError::TooShort)
+        );
+
+        // A huge handle count must not be trusted for the allocation.
+        let mut invalid = bytes.clone();
+        invalid[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(parse(&invalid), Err(HandleDatabaseParseError::TooShort));
+
+        let mut invalid = bytes.clone();
+        invalid[16..24].fill(0);
+        assert_eq!(
+            parse(&invalid),
+            Err(HandleDatabaseParseError::InvalidHandle)
+        );
+
+        // Remove the null terminator of the device path.
+        let mut invalid = bytes.clone();
+        let end = 40 + "PciRoot(0x0)".len() * 2;
+        invalid[end] = b'!';
+        assert_eq!(
+            parse(&invalid),
+            Err(HandleDatabaseParseError::InvalidString)
+        );
+
+        let mut invalid = bytes.clone();
+        invalid.push(0);
+        assert_eq!(parse(&invalid), Err(HandleDatabaseParseError::TrailingData));
+    }
+}
@@ -103,7 +103,8 @@
 //!
 //! Most UEFI functionality comes from the system, boot, and runtime
 //! tables. These can be accessed via the [`system`], [`boot`], and [`runtime`]
-//! modules.
+//! modules. The ACPI tables found through the configuration table can be
+//! parsed with the [`acpi`] module.
 //!
 //! ## Protocols
 //!
@@ -255,6 +256,7 @@ extern crate uefi_raw;
 
 #[macro_use]
 pub mod data_types;
+pub mod acpi;
 pub mod allocator;
 pub mod boot;
 #[cfg(feature = "alloc")]
@@ -264,8 +266,11 @@ pub mod mem;
 pub mod prelude;
 pub mod proto;
 pub mod runtime;
+pub mod sync;
 pub mod system;
 pub mod table;
+#[cfg(feature = "alloc")]
+pub mod task;
 
 pub(crate) mod polyfill;
 
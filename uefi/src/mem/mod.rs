@@ -19,6 +19,9 @@ mod aligned_buffer;
 #[cfg(feature = "alloc")]
 pub use aligned_buffer::{AlignedBuffer, AlignmentError};
 
+mod pages;
+pub use pages::{PageBox, Pages};
+
 /// Wrapper for memory allocated with UEFI's pool allocator. The memory is freed
 /// on drop.
 #[derive(Debug)]
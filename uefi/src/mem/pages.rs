@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Owned page allocations.
+
+use crate::boot::{self, AllocateType};
+use crate::data_types::PhysicalAddress;
+use crate::mem::memory_map::MemoryType;
+use crate::{Result, Status};
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use core::slice;
+use uefi_raw::table::boot::PAGE_SIZE;
+
+/// Consecutive pages of memory allocated with [`boot::allocate_pages`].
+///
+/// The pages are freed with [`boot::free_pages`] when this value is dropped,
+/// unless boot services have been exited. Use [`leak`] to keep the pages
+/// allocated, for example to hand them over to an OS kernel.
+///
+/// The memory is not initialized.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::mem::Pages;
+/// use uefi::mem::memory_map::MemoryType;
+///
+/// # fn test() -> uefi::Result {
+/// // Allocate a DMA buffer below 4 GiB.
+/// let mut pages = Pages::allocate_below(0xffff_ffff, 4, MemoryType::LOADER_DATA)?;
+/// pages.as_uninit_slice_mut().fill(core::mem::MaybeUninit::new(0));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`leak`]: Self::leak
+pub struct Pages {
+    ptr: NonNull<u8>,
+    count: usize,
+    memory_type: MemoryType,
+}
+
+impl Pages {
+    /// Allocate `count` pages at any address.
+    ///
+    /// # Errors
+    ///
+    /// See [`boot::allocate_pages`].
+    pub fn allocate(count: usize, memory_type: MemoryType) -> Result<Self> {
+        Self::allocate_with(AllocateType::AnyPages, count, memory_type)
+    }
+
+    /// Allocate `count` pages that end at or below `max_address`.
+    ///
+    /// For example, use `0xffff_ffff` to allocate memory below 4 GiB for
+    /// devices that only support 32-bit DMA.
+    ///
+    /// # Errors
+    ///
+    /// See [`boot::allocate_pages`].
+    pub fn allocate_below(
+        max_address: PhysicalAddress,
+        count: usize,
+        memory_type: MemoryType,
+    ) -> Result<Self> {
+        Self::allocate_with(AllocateType::MaxAddress(max_address), count, memory_type)
+    }
+
+    /// Allocate `count` pages starting at `address`, which must be page
+    /// aligned.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::NOT_FOUND`]: the pages at `address` are not available.
+    /// * See [`boot::allocate_pages`] for other errors.
+    pub fn allocate_at(
+        address: PhysicalAddress,
+        count: usize,
+        memory_type: MemoryType,
+    ) -> Result<Self> {
+        Self::allocate_with(AllocateType::Address(address), count, memory_type)
+    }
+
+    /// Allocate `count` pages with the given allocation strategy.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `count` is zero.
+    /// * See [`boot::allocate_pages`] for other errors.
+    pub fn allocate_with(
+        allocation_type: AllocateType,
+        count: usize,
+        memory_type: MemoryType,
+    ) -> Result<Self> {
+        if count == 0 {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        let ptr = boot::allocate_pages(allocation_type, memory_type, count)?;
+        Ok(Self {
+            ptr,
+            count,
+            memory_type,
+        })
+    }
+
+    /// Take ownership of pages allocated with [`boot::allocate_pages`], for
+    /// example pages previously leaked with [`leak`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to `count` pages allocated with
+    /// [`boot::allocate_pages`] with `memory_type`, which are not owned by
+    /// anything else.
+    ///
+    /// [`leak`]: Self::leak
+    #[must_use]
+    pub const unsafe fn from_raw(ptr: NonNull<u8>, count: usize, memory_type: MemoryType) -> Self {
+        Self {
+            ptr,
+            count,
+            memory_type,
+        }
+    }
+
+    /// Pointer to the start of the pages.
+    #[must_use]
+    pub const fn as_ptr(&self) -> NonNull<u8> {
+        self.ptr
+    }
+
+    /// Physical address of the start of the pages.
+    #[must_use]
+    pub fn phys_addr(&self) -> PhysicalAddress {
+        self.ptr.as_ptr() as PhysicalAddress
+    }
+
+    /// Number of pages.
+    #[must_use]
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Size of the pages in bytes.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.count * PAGE_SIZE
+    }
+
+    /// The memory type the pages were allocated with.
+    #[must_use]
+    pub const fn memory_type(&self) -> MemoryType {
+        self.memory_type
+    }
+
+    /// The memory of the pages, which may be uninitialized.
+    #[must_use]
+    pub const fn as_uninit_slice(&self) -> &[MaybeUninit<u8>] {
+        // SAFETY: the pages are valid for `size` bytes, and uninitialized
+        // memory is valid for `MaybeUninit`.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr().cast(), self.size()) }
+    }
+
+    /// The memory of the pages, which may be uninitialized.
+    #[must_use]
+    pub const fn as_uninit_slice_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        // SAFETY: the pages are valid for `size` bytes and owned by `self`,
+        // and uninitialized memory is valid for `MaybeUninit`.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr().cast(), self.size()) }
+    }
+
+    /// Keep the pages allocated, and return a pointer to them.
+    ///
+    /// The pages remain allocated with [`memory_type`] in the memory map,
+    /// so they can be handed over to an OS kernel after exiting boot
+    /// services. Use [`from_raw`] to take ownership of them again.
+    ///
+    /// [`memory_type`]: Self::memory_type
+    /// [`from_raw`]: Self::from_raw
+    #[must_use]
+    pub fn leak(self) -> NonNull<u8> {
+        ManuallyDrop::new(self).ptr
+    }
+}
+
+impl Debug for Pages {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pages")
+            .field("ptr", &self.ptr)
+            .field("count", &self.count)
+            .field("memory_type", &self.memory_type)
+            .finish()
+    }
+}
+
+impl Drop for Pages {
+    fn drop(&mut self) {
+        // After exiting boot services, the pages can't be freed anyway.
+        if !boot::are_boot_services_active() {
+            return;
+        }
+        // SAFETY: the pages are owned by `self` and not used after this.
+        if let Err(err) = unsafe { boot::free_pages(self.ptr, self.count) } {
+            log::error!(
+                "Failed to free {} pages at {:?}: {err}",
+                self.count,
+                self.ptr
+            );
+        }
+    }
+}
+
+/// A value of type `T` stored in its own [`Pages`], similar to a `Box<T>`.
+///
+/// This is useful for values that must be page aligned, live at a particular
+/// physical address, or be allocated with a particular [`MemoryType`], such
+/// as page tables, AP trampolines or boot information for an OS kernel.
+///
+/// The value is dropped and the pages are freed when the `PageBox` is
+/// dropped, unless boot services have been exited. Use [`leak`] to keep the
+/// value allocated.
+///
+/// `T` must not require an alignment of more than [`PAGE_SIZE`].
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::boot::AllocateType;
+/// use uefi::mem::PageBox;
+/// use uefi::mem::memory_map::MemoryType;
+///
+/// #[repr(C, align(4096))]
+/// struct PageTable([u64; 512]);
+///
+/// # fn test() -> uefi::Result {
+/// let table = PageBox::new_with(
+///     AllocateType::MaxAddress(0xffff_ffff),
+///     MemoryType::LOADER_DATA,
+///     PageTable([0; 512]),
+/// )?;
+/// let addr = PageBox::pages(&table).phys_addr();
+/// // Keep the page table alive after exiting boot services.
+/// let table: &'static mut PageTable = PageBox::leak(table);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`leak`]: Self::leak
+pub struct PageBox<T> {
+    // The value is dropped in `Drop`, before the pages are freed.
+    pages: ManuallyDrop<Pages>,
+    _value: PhantomData<T>,
+}
+
+impl<T> PageBox<T> {
+    /// Number of pages needed to store a `T`.
+    const PAGE_COUNT: usize = {
+        assert!(
+            mem::align_of::<T>() <= PAGE_SIZE,
+            "alignment of T is larger than a page"
+        );
+        // Zero-sized values still get a page, so that the pointer is unique.
+        let count = mem::size_of::<T>().div_ceil(PAGE_SIZE);
+        if count == 0 { 1 } else { count }
+    };
+
+    /// Move `value` into pages allocated at any address.
+    ///
+    /// # Errors
+    ///
+    /// See [`boot::allocate_pages`].
+    pub fn new(memory_type: MemoryType, value: T) -> Result<Self> {
+        Self::new_with(AllocateType::AnyPages, memory_type, value)
+    }
+
+    /// Move `value` into pages allocated with the given allocation strategy.
+    ///
+    /// # Errors
+    ///
+    /// See [`boot::allocate_pages`].
+    pub fn new_with(
+        allocation_type: AllocateType,
+        memory_type: MemoryType,
+        value: T,
+    ) -> Result<Self> {
+        let pages = Pages::allocate_with(allocation_type, Self::PAGE_COUNT, memory_type)?;
+        // SAFETY: the pages are large enough and sufficiently aligned for a
+        // `T`, see `PAGE_COUNT`.
+        unsafe { pages.ptr.cast::<T>().write(value) };
+        Ok(Self {
+            pages: ManuallyDrop::new(pages),
+            _value: PhantomData,
+        })
+    }
+
+    /// The pages that store the value.
+    #[must_use]
+    pub fn pages(this: &Self) -> &Pages {
+        &this.pages
+    }
+
+    /// Keep the value and its pages allocated, and return a reference to the
+    /// value.
+    ///
+    /// This is an associated function, so that it doesn't shadow methods of
+    /// `T`. Call it as `PageBox::leak(b)`.
+    #[must_use]
+    pub fn leak<'a>(this: Self) -> &'a mut T {
+        let this = ManuallyDrop::new(this);
+        // SAFETY: the value is initialized, and is never dropped or freed.
+        unsafe { this.pages.ptr.cast::<T>().as_mut() }
+    }
+
+    /// Move the value out of its pages, and free them.
+    #[must_use]
+    pub fn into_inner(this: Self) -> T {
+        let mut this = ManuallyDrop::new(this);
+        // SAFETY: the value is initialized, and is not used again after
+        // reading it, since the pages are freed immediately.
+        let value = unsafe { this.pages.ptr.cast::<T>().read() };
+        // SAFETY: the pages are not used after this.
+        unsafe { ManuallyDrop::drop(&mut this.pages) };
+        value
+    }
+}
+
+impl<T> Deref for PageBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the value is initialized, and owned by `self`.
+        unsafe { self.pages.ptr.cast::<T>().as_ref() }
+    }
+}
+
+impl<T> DerefMut for PageBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the value is initialized, and owned by `self`.
+        unsafe { self.pages.ptr.cast::<T>().as_mut() }
+    }
+}
+
+impl<T: Debug> Debug for PageBox<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageBox")
+            .field("pages", &*self.pages)
+            .field("value", &**self)
+            .finish()
+    }
+}
+
+impl<T> Drop for PageBox<T> {
+    fn drop(&mut self) {
+        // After exiting boot services, the pages can't be freed, so keep
+        // the value alive as well.
+        if !boot::are_boot_services_active() {
+            return;
+        }
+        // SAFETY: the value is initialized, and not used after this.
+        unsafe { self.pages.ptr.cast::<T>().drop_in_place() };
+        // SAFETY: the pages are not used after this.
+        unsafe { ManuallyDrop::drop(&mut self.pages) };
+    }
+}
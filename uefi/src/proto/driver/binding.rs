@@ -0,0 +1,354 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Driver binding, for writing drivers that follow the UEFI Driver Model.
+
+use crate::boot::{self, OpenProtocolAttributes, OpenProtocolParams, SearchType};
+use crate::proto::device_path::{DevicePath, FfiDevicePath};
+use crate::proto::loaded_image::LoadedImage;
+use crate::proto::producer::{InstalledProtocol, ProtocolImpl};
+use crate::proto::unsafe_protocol;
+use crate::{Error, Guid, Handle, Result, Status};
+use core::fmt::{self, Debug, Formatter};
+use core::ptr::NonNull;
+use core::slice;
+use uefi_raw::protocol::device_path::DevicePathProtocol;
+use uefi_raw::protocol::driver::DriverBindingProtocol;
+
+/// A driver that follows the UEFI Driver Model.
+///
+/// The firmware uses the driver binding to connect the driver to
+/// controllers: [`supported`] is called to check whether the driver can
+/// manage a controller, followed by [`start`] to start managing it.
+/// [`stop`] is called when the controller is disconnected.
+///
+/// Install the driver with [`install_driver_binding`] or
+/// [`register_driver`].
+///
+/// The methods take `&self`, since the firmware may call them re-entrantly,
+/// for example when [`start`] connects child controllers. Use interior
+/// mutability for the driver's state.
+///
+/// [`supported`]: Self::supported
+/// [`start`]: Self::start
+/// [`stop`]: Self::stop
+pub trait DriverBinding: 'static {
+    /// Version of the driver. If several drivers support a controller, the
+    /// one with the highest version is started first.
+    ///
+    /// Values `0x0`-`0xf` and `0xffff_fff0`-`0xffff_ffff` are reserved for
+    /// platform and OEM drivers.
+    const VERSION: u32 = 0x10;
+
+    /// Test whether the driver supports `controller`.
+    ///
+    /// `remaining_device_path` is the part of the controller's device path
+    /// that the caller wants to be connected. If it is `None`, all child
+    /// controllers should be considered; if it is an end node, no child
+    /// controllers should be created. Bus drivers should check that the first
+    /// node is supported.
+    ///
+    /// This function must not modify the state of the controller. Protocols
+    /// opened with [`OpenProtocolAttributes::ByDriver`] must be closed before
+    /// returning.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the driver does not support the controller.
+    /// * [`Status::ACCESS_DENIED`]: the controller is managed by a driver
+    ///   that requires exclusive access.
+    /// * [`Status::ALREADY_STARTED`]: the controller is already managed by
+    ///   this driver.
+    fn supported(&self, controller: Handle, remaining_device_path: Option<&DevicePath>) -> Result;
+
+    /// Start managing `controller`.
+    ///
+    /// See [`supported`] for the meaning of `remaining_device_path`.
+    ///
+    /// The driver should open the protocols it uses on the controller with
+    /// [`OpenProtocolAttributes::ByDriver`], which allows the firmware to
+    /// call [`stop`] when the controller is disconnected.
+    ///
+    /// [`supported`]: Self::supported
+    /// [`stop`]: Self::stop
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::DEVICE_ERROR`]: the device could not be started.
+    /// * [`Status::OUT_OF_RESOURCES`]: the driver could not allocate the
+    ///   resources for the controller.
+    fn start(&self, controller: Handle, remaining_device_path: Option<&DevicePath>) -> Result;
+
+    /// Stop managing `controller`.
+    ///
+    /// If `children` is empty, the driver should stop managing the
+    /// controller itself, which means closing all protocols it opened on it.
+    /// Otherwise, bus drivers should destroy the given child handles.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::DEVICE_ERROR`]: the device could not be stopped.
+    fn stop(&self, controller: Handle, children: &[Handle]) -> Result;
+}
+
+/// Protocol implementation for a [`DriverBinding`].
+struct BindingImpl<D> {
+    driver: D,
+    image_handle: Handle,
+    driver_binding_handle: Handle,
+}
+
+unsafe impl<D: DriverBinding> ProtocolImpl for BindingImpl<D> {
+    type Interface = DriverBindingProtocol;
+    const GUID: Guid = DriverBindingProtocol::GUID;
+
+    fn interface(&self) -> DriverBindingProtocol {
+        DriverBindingProtocol {
+            supported: supported::<D>,
+            start: start::<D>,
+            stop: stop::<D>,
+            version: D::VERSION,
+            image_handle: self.image_handle.as_ptr(),
+            driver_binding_handle: self.driver_binding_handle.as_ptr(),
+        }
+    }
+}
+
+/// Convert the result of calling the driver into a status for the firmware.
+const fn to_status(r: Result<Result>) -> Status {
+    match r {
+        Ok(Ok(())) => Status::SUCCESS,
+        Ok(Err(err)) | Err(err) => err.status(),
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must be null or point to a valid device path.
+unsafe fn device_path_from_ptr<'a>(ptr: *const DevicePathProtocol) -> Option<&'a DevicePath> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(unsafe { DevicePath::from_ffi_ptr(ptr.cast::<FfiDevicePath>()) })
+    }
+}
+
+unsafe extern "efiapi" fn supported<D: DriverBinding>(
+    this: *const DriverBindingProtocol,
+    controller: uefi_raw::Handle,
+    remaining_device_path: *const DevicePathProtocol,
+) -> Status {
+    let Some(controller) = (unsafe { Handle::from_ptr(controller) }) else {
+        return Status::INVALID_PARAMETER;
+    };
+    let remaining_device_path = unsafe { device_path_from_ptr(remaining_device_path) };
+    to_status(unsafe {
+        BindingImpl::<D>::with_this_ref(this, |binding| {
+            binding.driver.supported(controller, remaining_device_path)
+        })
+    })
+}
+
+unsafe extern "efiapi" fn start<D: DriverBinding>(
+    this: *const DriverBindingProtocol,
+    controller: uefi_raw::Handle,
+    remaining_device_path: *const DevicePathProtocol,
+) -> Status {
+    let Some(controller) = (unsafe { Handle::from_ptr(controller) }) else {
+        return Status::INVALID_PARAMETER;
+    };
+    let remaining_device_path = unsafe { device_path_from_ptr(remaining_device_path) };
+    to_status(unsafe {
+        BindingImpl::<D>::with_this_ref(this, |binding| {
+            binding.driver.start(controller, remaining_device_path)
+        })
+    })
+}
+
+unsafe extern "efiapi" fn stop<D: DriverBinding>(
+    this: *const DriverBindingProtocol,
+    controller: uefi_raw::Handle,
+    number_of_children: usize,
+    child_handle_buffer: *const uefi_raw::Handle,
+) -> Status {
+    let Some(controller) = (unsafe { Handle::from_ptr(controller) }) else {
+        return Status::INVALID_PARAMETER;
+    };
+    let children = if number_of_children == 0 || child_handle_buffer.is_null() {
+        &[]
+    } else {
+        // SAFETY: `Handle` is a non-null `repr(transparent)` wrapper around
+        // `uefi_raw::Handle`, and child handles are never null.
+        unsafe { slice::from_raw_parts(child_handle_buffer.cast::<Handle>(), number_of_children) }
+    };
+    to_status(unsafe {
+        BindingImpl::<D>::with_this_ref(this, |binding| binding.driver.stop(controller, children))
+    })
+}
+
+/// A [`DriverBinding`] installed with [`install_driver_binding`].
+///
+/// The driver binding is uninstalled when this value is dropped, after
+/// disconnecting the driver from all controllers it manages.
+pub struct InstalledDriverBinding<D: DriverBinding> {
+    protocol: Option<InstalledProtocol<BindingImpl<D>>>,
+}
+
+impl<D: DriverBinding> InstalledDriverBinding<D> {
+    /// The handle the driver binding is installed on.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.protocol().handle()
+    }
+
+    /// Disconnect the driver from all controllers, and uninstall the driver
+    /// binding.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::ACCESS_DENIED`]: the driver binding could not be
+    ///   uninstalled. The driver binding is returned as the error data, and
+    ///   stays installed.
+    pub fn uninstall(mut self) -> Result<D, Self> {
+        disconnect_all(self.handle());
+        // OK to unwrap: the protocol is only taken here and in `Drop`.
+        match self.protocol.take().unwrap().uninstall() {
+            Ok(binding) => Ok(binding.driver),
+            Err(err) => {
+                let (status, protocol) = err.split();
+                self.protocol = Some(protocol);
+                Err(Error::new(status, self))
+            }
+        }
+    }
+
+    const fn protocol(&self) -> &InstalledProtocol<BindingImpl<D>> {
+        // OK to unwrap: the protocol is only taken when `self` is consumed.
+        self.protocol.as_ref().unwrap()
+    }
+}
+
+impl<D: DriverBinding> Debug for InstalledDriverBinding<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstalledDriverBinding")
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
+impl<D: DriverBinding> Drop for InstalledDriverBinding<D> {
+    fn drop(&mut self) {
+        if let Some(protocol) = self.protocol.take() {
+            if boot::are_boot_services_active() {
+                disconnect_all(protocol.handle());
+            }
+            drop(protocol);
+        }
+    }
+}
+
+/// Disconnect the driver `image_handle` from all controllers it manages.
+fn disconnect_all(image_handle: Handle) {
+    let Ok(handles) = boot::locate_handle_buffer(SearchType::AllHandles) else {
+        return;
+    };
+    for handle in handles.iter() {
+        // Fails for most handles, since they are not managed by the driver.
+        let _ = boot::disconnect_controller(*handle, Some(image_handle), None);
+    }
+}
+
+/// Install the driver binding for `driver` on the current image's handle.
+///
+/// The driver binding is uninstalled when the returned value is dropped.
+/// Use [`register_driver`] instead to keep the driver installed until the
+/// image is unloaded.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: a driver binding is already installed on
+///   the image handle.
+pub fn install_driver_binding<D: DriverBinding>(driver: D) -> Result<InstalledDriverBinding<D>> {
+    let image_handle = boot::image_handle();
+    let binding = BindingImpl {
+        driver,
+        image_handle,
+        driver_binding_handle: image_handle,
+    };
+    let protocol = InstalledProtocol::install(Some(image_handle), binding)?;
+    Ok(InstalledDriverBinding {
+        protocol: Some(protocol),
+    })
+}
+
+/// Install the driver binding for `driver` on the current image's handle,
+/// and register an unload function for the image.
+///
+/// This is the usual way to start a driver from its entry point. The driver
+/// stays installed after the entry point returns. When the image is unloaded
+/// with [`boot::unload_image`], the driver is disconnected from all
+/// controllers, and the driver binding is uninstalled.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: a driver binding is already installed on
+///   the image handle.
+/// * [`Status::ACCESS_DENIED`]: the image's [`LoadedImage`] protocol is
+///   already open exclusively.
+pub fn register_driver<D: DriverBinding>(driver: D) -> Result {
+    let installed = install_driver_binding(driver)?;
+    let mut loaded_image = boot::open_protocol_exclusive::<LoadedImage>(boot::image_handle())?;
+    // SAFETY: the unload function is part of the image.
+    unsafe { loaded_image.set_unload(unload::<D>) };
+    // Reclaimed in `unload`.
+    let _ = installed.into_raw();
+    Ok(())
+}
+
+impl<D: DriverBinding> InstalledDriverBinding<D> {
+    fn into_raw(mut self) -> NonNull<DriverBindingProtocol> {
+        // OK to unwrap: the protocol is only taken when `self` is consumed.
+        self.protocol.take().unwrap().into_raw()
+    }
+}
+
+/// The raw driver binding protocol, used by `unload` to find the installed
+/// driver binding.
+#[repr(transparent)]
+#[unsafe_protocol(DriverBindingProtocol::GUID)]
+struct RawDriverBinding(DriverBindingProtocol);
+
+extern "efiapi" fn unload<D: DriverBinding>(image_handle: Handle) -> Status {
+    let interface = {
+        // SAFETY: the protocol is only used to get the interface pointer.
+        let protocol = unsafe {
+            boot::open_protocol::<RawDriverBinding>(
+                OpenProtocolParams {
+                    handle: image_handle,
+                    agent: image_handle,
+                    controller: None,
+                },
+                OpenProtocolAttributes::GetProtocol,
+            )
+        };
+        match protocol {
+            Ok(protocol) => NonNull::from(&protocol.0),
+            Err(err) => return err.status(),
+        }
+    };
+
+    // SAFETY: the interface was leaked by `register_driver`.
+    let protocol =
+        unsafe { InstalledProtocol::<BindingImpl<D>>::from_raw(image_handle, interface) };
+    let installed = InstalledDriverBinding {
+        protocol: Some(protocol),
+    };
+    match installed.uninstall() {
+        Ok(_) => Status::SUCCESS,
+        Err(err) => {
+            let (status, installed) = err.split();
+            // Keep the driver installed, since the image is not unloaded.
+            let _ = installed.into_raw();
+            status
+        }
+    }
+}
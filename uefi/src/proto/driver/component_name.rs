@@ -263,9 +263,12 @@ impl Display for LanguageError {
 
 impl core::error::Error for LanguageError {}
 
+/// Format of a language list.
 #[derive(Debug, PartialEq)]
-enum LanguageIterKind {
+pub(crate) enum LanguageIterKind {
+    /// Concatenated three-character ISO 639-2 codes.
     V1,
+    /// RFC 4646 codes separated by semicolons.
     V2,
 }
 
@@ -277,7 +280,7 @@ pub struct LanguageIter<'a> {
     kind: LanguageIterKind,
 }
 
-impl LanguageIter<'_> {
+impl<'a> LanguageIter<'a> {
     const fn new(
         languages: *const u8,
         kind: LanguageIterKind,
@@ -299,6 +302,21 @@ impl LanguageIter<'_> {
             kind,
         })
     }
+
+    /// Create an iterator over the languages in `bytes`, which end at the
+    /// first null character or at the end of the slice.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_bytes(
+        bytes: &'a [u8],
+        kind: LanguageIterKind,
+    ) -> core::result::Result<Self, LanguageError> {
+        let len = bytes.iter().position(|c| *c == 0).unwrap_or(bytes.len());
+        let languages = &bytes[..len];
+        if let Some(index) = languages.iter().position(|c| !c.is_ascii()) {
+            return Err(LanguageError::Ascii { index });
+        }
+        Ok(Self { languages, kind })
+    }
 }
 
 impl<'a> Iterator for LanguageIter<'a> {
@@ -425,6 +443,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_language_iter_from_bytes() {
+        // Without a null terminator.
+        assert_eq!(
+            LanguageIter::from_bytes(b"en-US;fr", V2)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            ["en-US", "fr"]
+        );
+
+        // Data after the null terminator is ignored.
+        assert_eq!(
+            LanguageIter::from_bytes(b"engfra\0\xff", V1)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            ["eng", "fra"]
+        );
+
+        assert_eq!(
+            LanguageIter::from_bytes(b"en;\xff", V2).err().unwrap(),
+            LanguageError::Ascii { index: 3 },
+        );
+    }
+
     #[test]
     fn test_language_to_cstr() {
         let mut expected = [0; 64];
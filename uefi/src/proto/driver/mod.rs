@@ -2,6 +2,12 @@
 
 //! UEFI driver model protocols.
 
+#[cfg(feature = "alloc")]
+mod binding;
 mod component_name;
+mod service_binding;
 
+#[cfg(feature = "alloc")]
+pub use binding::{DriverBinding, InstalledDriverBinding, install_driver_binding, register_driver};
 pub use component_name::*;
+pub use service_binding::{ServiceBinding, ServiceBound, ServiceChild};
@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Service binding, for creating child handles of network stacks and other
+//! services.
+
+use crate::boot::{self, OpenProtocolAttributes, OpenProtocolParams, ScopedProtocol};
+use crate::proto::{Protocol, ProtocolPointer};
+use crate::{Guid, Handle, Identify, Result, StatusExt};
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use uefi_raw::protocol::driver::ServiceBindingProtocol;
+
+/// A protocol whose instances are created with a service binding protocol.
+///
+/// Many protocols, in particular the network protocols, can't be used
+/// directly on the handle of a device. Instead, a service binding protocol
+/// is installed on the device, which creates child handles with their own
+/// instance of the protocol. Use [`ServiceBinding`] to create such children.
+///
+/// # Safety
+///
+/// [`SERVICE_BINDING_GUID`] must identify a service binding protocol
+/// (`EFI_SERVICE_BINDING_PROTOCOL`) that creates instances of this protocol.
+///
+/// [`SERVICE_BINDING_GUID`]: Self::SERVICE_BINDING_GUID
+pub unsafe trait ServiceBound: ProtocolPointer {
+    /// GUID of the service binding protocol for this protocol.
+    const SERVICE_BINDING_GUID: Guid;
+}
+
+/// The service binding protocol for `P`, used to open it on the parent
+/// handle.
+#[repr(transparent)]
+struct RawServiceBinding<P: ?Sized> {
+    raw: ServiceBindingProtocol,
+    _protocol: PhantomData<fn() -> *const P>,
+}
+
+unsafe impl<P: ServiceBound + ?Sized> Identify for RawServiceBinding<P> {
+    const GUID: Guid = P::SERVICE_BINDING_GUID;
+}
+
+impl<P: ServiceBound + ?Sized> Protocol for RawServiceBinding<P> {}
+
+/// The service binding protocol for `P` on a handle.
+///
+/// The service binding protocol creates child handles with an instance of
+/// `P` installed, see [`create`].
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::boot::{self, SearchType};
+/// use uefi::proto::driver::{ServiceBinding, ServiceBound};
+/// use uefi::proto::unsafe_protocol;
+/// use uefi::{guid, Guid};
+///
+/// /// `EFI_TCP4_PROTOCOL`.
+/// #[unsafe_protocol("65530bc7-a359-410f-b010-5aadc7ec2b62")]
+/// struct Tcp4 {
+///     // ...
+/// }
+///
+/// unsafe impl ServiceBound for Tcp4 {
+///     const SERVICE_BINDING_GUID: Guid = guid!("00720665-67eb-4a99-baf7-d3c33a1c7cc9");
+/// }
+///
+/// # fn test() -> uefi::Result {
+/// let handles =
+///     boot::locate_handle_buffer(SearchType::ByProtocol(&Tcp4::SERVICE_BINDING_GUID))?;
+/// let binding = ServiceBinding::<Tcp4>::new(handles[0]);
+/// let mut tcp = binding.create()?;
+/// // ... use `tcp` ...
+///
+/// // Dropping the child destroys its handle.
+/// drop(tcp);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`create`]: Self::create
+pub struct ServiceBinding<P: ServiceBound + ?Sized> {
+    handle: Handle,
+    _protocol: PhantomData<fn() -> *const P>,
+}
+
+impl<P: ServiceBound + ?Sized> ServiceBinding<P> {
+    /// Use the service binding protocol for `P` on `handle`.
+    ///
+    /// The protocol is opened when needed, so this does not check that
+    /// `handle` supports it.
+    #[must_use]
+    pub const fn new(handle: Handle) -> Self {
+        Self {
+            handle,
+            _protocol: PhantomData,
+        }
+    }
+
+    /// The handle the service binding protocol is installed on.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Create a child handle, and open `P` on it.
+    ///
+    /// The child handle is destroyed when the returned [`ServiceChild`] is
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: the handle does not support the service
+    ///   binding protocol for `P`.
+    /// * [`Status::OUT_OF_RESOURCES`]: there are not enough resources
+    ///   available to create the child.
+    ///
+    /// [`Status::UNSUPPORTED`]: crate::Status::UNSUPPORTED
+    /// [`Status::OUT_OF_RESOURCES`]: crate::Status::OUT_OF_RESOURCES
+    pub fn create(&self) -> Result<ServiceChild<P>> {
+        let child = self.create_child_handle()?;
+
+        // SAFETY: the protocol is closed before the child is destroyed, see
+        // `ServiceChild`'s `Drop` impl.
+        let protocol = unsafe {
+            boot::open_protocol::<P>(
+                OpenProtocolParams {
+                    handle: child,
+                    agent: boot::image_handle(),
+                    controller: None,
+                },
+                OpenProtocolAttributes::GetProtocol,
+            )
+        };
+        match protocol {
+            Ok(protocol) => Ok(ServiceChild {
+                binding: Self::new(self.handle),
+                child,
+                protocol: Some(protocol),
+            }),
+            Err(err) => {
+                // SAFETY: the child was just created, and is not used.
+                let _ = unsafe { self.destroy_child_handle(child) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Create a child handle with an instance of `P` installed.
+    ///
+    /// The child must be destroyed with [`destroy_child_handle`]. Usually
+    /// it's simpler to use [`create`], which does this automatically.
+    ///
+    /// # Errors
+    ///
+    /// See [`create`].
+    ///
+    /// [`create`]: Self::create
+    /// [`destroy_child_handle`]: Self::destroy_child_handle
+    pub fn create_child_handle(&self) -> Result<Handle> {
+        let mut binding = self.open()?;
+        let mut child = ptr::null_mut();
+        unsafe { (binding.raw.create_child)(&mut binding.raw, &mut child) }
+            // OK to unwrap: the child handle is non-null on success.
+            .to_result_with_val(|| unsafe { Handle::from_ptr(child) }.unwrap())
+    }
+
+    /// Destroy a child handle created with [`create_child_handle`].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the instance of `P` on the child is no
+    /// longer in use.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::UNSUPPORTED`]: `child` was not created by this service
+    ///   binding.
+    /// * [`Status::INVALID_PARAMETER`]: `child` is not a valid handle.
+    /// * [`Status::ACCESS_DENIED`]: the child could not be destroyed, because
+    ///   its protocols are in use.
+    ///
+    /// [`create_child_handle`]: Self::create_child_handle
+    /// [`Status::UNSUPPORTED`]: crate::Status::UNSUPPORTED
+    /// [`Status::INVALID_PARAMETER`]: crate::Status::INVALID_PARAMETER
+    /// [`Status::ACCESS_DENIED`]: crate::Status::ACCESS_DENIED
+    pub unsafe fn destroy_child_handle(&self, child: Handle) -> Result {
+        let mut binding = self.open()?;
+        unsafe { (binding.raw.destroy_child)(&mut binding.raw, child.as_ptr()) }.to_result()
+    }
+
+    fn open(&self) -> Result<ScopedProtocol<RawServiceBinding<P>>> {
+        // SAFETY: the protocol is only used for the duration of a single
+        // call.
+        unsafe {
+            boot::open_protocol::<RawServiceBinding<P>>(
+                OpenProtocolParams {
+                    handle: self.handle,
+                    agent: boot::image_handle(),
+                    controller: None,
+                },
+                OpenProtocolAttributes::GetProtocol,
+            )
+        }
+    }
+}
+
+impl<P: ServiceBound + ?Sized> Clone for ServiceBinding<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: ServiceBound + ?Sized> Copy for ServiceBinding<P> {}
+
+impl<P: ServiceBound + ?Sized> Debug for ServiceBinding<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceBinding")
+            .field("guid", &P::SERVICE_BINDING_GUID)
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+/// A child handle created by [`ServiceBinding::create`], with `P` opened.
+///
+/// Dereferences to `P`. The protocol is closed and the child handle is
+/// destroyed when this value is dropped.
+pub struct ServiceChild<P: ServiceBound + ?Sized> {
+    binding: ServiceBinding<P>,
+    child: Handle,
+    // Always `Some`, except while dropping.
+    protocol: Option<ScopedProtocol<P>>,
+}
+
+impl<P: ServiceBound + ?Sized> ServiceChild<P> {
+    /// The child handle.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.child
+    }
+
+    /// The service binding that created the child.
+    #[must_use]
+    pub const fn binding(&self) -> ServiceBinding<P> {
+        self.binding
+    }
+}
+
+impl<P: ServiceBound + ?Sized> Deref for ServiceChild<P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        // OK to unwrap: the protocol is only taken when dropping.
+        self.protocol.as_ref().unwrap()
+    }
+}
+
+impl<P: ServiceBound + ?Sized> DerefMut for ServiceChild<P> {
+    fn deref_mut(&mut self) -> &mut P {
+        // OK to unwrap: the protocol is only taken when dropping.
+        self.protocol.as_mut().unwrap()
+    }
+}
+
+impl<P: ServiceBound + ?Sized> Debug for ServiceChild<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceChild")
+            .field("binding", &self.binding)
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+impl<P: ServiceBound + ?Sized> Drop for ServiceChild<P> {
+    fn drop(&mut self) {
+        // The protocol must be closed before destroying the child.
+        self.protocol = None;
+
+        if !boot::are_boot_services_active() {
+            return;
+        }
+        // SAFETY: the protocol has been closed, and `self` was the only user.
+        if let Err(err) = unsafe { self.binding.destroy_child_handle(self.child) } {
+            log::error!(
+                "Failed to destroy child {:?} of service binding {}: {err}",
+                self.child,
+                P::SERVICE_BINDING_GUID
+            );
+        }
+    }
+}
@@ -46,6 +46,8 @@ pub mod network;
 pub mod nvme;
 pub mod pci;
 pub mod pi;
+#[cfg(feature = "alloc")]
+pub mod producer;
 pub mod rng;
 #[cfg(feature = "alloc")]
 pub mod scsi;
@@ -13,8 +13,10 @@ use core::ffi::{CStr, c_char, c_void};
 use core::ptr;
 use log::debug;
 
+use uefi::Guid;
 use uefi::boot::ScopedProtocol;
 use uefi::prelude::*;
+use uefi::proto::driver::ServiceBound;
 use uefi::proto::unsafe_protocol;
 use uefi_raw::protocol::driver::ServiceBindingProtocol;
 use uefi_raw::protocol::network::http::{
@@ -29,6 +31,10 @@ use uefi_raw::protocol::network::http::{
 #[unsafe_protocol(HttpProtocol::GUID)]
 pub struct Http(HttpProtocol);
 
+unsafe impl ServiceBound for Http {
+    const SERVICE_BINDING_GUID: Guid = HttpProtocol::SERVICE_BINDING_GUID;
+}
+
 impl Http {
     /// Receive HTTP Protocol configuration.
     pub fn get_mode_data(&mut self, config_data: &mut HttpConfigData) -> uefi::Result<()> {
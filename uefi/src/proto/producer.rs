@@ -0,0 +1,345 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Producing protocols implemented in Rust.
+//!
+//! A protocol producer installs a protocol interface (a structure of function
+//! pointers, and sometimes data) on a handle, so that other drivers and
+//! applications can open it. To implement a protocol in Rust:
+//!
+//! 1. Implement [`ProtocolImpl`] for a type holding the protocol's state. The
+//!    associated [`Interface`] type is the raw interface structure, usually
+//!    one of the types from [`uefi_raw::protocol`].
+//! 2. Write the interface's functions as `extern "efiapi"` thunks that call
+//!    [`ProtocolImpl::with_this`] to get `&mut Self` from the `this` pointer.
+//! 3. Install the protocol with [`InstalledProtocol::install`]. The protocol is
+//!    uninstalled when the returned value is dropped.
+//!
+//! # Example
+//!
+//! ```
+//! use uefi::proto::producer::{InstalledProtocol, ProtocolImpl};
+//! use uefi::{guid, Guid, Status};
+//!
+//! /// Raw interface of a protocol that counts calls.
+//! #[repr(C)]
+//! pub struct CounterProtocol {
+//!     pub increment: unsafe extern "efiapi" fn(this: *mut Self, count: *mut u32) -> Status,
+//! }
+//!
+//! struct Counter {
+//!     count: u32,
+//! }
+//!
+//! unsafe impl ProtocolImpl for Counter {
+//!     type Interface = CounterProtocol;
+//!     const GUID: Guid = guid!("1ba3f6e3-39a4-4d83-9bd2-ff60f1dba4b3");
+//!
+//!     fn interface(&self) -> CounterProtocol {
+//!         CounterProtocol { increment }
+//!     }
+//! }
+//!
+//! unsafe extern "efiapi" fn increment(this: *mut CounterProtocol, count: *mut u32) -> Status {
+//!     if count.is_null() {
+//!         return Status::INVALID_PARAMETER;
+//!     }
+//!     let r = unsafe {
+//!         Counter::with_this(this, |counter| {
+//!             counter.count += 1;
+//!             counter.count
+//!         })
+//!     };
+//!     match r {
+//!         Ok(value) => {
+//!             unsafe { count.write(value) };
+//!             Status::SUCCESS
+//!         }
+//!         Err(err) => err.status(),
+//!     }
+//! }
+//!
+//! # fn test() -> uefi::Result {
+//! let counter = InstalledProtocol::install(None, Counter { count: 0 })?;
+//! let handle = counter.handle();
+//! // ...
+//! drop(counter);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Interface`]: ProtocolImpl::Interface
+
+use crate::boot;
+use crate::{Error, Guid, Handle, Result, Status};
+use alloc::boxed::Box;
+use core::cell::RefCell;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomPinned;
+use core::mem::ManuallyDrop;
+use core::pin::Pin;
+use core::ptr::{self, NonNull};
+
+/// A protocol implemented in Rust.
+///
+/// See the [module documentation][self] for an example.
+///
+/// # Safety
+///
+/// [`Interface`] must have the layout that consumers of the protocol
+/// identified by [`GUID`] expect, and the interface returned by
+/// [`interface`] must be valid for that protocol.
+///
+/// [`Interface`]: Self::Interface
+/// [`GUID`]: Self::GUID
+/// [`interface`]: Self::interface
+pub unsafe trait ProtocolImpl: Sized + 'static {
+    /// Raw interface structure of the protocol, for example
+    /// [`uefi_raw::protocol::rng::RngProtocol`].
+    type Interface: 'static;
+
+    /// GUID of the protocol.
+    const GUID: Guid;
+
+    /// Create the protocol interface. This is called once when the protocol
+    /// is installed.
+    fn interface(&self) -> Self::Interface;
+
+    /// Call `f` with the implementation that owns the interface `this`.
+    ///
+    /// This is intended to be called from the interface's functions, which
+    /// receive a pointer to the interface as their first argument.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be null, or point to an interface installed by
+    /// [`InstalledProtocol<Self>`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `this` is null.
+    /// * [`Status::ACCESS_DENIED`]: the implementation is already borrowed,
+    ///   for example because the protocol was called re-entrantly from an
+    ///   event notification function.
+    unsafe fn with_this<R>(
+        this: *const Self::Interface,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> Result<R> {
+        let container = unsafe { Container::<Self>::from_interface(this) }?;
+        let mut value = container
+            .value
+            .try_borrow_mut()
+            .map_err(|_| Status::ACCESS_DENIED)?;
+        Ok(f(&mut value))
+    }
+
+    /// Call `f` with a shared reference to the implementation that owns the
+    /// interface `this`.
+    ///
+    /// Unlike [`with_this`], this can be nested, so it's suitable for
+    /// protocols that may be called re-entrantly. The implementation can use
+    /// interior mutability for its state.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be null, or point to an interface installed by
+    /// [`InstalledProtocol<Self>`].
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: `this` is null.
+    /// * [`Status::ACCESS_DENIED`]: the implementation is mutably borrowed.
+    ///
+    /// [`with_this`]: Self::with_this
+    unsafe fn with_this_ref<R>(
+        this: *const Self::Interface,
+        f: impl FnOnce(&Self) -> R,
+    ) -> Result<R> {
+        let container = unsafe { Container::<Self>::from_interface(this) }?;
+        let value = container
+            .value
+            .try_borrow()
+            .map_err(|_| Status::ACCESS_DENIED)?;
+        Ok(f(&value))
+    }
+}
+
+/// Storage for an installed protocol. The firmware holds a pointer to the
+/// interface, so the container must be pinned.
+#[repr(C)]
+struct Container<T: ProtocolImpl> {
+    // Must be the first field, see `from_interface`.
+    interface: T::Interface,
+    value: RefCell<T>,
+    _pinned: PhantomPinned,
+}
+
+impl<T: ProtocolImpl> Container<T> {
+    /// Get the container from a pointer to its interface.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be null, or point to the interface of a live container.
+    unsafe fn from_interface<'a>(this: *const T::Interface) -> Result<&'a Self> {
+        if this.is_null() {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        // SAFETY: the interface is the first field of the `#[repr(C)]`
+        // container, so the pointers are interchangeable.
+        Ok(unsafe { &*this.cast::<Self>() })
+    }
+}
+
+/// A protocol implemented in Rust, installed on a handle.
+///
+/// The protocol is uninstalled when this value is dropped. If the firmware
+/// refuses to uninstall the protocol, for example because another driver
+/// has it open and cannot be stopped, the memory for the protocol is leaked
+/// so that the installed interface remains valid.
+///
+/// Note that consumers which opened the protocol without
+/// [`BY_DRIVER`](boot::OpenProtocolAttributes) are not notified when the
+/// protocol is uninstalled, and must not use it afterwards.
+pub struct InstalledProtocol<T: ProtocolImpl> {
+    handle: Handle,
+    // Only dropped once the interface has been uninstalled.
+    container: ManuallyDrop<Pin<Box<Container<T>>>>,
+}
+
+impl<T: ProtocolImpl> InstalledProtocol<T> {
+    /// Install the protocol implemented by `value` on `handle`.
+    ///
+    /// If `handle` is `None`, a new handle is created.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::OUT_OF_RESOURCES`]: failed to allocate a new handle.
+    /// * [`Status::INVALID_PARAMETER`]: the protocol is already installed on
+    ///   `handle`.
+    pub fn install(handle: Option<Handle>, value: T) -> Result<Self> {
+        let container = Box::pin(Container {
+            interface: value.interface(),
+            value: RefCell::new(value),
+            _pinned: PhantomPinned,
+        });
+        let interface = ptr::from_ref(&container.interface).cast();
+
+        // SAFETY: the interface is valid for the protocol per the
+        // requirements of `ProtocolImpl`, and stays valid until it is
+        // uninstalled, see `Drop`.
+        let handle = unsafe { boot::install_protocol_interface(handle, &T::GUID, interface) }?;
+        Ok(Self {
+            handle,
+            container: ManuallyDrop::new(container),
+        })
+    }
+
+    /// The handle the protocol is installed on.
+    #[must_use]
+    pub const fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Pointer to the installed interface.
+    #[must_use]
+    pub fn interface(&self) -> NonNull<T::Interface> {
+        NonNull::from(&self.container.interface)
+    }
+
+    /// Call `f` with a mutable reference to the implementation.
+    ///
+    /// Returns `None` if the implementation is already borrowed, which can
+    /// happen if this is called while the protocol is in use, for example
+    /// from an event notification function.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let mut value = self.container.value.try_borrow_mut().ok()?;
+        Some(f(&mut value))
+    }
+
+    /// Keep the protocol installed, and return a pointer to its interface.
+    ///
+    /// The protocol can be reclaimed later with [`from_raw`], for example in
+    /// an image's unload function.
+    ///
+    /// [`from_raw`]: Self::from_raw
+    #[must_use]
+    pub fn into_raw(self) -> NonNull<T::Interface> {
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: `this` is not used after taking the container. The
+        // container is never moved out of the box.
+        let container =
+            unsafe { Pin::into_inner_unchecked(ManuallyDrop::take(&mut this.container)) };
+        NonNull::from(Box::leak(container)).cast()
+    }
+
+    /// Reclaim a protocol leaked with [`into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `interface` must have been returned by [`into_raw`] for a protocol
+    /// installed on `handle`, and must not be reclaimed more than once.
+    ///
+    /// [`into_raw`]: Self::into_raw
+    #[must_use]
+    pub unsafe fn from_raw(handle: Handle, interface: NonNull<T::Interface>) -> Self {
+        // SAFETY: the interface is the first field of the container, and was
+        // allocated by `install`.
+        let container = unsafe { Box::from_raw(interface.cast::<Container<T>>().as_ptr()) };
+        Self {
+            handle,
+            container: ManuallyDrop::new(Box::into_pin(container)),
+        }
+    }
+
+    /// Uninstall the protocol and return the implementation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::ACCESS_DENIED`]: the protocol is still in use and could
+    ///   not be uninstalled. The protocol is returned as the error data, and
+    ///   stays installed.
+    pub fn uninstall(self) -> Result<T, Self> {
+        if let Err(err) = self.uninstall_interface() {
+            return Err(Error::new(err.status(), self));
+        }
+
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: `this` is not used after taking the container, and the
+        // interface is no longer installed, so it no longer needs to be pinned.
+        let container =
+            unsafe { Pin::into_inner_unchecked(ManuallyDrop::take(&mut this.container)) };
+        Ok(container.value.into_inner())
+    }
+
+    fn uninstall_interface(&self) -> Result {
+        let interface = ptr::from_ref(&self.container.interface).cast();
+        // SAFETY: the interface was installed by `install`. Consumers that
+        // opened the protocol `BY_DRIVER` are stopped by the firmware.
+        unsafe { boot::uninstall_protocol_interface(self.handle, &T::GUID, interface) }
+    }
+}
+
+impl<T: ProtocolImpl> Debug for InstalledProtocol<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstalledProtocol")
+            .field("guid", &T::GUID)
+            .field("handle", &self.handle)
+            .field("interface", &self.interface())
+            .finish()
+    }
+}
+
+impl<T: ProtocolImpl> Drop for InstalledProtocol<T> {
+    fn drop(&mut self) {
+        // After exiting boot services, the memory can't be freed anyway.
+        if !boot::are_boot_services_active() {
+            return;
+        }
+        match self.uninstall_interface() {
+            // SAFETY: the container is not used after this.
+            Ok(()) => unsafe { ManuallyDrop::drop(&mut self.container) },
+            // Leak the container to keep the interface valid, since it is
+            // still installed.
+            Err(err) => log::error!("Failed to uninstall protocol {}: {err}", T::GUID),
+        }
+    }
+}
@@ -0,0 +1,816 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Boot manager variables.
+//!
+//! The UEFI boot manager is configured with variables in the
+//! [`VariableVendor::GLOBAL_VARIABLE`] namespace:
+//!
+//! * `Boot####`, `Driver####` and `SysPrep####` contain [`LoadOption`]s,
+//!   where `####` is the option number as four uppercase hexadecimal digits.
+//! * `BootOrder`, `DriverOrder` and `SysPrepOrder` contain the order in
+//!   which the load options are processed. See [`get_order`].
+//! * `BootNext` is the boot option to try first on the next boot only. See
+//!   [`get_boot_next`].
+//! * `BootCurrent` is the boot option that was selected for the current
+//!   boot. See [`get_boot_current`].
+//! * `Timeout` is the number of seconds the boot manager waits before
+//!   booting the first option in `BootOrder`. See [`get_timeout`].
+//! * `Key####` contains a [`KeyOption`], a hot key that boots a boot option.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::runtime::boot_manager::{self, LoadOption, LoadOptionType};
+//! use uefi::proto::device_path::DevicePath;
+//! use uefi::cstr16;
+//!
+//! # fn test(path: &DevicePath) -> uefi::Result {
+//! // Add a boot option for `path` and boot it next.
+//! let option = LoadOption::new(cstr16!("My OS"), &[path]);
+//! let number = boot_manager::next_free_option_number(LoadOptionType::Boot)?;
+//! boot_manager::set_load_option(LoadOptionType::Boot, number, &option)?;
+//!
+//! let mut order = boot_manager::get_order(LoadOptionType::Boot)?;
+//! order.push(number);
+//! boot_manager::set_order(LoadOptionType::Boot, &order)?;
+//! boot_manager::set_boot_next(Some(number))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{VariableAttributes, VariableVendor};
+use crate::proto::device_path::DevicePath;
+use crate::{CStr16, CString16, Result, Status, boot, cstr16, runtime};
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+use core::mem;
+
+pub use uefi_raw::boot_manager::{BootKeyData, LoadOptionAttributes};
+pub use uefi_raw::protocol::console::InputKey;
+
+/// Attributes of the boot manager variables, except `BootCurrent`.
+const ATTRIBUTES: VariableAttributes = VariableAttributes::NON_VOLATILE
+    .union(VariableAttributes::BOOTSERVICE_ACCESS)
+    .union(VariableAttributes::RUNTIME_ACCESS);
+
+/// Kind of a [`LoadOption`], which determines the name of its variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum LoadOptionType {
+    /// `Boot####` options, which boot an operating system or run an
+    /// application.
+    Boot,
+
+    /// `Driver####` options, which load drivers before the boot options are
+    /// processed.
+    Driver,
+
+    /// `SysPrep####` options, which run applications that prepare the
+    /// system before the boot options are processed.
+    SysPrep,
+}
+
+impl LoadOptionType {
+    /// Prefix of the variable names of the load options.
+    #[must_use]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Boot => "Boot",
+            Self::Driver => "Driver",
+            Self::SysPrep => "SysPrep",
+        }
+    }
+
+    /// Name of the variable that stores the order of the load options, for
+    /// example `BootOrder`.
+    #[must_use]
+    pub const fn order_variable_name(self) -> &'static CStr16 {
+        match self {
+            Self::Boot => cstr16!("BootOrder"),
+            Self::Driver => cstr16!("DriverOrder"),
+            Self::SysPrep => cstr16!("SysPrepOrder"),
+        }
+    }
+
+    /// Name of the variable of load option `number`, for example
+    /// `Boot000A`.
+    #[must_use]
+    pub fn variable_name(self, number: u16) -> CString16 {
+        numbered_name(self.prefix(), number)
+    }
+
+    /// Get the number of the load option stored in the variable `name`, or
+    /// `None` if `name` is not the name of a load option of this type.
+    #[must_use]
+    pub fn option_number(self, name: &CStr16) -> Option<u16> {
+        parse_numbered_name(self.prefix(), name)
+    }
+}
+
+/// Format a variable name such as `Boot000A`.
+fn numbered_name(prefix: &str, number: u16) -> CString16 {
+    CString16::try_from(format!("{prefix}{number:04X}").as_str()).expect("variable name is ASCII")
+}
+
+/// Parse a variable name such as `Boot000A`. Lowercase hexadecimal digits
+/// are not accepted, as required by the specification.
+fn parse_numbered_name(prefix: &str, name: &CStr16) -> Option<u16> {
+    let name = name.to_u16_slice();
+    let digits = name.strip_prefix(&*prefix.encode_utf16().collect::<Vec<_>>())?;
+    if digits.len() != 4 {
+        return None;
+    }
+    digits.iter().try_fold(0u16, |number, &digit| {
+        let value = match digit {
+            0x30..=0x39 => digit - 0x30,
+            0x41..=0x46 => digit - 0x41 + 10,
+            _ => return None,
+        };
+        Some((number << 4) | value)
+    })
+}
+
+/// Error returned when parsing a [`LoadOption`] or [`KeyOption`] fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OptionParseError {
+    /// The data ends before the end of the option.
+    TooShort,
+
+    /// The description is not a null-terminated UCS-2 string.
+    InvalidDescription,
+
+    /// The file path list is not a list of valid device paths.
+    InvalidFilePathList,
+}
+
+impl Display for OptionParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::TooShort => "the option data is too short",
+            Self::InvalidDescription => "the description is not a valid UCS-2 string",
+            Self::InvalidFilePathList => "the file path list is invalid",
+        };
+        f.write_str(s)
+    }
+}
+
+impl core::error::Error for OptionParseError {}
+
+/// A load option (`EFI_LOAD_OPTION`), stored in the `Boot####`,
+/// `Driver####` and `SysPrep####` variables.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadOption {
+    /// Attributes of the load option.
+    pub attributes: LoadOptionAttributes,
+
+    /// Description of the load option shown to the user.
+    pub description: CString16,
+
+    /// Device paths concatenated with their end nodes. The first path is
+    /// the path of the image to load.
+    file_path_list: Vec<u8>,
+
+    /// Data passed to the image as its load options.
+    pub optional_data: Vec<u8>,
+}
+
+impl LoadOption {
+    /// Size of the fields before the description.
+    const HEADER_SIZE: usize = mem::size_of::<u32>() + mem::size_of::<u16>();
+
+    /// Create an [`ACTIVE`] load option for the image at `file_paths[0]`.
+    ///
+    /// The remaining paths are optional, and their meaning depends on the
+    /// image.
+    ///
+    /// [`ACTIVE`]: LoadOptionAttributes::ACTIVE
+    #[must_use]
+    pub fn new(description: &CStr16, file_paths: &[&DevicePath]) -> Self {
+        let mut option = Self {
+            attributes: LoadOptionAttributes::ACTIVE,
+            description: description.into(),
+            file_path_list: Vec::new(),
+            optional_data: Vec::new(),
+        };
+        option.set_file_paths(file_paths);
+        option
+    }
+
+    /// Parse a load option from the data of its variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionParseError`] if `bytes` is not a valid load
+    /// option.
+    pub fn parse(bytes: &[u8]) -> core::result::Result<Self, OptionParseError> {
+        if bytes.len() < Self::HEADER_SIZE {
+            return Err(OptionParseError::TooShort);
+        }
+        let attributes = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let file_path_list_len = usize::from(u16::from_le_bytes(bytes[4..6].try_into().unwrap()));
+        let rest = &bytes[Self::HEADER_SIZE..];
+
+        // The description is not necessarily aligned, so read it in chunks.
+        let mut description = Vec::new();
+        let mut chars = rest.chunks_exact(2);
+        loop {
+            let c = chars.next().ok_or(OptionParseError::TooShort)?;
+            let c = u16::from_le_bytes([c[0], c[1]]);
+            description.push(c);
+            if c == 0 {
+                break;
+            }
+        }
+        let description =
+            CString16::try_from(description).map_err(|_| OptionParseError::InvalidDescription)?;
+        let rest = &rest[description.num_bytes()..];
+
+        let file_path_list = rest
+            .get(..file_path_list_len)
+            .ok_or(OptionParseError::TooShort)?;
+        if FilePaths(file_path_list).any(|path| path.is_none()) {
+            return Err(OptionParseError::InvalidFilePathList);
+        }
+
+        Ok(Self {
+            attributes: LoadOptionAttributes::from_bits_retain(attributes),
+            description,
+            file_path_list: file_path_list.to_vec(),
+            optional_data: rest[file_path_list_len..].to_vec(),
+        })
+    }
+
+    /// Serialize the load option to the data of its variable.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            Self::HEADER_SIZE
+                + self.description.num_bytes()
+                + self.file_path_list.len()
+                + self.optional_data.len(),
+        );
+        bytes.extend_from_slice(&self.attributes.bits().to_le_bytes());
+        // The length always fits, see `set_file_paths`.
+        bytes.extend_from_slice(&(self.file_path_list.len() as u16).to_le_bytes());
+        for c in self.description.to_u16_slice_with_nul() {
+            bytes.extend_from_slice(&c.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.file_path_list);
+        bytes.extend_from_slice(&self.optional_data);
+        bytes
+    }
+
+    /// The path of the image to load, or `None` if the file path list is
+    /// empty.
+    #[must_use]
+    pub fn file_path(&self) -> Option<&DevicePath> {
+        self.file_paths().next()
+    }
+
+    /// Iterator over the device paths in the file path list.
+    pub fn file_paths(&self) -> impl Iterator<Item = &DevicePath> {
+        // The list was validated when it was set.
+        FilePaths(&self.file_path_list).map_while(|path| path)
+    }
+
+    /// Replace the file path list. The first path is the path of the image
+    /// to load.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total size of the paths does not fit in a `u16`.
+    pub fn set_file_paths(&mut self, file_paths: &[&DevicePath]) {
+        self.file_path_list.clear();
+        for path in file_paths {
+            self.file_path_list.extend_from_slice(path.as_bytes());
+        }
+        assert!(
+            u16::try_from(self.file_path_list.len()).is_ok(),
+            "file path list is too long"
+        );
+    }
+}
+
+/// Iterator over packed device paths. Yields `None` for an invalid path.
+struct FilePaths<'a>(&'a [u8]);
+
+impl<'a> Iterator for FilePaths<'a> {
+    type Item = Option<&'a DevicePath>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        match <&DevicePath>::try_from(self.0) {
+            Ok(path) => {
+                self.0 = &self.0[path.as_bytes().len()..];
+                Some(Some(path))
+            }
+            Err(_) => {
+                self.0 = &[];
+                Some(None)
+            }
+        }
+    }
+}
+
+/// A hot key (`EFI_KEY_OPTION`) stored in a `Key####` variable, which
+/// boots a boot option when its keys are pressed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyOption {
+    modifiers: BootKeyData,
+    boot_option_crc: u32,
+    boot_option: u16,
+    keys: Vec<InputKey>,
+}
+
+impl KeyOption {
+    /// Size of the fields before the keys.
+    const HEADER_SIZE: usize = 2 * mem::size_of::<u32>() + mem::size_of::<u16>();
+
+    /// Size of an `EFI_INPUT_KEY`.
+    const KEY_SIZE: usize = 2 * mem::size_of::<u16>();
+
+    /// Maximum number of keys.
+    const MAX_KEYS: usize = 3;
+
+    /// Create a key option that boots `Boot####` option `boot_option`,
+    /// which must contain `load_option`, when the `modifiers` and up to
+    /// three `keys` are pressed.
+    ///
+    /// The key option is only valid as long as the boot option is not
+    /// changed, so the CRC32 of `load_option` is stored in it.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: there are more than three `keys`.
+    /// * See [`boot::calculate_crc32`] for other errors.
+    pub fn new(
+        modifiers: BootKeyData,
+        keys: &[InputKey],
+        boot_option: u16,
+        load_option: &LoadOption,
+    ) -> Result<Self> {
+        if keys.len() > Self::MAX_KEYS {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        Ok(Self {
+            modifiers: modifiers.difference(BootKeyData::INPUT_KEY_COUNT),
+            boot_option_crc: boot::calculate_crc32(&load_option.to_bytes())?,
+            boot_option,
+            keys: keys.to_vec(),
+        })
+    }
+
+    /// Parse a key option from the data of its variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionParseError::TooShort`] if `bytes` is too short for
+    /// the number of keys in the key option.
+    pub fn parse(bytes: &[u8]) -> core::result::Result<Self, OptionParseError> {
+        if bytes.len() < Self::HEADER_SIZE {
+            return Err(OptionParseError::TooShort);
+        }
+        let key_data =
+            BootKeyData::from_bits_retain(u32::from_le_bytes(bytes[0..4].try_into().unwrap()));
+        let key_count = (key_data.bits() >> 30) as usize;
+        let keys = bytes[Self::HEADER_SIZE..]
+            .get(..key_count * Self::KEY_SIZE)
+            .ok_or(OptionParseError::TooShort)?
+            .chunks_exact(Self::KEY_SIZE)
+            .map(|key| InputKey {
+                scan_code: u16::from_le_bytes([key[0], key[1]]),
+                unicode_char: u16::from_le_bytes([key[2], key[3]]),
+            })
+            .collect();
+
+        Ok(Self {
+            modifiers: key_data.difference(BootKeyData::INPUT_KEY_COUNT),
+            boot_option_crc: u32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+            boot_option: u16::from_le_bytes(bytes[8..10].try_into().unwrap()),
+            keys,
+        })
+    }
+
+    /// Serialize the key option to the data of its variable.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let key_data = self.modifiers.bits() | ((self.keys.len() as u32) << 30);
+        let mut bytes = Vec::with_capacity(Self::HEADER_SIZE + self.keys.len() * Self::KEY_SIZE);
+        bytes.extend_from_slice(&key_data.to_le_bytes());
+        bytes.extend_from_slice(&self.boot_option_crc.to_le_bytes());
+        bytes.extend_from_slice(&self.boot_option.to_le_bytes());
+        for key in &self.keys {
+            bytes.extend_from_slice(&key.scan_code.to_le_bytes());
+            bytes.extend_from_slice(&key.unicode_char.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Modifier keys that must be pressed, and the revision of the key
+    /// option. Does not include [`BootKeyData::INPUT_KEY_COUNT`].
+    #[must_use]
+    pub const fn modifiers(&self) -> BootKeyData {
+        self.modifiers
+    }
+
+    /// CRC32 of the [`LoadOption`] of the boot option.
+    #[must_use]
+    pub const fn boot_option_crc(&self) -> u32 {
+        self.boot_option_crc
+    }
+
+    /// Number of the `Boot####` option to boot.
+    #[must_use]
+    pub const fn boot_option(&self) -> u16 {
+        self.boot_option
+    }
+
+    /// Keys that must be pressed, in addition to the modifiers.
+    #[must_use]
+    pub fn keys(&self) -> &[InputKey] {
+        &self.keys
+    }
+}
+
+/// Read a variable in the global namespace that must be a `u16`. Returns
+/// `None` if the variable does not exist.
+fn get_u16(name: &CStr16) -> Result<Option<u16>> {
+    Ok(runtime::get_global(name, runtime::get_variable_array)?.map(u16::from_le_bytes))
+}
+
+/// Read a variable in the global namespace. Returns `None` if the variable
+/// does not exist.
+fn get_bytes(name: &CStr16) -> Result<Option<Vec<u8>>> {
+    Ok(runtime::get_global(name, runtime::get_variable_boxed)?.map(|(data, _)| data.into_vec()))
+}
+
+/// Write a boot manager variable in the global namespace.
+fn set_bytes(name: &CStr16, data: &[u8]) -> Result {
+    runtime::set_variable(name, &VariableVendor::GLOBAL_VARIABLE, ATTRIBUTES, data)
+}
+
+/// Get the numbers of the variables in the global namespace whose names
+/// start with `prefix`, in ascending order.
+fn numbers_with_prefix(prefix: &str) -> Result<Vec<u16>> {
+    let mut numbers = Vec::new();
+    for key in runtime::variable_keys() {
+        let key = key?;
+        if key.vendor != VariableVendor::GLOBAL_VARIABLE {
+            continue;
+        }
+        if let Some(number) = parse_numbered_name(prefix, &key.name) {
+            numbers.push(number);
+        }
+    }
+    numbers.sort_unstable();
+    numbers.dedup();
+    Ok(numbers)
+}
+
+/// Get the smallest number that is not in `used`, which must be sorted.
+fn first_unused(used: &[u16]) -> Result<u16> {
+    (0..=u16::MAX)
+        .find(|n| used.binary_search(n).is_err())
+        .ok_or_else(|| Status::OUT_OF_RESOURCES.into())
+}
+
+/// Read load option `number` of type `ty`.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the load option does not exist.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a valid load option.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_load_option(ty: LoadOptionType, number: u16) -> Result<LoadOption> {
+    let data = get_bytes(&ty.variable_name(number))?.ok_or(Status::NOT_FOUND)?;
+    LoadOption::parse(&data).map_err(|_| Status::VOLUME_CORRUPTED.into())
+}
+
+/// Create or replace load option `number` of type `ty`.
+///
+/// This does not add the option to the order variable, see [`set_order`].
+///
+/// # Errors
+///
+/// See [`runtime::set_variable`].
+pub fn set_load_option(ty: LoadOptionType, number: u16, option: &LoadOption) -> Result {
+    set_bytes(&ty.variable_name(number), &option.to_bytes())
+}
+
+/// Delete load option `number` of type `ty`.
+///
+/// This does not remove the option from the order variable, see
+/// [`set_order`].
+///
+/// # Errors
+///
+/// See [`runtime::delete_variable`].
+pub fn delete_load_option(ty: LoadOptionType, number: u16) -> Result {
+    runtime::delete_variable(&ty.variable_name(number), &VariableVendor::GLOBAL_VARIABLE)
+}
+
+/// Get the numbers of all load options of type `ty`, in ascending order.
+///
+/// # Errors
+///
+/// See [`runtime::variable_keys`].
+pub fn option_numbers(ty: LoadOptionType) -> Result<Vec<u16>> {
+    numbers_with_prefix(ty.prefix())
+}
+
+/// Get the smallest load option number of type `ty` that is neither used
+/// by a load option nor listed in the order variable.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: all option numbers are used.
+/// * See [`runtime::variable_keys`] for other errors.
+pub fn next_free_option_number(ty: LoadOptionType) -> Result<u16> {
+    let mut used = option_numbers(ty)?;
+    used.extend(get_order(ty)?);
+    used.sort_unstable();
+    first_unused(&used)
+}
+
+/// Get the order in which the load options of type `ty` are processed, for
+/// example the contents of `BootOrder`. Returns an empty list if the order
+/// variable does not exist.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the size of the variable is odd.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_order(ty: LoadOptionType) -> Result<Vec<u16>> {
+    let Some(data) = get_bytes(ty.order_variable_name())? else {
+        return Ok(Vec::new());
+    };
+    if data.len() % 2 != 0 {
+        return Err(Status::VOLUME_CORRUPTED.into());
+    }
+    Ok(data
+        .chunks_exact(2)
+        .map(|n| u16::from_le_bytes([n[0], n[1]]))
+        .collect())
+}
+
+/// Set the order in which the load options of type `ty` are processed, for
+/// example the contents of `BootOrder`.
+///
+/// # Errors
+///
+/// See [`runtime::set_variable`].
+pub fn set_order(ty: LoadOptionType, order: &[u16]) -> Result {
+    let data: Vec<u8> = order.iter().flat_map(|n| n.to_le_bytes()).collect();
+    set_bytes(ty.order_variable_name(), &data)
+}
+
+/// Get the boot option that is tried first on the next boot, from the
+/// `BootNext` variable.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a `u16`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_boot_next() -> Result<Option<u16>> {
+    get_u16(cstr16!("BootNext"))
+}
+
+/// Set the boot option that is tried first on the next boot only, or
+/// delete the `BootNext` variable if `number` is `None`.
+///
+/// # Errors
+///
+/// See [`runtime::set_variable`] and [`runtime::delete_variable`].
+pub fn set_boot_next(number: Option<u16>) -> Result {
+    let name = cstr16!("BootNext");
+    match number {
+        Some(number) => set_bytes(name, &number.to_le_bytes()),
+        None => match runtime::delete_variable(name, &VariableVendor::GLOBAL_VARIABLE) {
+            Err(err) if err.status() == Status::NOT_FOUND => Ok(()),
+            result => result,
+        },
+    }
+}
+
+/// Get the boot option that was selected for the current boot, from the
+/// `BootCurrent` variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the boot manager did not set `BootCurrent`.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a `u16`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_boot_current() -> Result<u16> {
+    get_u16(cstr16!("BootCurrent"))?.ok_or_else(|| Status::NOT_FOUND.into())
+}
+
+/// Get the boot manager timeout in seconds from the `Timeout` variable, or
+/// `None` if it does not exist.
+///
+/// A timeout of `0xffff` means that the boot manager waits for user input.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a `u16`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_timeout() -> Result<Option<u16>> {
+    get_u16(cstr16!("Timeout"))
+}
+
+/// Set the boot manager timeout in seconds.
+///
+/// # Errors
+///
+/// See [`runtime::set_variable`].
+pub fn set_timeout(seconds: u16) -> Result {
+    set_bytes(cstr16!("Timeout"), &seconds.to_le_bytes())
+}
+
+/// Read key option `number` from its `Key####` variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the key option does not exist.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a valid key option.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_key_option(number: u16) -> Result<KeyOption> {
+    let data = get_bytes(&numbered_name("Key", number))?.ok_or(Status::NOT_FOUND)?;
+    KeyOption::parse(&data).map_err(|_| Status::VOLUME_CORRUPTED.into())
+}
+
+/// Create or replace key option `number`.
+///
+/// # Errors
+///
+/// See [`runtime::set_variable`].
+pub fn set_key_option(number: u16, option: &KeyOption) -> Result {
+    set_bytes(&numbered_name("Key", number), &option.to_bytes())
+}
+
+/// Delete key option `number`.
+///
+/// # Errors
+///
+/// See [`runtime::delete_variable`].
+pub fn delete_key_option(number: u16) -> Result {
+    runtime::delete_variable(
+        &numbered_name("Key", number),
+        &VariableVendor::GLOBAL_VARIABLE,
+    )
+}
+
+/// Get the numbers of all key options, in ascending order.
+///
+/// # Errors
+///
+/// See [`runtime::variable_keys`].
+pub fn key_option_numbers() -> Result<Vec<u16>> {
+    numbers_with_prefix("Key")
+}
+
+/// Get the smallest key option number that is not used.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: all key option numbers are used.
+/// * See [`runtime::variable_keys`] for other errors.
+pub fn next_free_key_option_number() -> Result<u16> {
+    first_unused(&key_option_numbers()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// A device path with a single `Media/FilePath` node for `\a`, followed
+    /// by the end node.
+    #[rustfmt::skip]
+    const PATH: &[u8] = &[
+        0x04, 0x04, 0x0a, 0x00, b'\\', 0x00, b'a', 0x00, 0x00, 0x00,
+        0x7f, 0xff, 0x04, 0x00,
+    ];
+
+    #[test]
+    fn test_variable_names() {
+        assert_eq!(LoadOptionType::Boot.variable_name(0xa), cstr16!("Boot000A"));
+        assert_eq!(
+            LoadOptionType::SysPrep.variable_name(0xffff),
+            cstr16!("SysPrepFFFF")
+        );
+
+        let ty = LoadOptionType::Driver;
+        assert_eq!(ty.option_number(cstr16!("Driver12AB")), Some(0x12ab));
+        assert_eq!(ty.option_number(cstr16!("Driver12ab")), None);
+        assert_eq!(ty.option_number(cstr16!("Driver123")), None);
+        assert_eq!(ty.option_number(cstr16!("DriverOrder")), None);
+        assert_eq!(ty.option_number(cstr16!("Boot0001")), None);
+    }
+
+    #[test]
+    fn test_first_unused() {
+        assert_eq!(first_unused(&[]), Ok(0));
+        assert_eq!(first_unused(&[0, 1, 3]), Ok(2));
+        assert_eq!(first_unused(&[1]), Ok(0));
+    }
+
+    #[test]
+    fn test_load_option() {
+        let path = <&DevicePath>::try_from(PATH).unwrap();
+        let mut option = LoadOption::new(cstr16!("OS"), &[path, path]);
+        option.attributes |= LoadOptionAttributes::HIDDEN;
+        option.optional_data = vec![1, 2, 3];
+
+        #[rustfmt::skip]
+        let mut expected = vec![
+            // Attributes.
+            0x09, 0x00, 0x00, 0x00,
+            // File path list length.
+            0x1c, 0x00,
+            // Description.
+            b'O', 0x00, b'S', 0x00, 0x00, 0x00,
+        ];
+        expected.extend_from_slice(PATH);
+        expected.extend_from_slice(PATH);
+        expected.extend_from_slice(&[1, 2, 3]);
+
+        let bytes = option.to_bytes();
+        assert_eq!(bytes, expected);
+
+        let parsed = LoadOption::parse(&bytes).unwrap();
+        assert_eq!(parsed, option);
+        assert_eq!(parsed.file_path(), Some(path));
+        assert_eq!(parsed.file_paths().count(), 2);
+    }
+
+    #[test]
+    fn test_load_option_errors() {
+        let path = <&DevicePath>::try_from(PATH).unwrap();
+        let bytes = LoadOption::new(cstr16!("OS"), &[path]).to_bytes();
+
+        assert_eq!(
+            LoadOption::parse(&bytes[..5]),
+            Err(OptionParseError::TooShort)
+        );
+        // Description without a null terminator.
+        assert_eq!(
+            LoadOption::parse(&bytes[..10]),
+            Err(OptionParseError::TooShort)
+        );
+        // Truncated file path list.
+        assert_eq!(
+            LoadOption::parse(&bytes[..bytes.len() - 1]),
+            Err(OptionParseError::TooShort)
+        );
+
+        // File path list that is not a device path.
+        let mut invalid = bytes.clone();
+        invalid[4] = 2;
+        assert_eq!(
+            LoadOption::parse(&invalid),
+            Err(OptionParseError::InvalidFilePathList)
+        );
+    }
+
+    #[test]
+    fn test_key_option() {
+        #[rustfmt::skip]
+        let bytes = [
+            // Key data: control pressed, two keys.
+            0x00, 0x02, 0x00, 0x80,
+            // Boot option CRC.
+            0x78, 0x56, 0x34, 0x12,
+            // Boot option.
+            0x01, 0x00,
+            // Keys.
+            0x00, 0x00, b'a', 0x00,
+            0x0b, 0x00, 0x00, 0x00,
+        ];
+
+        let option = KeyOption::parse(&bytes).unwrap();
+        assert_eq!(option.modifiers(), BootKeyData::CONTROL_PRESSED);
+        assert_eq!(option.boot_option_crc(), 0x1234_5678);
+        assert_eq!(option.boot_option(), 1);
+        assert_eq!(
+            option.keys(),
+            [
+                InputKey {
+                    scan_code: 0,
+                    unicode_char: u16::from(b'a'),
+                },
+                InputKey {
+                    scan_code: 0x0b,
+                    unicode_char: 0,
+                },
+            ]
+        );
+        assert_eq!(option.to_bytes(), bytes);
+
+        assert_eq!(
+            KeyOption::parse(&bytes[..bytes.len() - 1]),
+            Err(OptionParseError::TooShort)
+        );
+    }
+}
@@ -0,0 +1,603 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Building capsules for [`update_capsule`].
+//!
+//! [`update_capsule`] takes the capsules both as an array of pointers to
+//! their headers, and as a scatter-gather list of [`CapsuleBlockDescriptor`]s
+//! in physical memory, which the firmware uses to find the capsules after a
+//! reset. [`CapsuleBuilder`] creates both from capsule payloads.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::runtime::capsule::{CapsuleBuilder, FmpCapsule, FmpImage};
+//! use uefi::runtime::{self, CapsuleFlags};
+//! use uefi::{Status, guid};
+//!
+//! # fn test(firmware: Vec<u8>) -> uefi::Result {
+//! let mut fmp = FmpCapsule::default();
+//! fmp.images.push(FmpImage::new(
+//!     guid!("6b9fbc19-a9c4-43ae-8b4f-2fd6c4d7ab3b"),
+//!     1,
+//!     firmware,
+//! ));
+//!
+//! let update = CapsuleBuilder::new()
+//!     .fmp_capsule(CapsuleFlags::PERSIST_ACROSS_RESET, &fmp)
+//!     .build()?;
+//! let reset_type = update.update()?;
+//! runtime::reset(reset_type, Status::SUCCESS, None);
+//! # }
+//! ```
+//!
+//! # Capsules on disk
+//!
+//! Alternatively, capsules can be written to the [`UPDATE_CAPSULE_DIRECTORY`]
+//! of the EFI system partition with [`write_capsule_file`]. After
+//! [`request_file_capsule_delivery`], the firmware processes them on the next
+//! boot.
+//!
+//! ```no_run
+//! use uefi::fs::FileSystem;
+//! use uefi::runtime::capsule::{self, FmpCapsule};
+//! use uefi::runtime::{self, CapsuleFlags, CapsuleHeader, ResetType};
+//! use uefi::{Status, boot, cstr16};
+//!
+//! # fn test(fmp: &FmpCapsule) -> Result<(), Box<dyn core::error::Error>> {
+//! let esp = boot::get_image_file_system(boot::image_handle())?;
+//! let mut fs = FileSystem::new(esp);
+//! capsule::write_capsule_file(
+//!     &mut fs,
+//!     cstr16!("firmware.cap"),
+//!     CapsuleHeader::FIRMWARE_MANAGEMENT_CAPSULE_GUID,
+//!     CapsuleFlags::PERSIST_ACROSS_RESET,
+//!     &fmp.to_bytes(),
+//! )?;
+//! runtime::request_file_capsule_delivery()?;
+//! runtime::reset(ResetType::COLD, Status::SUCCESS, None);
+//! # }
+//! ```
+//!
+//! [`request_file_capsule_delivery`]: super::request_file_capsule_delivery
+//! [`update_capsule`]: super::update_capsule
+
+use super::{
+    CapsuleBlockDescriptor, CapsuleFlags, CapsuleHeader, CapsuleInfo, ResetType,
+    query_capsule_capabilities, update_capsule,
+};
+use crate::data_types::PhysicalAddress;
+use crate::fs::{Error, FileSystem, FileSystemResult, IoError, IoErrorContext, PathBuf};
+use crate::mem::Pages;
+use crate::mem::memory_map::MemoryType;
+use crate::{CStr16, Guid, Result, Status, cstr16};
+use alloc::vec::Vec;
+use core::{mem, ptr, slice};
+use uefi_raw::capsule::{FirmwareManagementCapsuleHeader, FirmwareManagementCapsuleImageHeader};
+use uefi_raw::table::boot::PAGE_SIZE;
+
+pub use uefi_raw::capsule::ImageCapsuleSupport;
+
+/// Builder for capsules passed to [`update_capsule`].
+///
+/// The capsules and the scatter-gather list are allocated in pages of
+/// [`MemoryType::LOADER_DATA`] by default. The scatter-gather list is
+/// split into blocks of one page each, linked with continuation
+/// descriptors.
+///
+/// [`update_capsule`]: super::update_capsule
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct CapsuleBuilder {
+    capsules: Vec<PendingCapsule>,
+    memory_type: MemoryType,
+    max_block_size: Option<usize>,
+}
+
+/// A capsule added to a [`CapsuleBuilder`].
+#[derive(Clone, Debug)]
+struct PendingCapsule {
+    guid: Guid,
+    flags: CapsuleFlags,
+    payload: Vec<u8>,
+}
+
+impl CapsuleBuilder {
+    /// Create a builder without capsules.
+    pub const fn new() -> Self {
+        Self {
+            capsules: Vec::new(),
+            memory_type: MemoryType::LOADER_DATA,
+            max_block_size: None,
+        }
+    }
+
+    /// Add a capsule with type `guid`. The [`CapsuleHeader`] is prepended
+    /// to `payload`.
+    ///
+    /// If `flags` contains [`CapsuleFlags::INITIATE_RESET`] or
+    /// [`CapsuleFlags::POPULATE_SYSTEM_TABLE`],
+    /// [`CapsuleFlags::PERSIST_ACROSS_RESET`] is added, as required by the
+    /// specification.
+    pub fn capsule(mut self, guid: Guid, flags: CapsuleFlags, payload: &[u8]) -> Self {
+        self.capsules.push(PendingCapsule {
+            guid,
+            flags: normalize_flags(flags),
+            payload: payload.to_vec(),
+        });
+        self
+    }
+
+    /// Add a firmware management capsule, which updates firmware images
+    /// through the firmware management protocol. See [`capsule`] for the
+    /// handling of `flags`.
+    ///
+    /// [`capsule`]: Self::capsule
+    pub fn fmp_capsule(self, flags: CapsuleFlags, capsule: &FmpCapsule) -> Self {
+        self.capsule(
+            CapsuleHeader::FIRMWARE_MANAGEMENT_CAPSULE_GUID,
+            flags,
+            &capsule.to_bytes(),
+        )
+    }
+
+    /// Set the memory type of the capsules and the scatter-gather list. The
+    /// default is [`MemoryType::LOADER_DATA`].
+    pub const fn memory_type(mut self, memory_type: MemoryType) -> Self {
+        self.memory_type = memory_type;
+        self
+    }
+
+    /// Split the capsules into data blocks of at most `size` bytes in the
+    /// scatter-gather list. By default, each capsule is described by a
+    /// single block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub const fn max_block_size(mut self, size: usize) -> Self {
+        assert!(size != 0, "the block size must not be zero");
+        self.max_block_size = Some(size);
+        self
+    }
+
+    /// Allocate the capsules and the scatter-gather list, and check with
+    /// [`query_capsule_capabilities`] that the firmware supports them.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::INVALID_PARAMETER`]: no capsules were added.
+    /// * [`Status::BAD_BUFFER_SIZE`]: a capsule is larger than 4 GiB, or the
+    ///   capsules are larger than the maximum size supported by the
+    ///   firmware.
+    /// * See [`query_capsule_capabilities`] and
+    ///   [`boot::allocate_pages`] for other errors.
+    ///
+    /// [`boot::allocate_pages`]: crate::boot::allocate_pages
+    pub fn build(self) -> Result<CapsuleUpdate> {
+        if self.capsules.is_empty() {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+
+        let mut capsules = Vec::with_capacity(self.capsules.len());
+        let mut blocks = Vec::new();
+        for capsule in &self.capsules {
+            let bytes = capsule_bytes(capsule.guid, capsule.flags, &capsule.payload)?;
+            let pages = Pages::allocate(bytes.len().div_ceil(PAGE_SIZE), self.memory_type)?;
+            // SAFETY: the pages are large enough for the capsule.
+            unsafe {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), pages.as_ptr().as_ptr(), bytes.len());
+            }
+            let block_size = self.max_block_size.unwrap_or(bytes.len());
+            for offset in (0..bytes.len()).step_by(block_size) {
+                blocks.push(CapsuleBlockDescriptor {
+                    length: block_size.min(bytes.len() - offset) as u64,
+                    address: pages.phys_addr() + offset as u64,
+                });
+            }
+            capsules.push(pages);
+        }
+
+        let table_len = PAGE_SIZE / mem::size_of::<CapsuleBlockDescriptor>();
+        let table_count = blocks.len().div_ceil(table_len - 1);
+        let mut tables = (0..table_count)
+            .map(|_| Pages::allocate(1, self.memory_type))
+            .collect::<Result<Vec<_>>>()?;
+        let addresses: Vec<_> = tables.iter().map(Pages::phys_addr).collect();
+        let mut table_slices: Vec<_> = tables
+            .iter_mut()
+            .map(|table| {
+                // SAFETY: the page is owned by `table`, is suitably aligned,
+                // and is fully initialized by `chain_descriptors`.
+                unsafe {
+                    slice::from_raw_parts_mut(
+                        table.as_ptr().cast::<CapsuleBlockDescriptor>().as_ptr(),
+                        table_len,
+                    )
+                }
+            })
+            .collect();
+        chain_descriptors(&blocks, &mut table_slices, &addresses);
+
+        let update = CapsuleUpdate {
+            capsules,
+            tables,
+            info: CapsuleInfo::default(),
+        };
+        let info = query_capsule_capabilities(&update.headers())?;
+        let total_size = update
+            .headers()
+            .iter()
+            .map(|header| u64::from(header.capsule_image_size))
+            .sum::<u64>();
+        if total_size > info.maximum_capsule_size {
+            return Err(Status::BAD_BUFFER_SIZE.into());
+        }
+        Ok(CapsuleUpdate { info, ..update })
+    }
+}
+
+impl Default for CapsuleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Add the flags that are required by the flags in `flags`.
+fn normalize_flags(mut flags: CapsuleFlags) -> CapsuleFlags {
+    if flags.intersects(CapsuleFlags::INITIATE_RESET | CapsuleFlags::POPULATE_SYSTEM_TABLE) {
+        flags |= CapsuleFlags::PERSIST_ACROSS_RESET;
+    }
+    flags
+}
+
+/// Serialize a capsule with its header.
+fn capsule_bytes(guid: Guid, flags: CapsuleFlags, payload: &[u8]) -> Result<Vec<u8>> {
+    let header_size = mem::size_of::<CapsuleHeader>();
+    let size = u32::try_from(header_size + payload.len()).map_err(|_| Status::BAD_BUFFER_SIZE)?;
+    let mut bytes = Vec::with_capacity(size as usize);
+    bytes.extend_from_slice(&guid.to_bytes());
+    bytes.extend_from_slice(&(header_size as u32).to_le_bytes());
+    bytes.extend_from_slice(&flags.bits().to_le_bytes());
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.extend_from_slice(payload);
+    Ok(bytes)
+}
+
+/// Directory of the EFI system partition from which the firmware loads
+/// capsules on disk.
+pub const UPDATE_CAPSULE_DIRECTORY: &CStr16 = cstr16!("\\EFI\\UpdateCapsule");
+
+/// Write a capsule with type `guid` to `file_name` in the
+/// [`UPDATE_CAPSULE_DIRECTORY`] of `fs`, which must be the EFI system
+/// partition. The [`CapsuleHeader`] is prepended to `payload`.
+///
+/// The firmware processes the capsules in the directory in alphabetical
+/// order of their file names, and deletes them afterwards.
+///
+/// # Errors
+///
+/// Returns an [`Error::Io`] with a [`Status::BAD_BUFFER_SIZE`] error if the
+/// capsule is larger than 4 GiB, or the errors of [`FileSystem::write`].
+pub fn write_capsule_file(
+    fs: &mut FileSystem,
+    file_name: &CStr16,
+    guid: Guid,
+    flags: CapsuleFlags,
+    payload: &[u8],
+) -> FileSystemResult<()> {
+    let mut path = PathBuf::from(UPDATE_CAPSULE_DIRECTORY);
+    path.push(file_name);
+    let bytes = capsule_bytes(guid, flags, payload).map_err(|err| {
+        Error::Io(IoError {
+            path: path.clone(),
+            context: IoErrorContext::WriteFailure,
+            uefi_error: err,
+        })
+    })?;
+    fs.create_dir_all(UPDATE_CAPSULE_DIRECTORY)?;
+    fs.write(&path, bytes)
+}
+
+/// Write the scatter-gather list of `blocks` into `tables`, which are at
+/// the physical `addresses`. The last entry of each table is a
+/// continuation descriptor that points to the next table, or the
+/// terminator.
+fn chain_descriptors(
+    blocks: &[CapsuleBlockDescriptor],
+    tables: &mut [&mut [CapsuleBlockDescriptor]],
+    addresses: &[PhysicalAddress],
+) {
+    let mut blocks = blocks.iter();
+    let table_count = tables.len();
+    for (i, table) in tables.iter_mut().enumerate() {
+        let (last, entries) = table.split_last_mut().unwrap();
+        // Entries after the last block are terminators, so that the rest of
+        // the table is never read.
+        for entry in entries {
+            *entry = blocks.next().copied().unwrap_or_default();
+        }
+        *last = CapsuleBlockDescriptor {
+            length: 0,
+            address: if i + 1 < table_count {
+                addresses[i + 1]
+            } else {
+                0
+            },
+        };
+    }
+}
+
+/// Capsules and their scatter-gather list, ready to be passed to
+/// [`update_capsule`]. Created with [`CapsuleBuilder::build`].
+///
+/// The memory of the capsules is freed when this is dropped.
+///
+/// [`update_capsule`]: super::update_capsule
+#[derive(Debug)]
+pub struct CapsuleUpdate {
+    capsules: Vec<Pages>,
+    tables: Vec<Pages>,
+    info: CapsuleInfo,
+}
+
+impl CapsuleUpdate {
+    /// The maximum capsule size and reset type reported by
+    /// [`query_capsule_capabilities`].
+    #[must_use]
+    pub const fn info(&self) -> &CapsuleInfo {
+        &self.info
+    }
+
+    /// The headers of the capsules.
+    #[must_use]
+    pub fn headers(&self) -> Vec<&CapsuleHeader> {
+        self.capsules
+            .iter()
+            // SAFETY: each allocation starts with a capsule header.
+            .map(|pages| unsafe { pages.as_ptr().cast::<CapsuleHeader>().as_ref() })
+            .collect()
+    }
+
+    /// The first table of the scatter-gather list, whose physical address
+    /// is passed to [`update_capsule`].
+    ///
+    /// [`update_capsule`]: super::update_capsule
+    #[must_use]
+    pub fn block_descriptors(&self) -> &[CapsuleBlockDescriptor] {
+        let table = &self.tables[0];
+        // SAFETY: the table was initialized in `build`.
+        unsafe {
+            slice::from_raw_parts(
+                table.as_ptr().cast::<CapsuleBlockDescriptor>().as_ptr(),
+                PAGE_SIZE / mem::size_of::<CapsuleBlockDescriptor>(),
+            )
+        }
+    }
+
+    /// Pass the capsules to the firmware with [`update_capsule`], and return
+    /// the reset type needed to process them.
+    ///
+    /// Capsules with [`CapsuleFlags::PERSIST_ACROSS_RESET`] are processed
+    /// after the next reset, so their memory is kept allocated. Reset the
+    /// system with [`runtime::reset`] and the returned reset type. If a
+    /// capsule has [`CapsuleFlags::INITIATE_RESET`], the firmware resets the
+    /// system itself, and this function does not return.
+    ///
+    /// # Errors
+    ///
+    /// See [`update_capsule`].
+    ///
+    /// [`runtime::reset`]: super::reset
+    /// [`update_capsule`]: super::update_capsule
+    pub fn update(self) -> Result<ResetType> {
+        update_capsule(&self.headers(), self.block_descriptors())?;
+
+        let persist = self
+            .headers()
+            .iter()
+            .any(|header| header.flags.contains(CapsuleFlags::PERSIST_ACROSS_RESET));
+        if persist {
+            // The firmware reads the capsules after the reset.
+            for pages in self.capsules.into_iter().chain(self.tables) {
+                let _ = pages.leak();
+            }
+        }
+        Ok(self.info.reset_type)
+    }
+}
+
+/// A firmware image in an [`FmpCapsule`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FmpImage {
+    /// Identifies the firmware management protocol instance that updates
+    /// the image, by the `ImageTypeId` of its image descriptors.
+    pub image_type_id: Guid,
+
+    /// Index of the image to update, starting at 1.
+    pub image_index: u8,
+
+    /// The hardware instance to update, or zero to update all instances.
+    pub hardware_instance: u64,
+
+    /// Capabilities of the image.
+    pub capsule_support: ImageCapsuleSupport,
+
+    /// The image. If the firmware requires authentication, this starts
+    /// with an `EFI_FIRMWARE_IMAGE_AUTHENTICATION` created by the caller.
+    pub image: Vec<u8>,
+
+    /// Vendor code passed to the firmware management protocol.
+    pub vendor_code: Vec<u8>,
+}
+
+impl FmpImage {
+    /// Create an image for all hardware instances, without vendor code.
+    #[must_use]
+    pub const fn new(image_type_id: Guid, image_index: u8, image: Vec<u8>) -> Self {
+        Self {
+            image_type_id,
+            image_index,
+            hardware_instance: 0,
+            capsule_support: ImageCapsuleSupport::empty(),
+            image,
+            vendor_code: Vec::new(),
+        }
+    }
+}
+
+/// Payload of a firmware management capsule
+/// (`EFI_FIRMWARE_MANAGEMENT_CAPSULE_HEADER`), see
+/// [`CapsuleBuilder::fmp_capsule`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FmpCapsule {
+    /// UEFI drivers that are loaded before the images are processed, for
+    /// example to provide the firmware management protocol.
+    pub embedded_drivers: Vec<Vec<u8>>,
+
+    /// The firmware images.
+    pub images: Vec<FmpImage>,
+}
+
+impl FmpCapsule {
+    /// Serialize the payload. Each item is aligned to 8 bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 65535 drivers or images, or an image
+    /// or its vendor code is larger than 4 GiB.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let item_count = self.embedded_drivers.len() + self.images.len();
+        let header = FirmwareManagementCapsuleHeader {
+            version: FirmwareManagementCapsuleHeader::INIT_VERSION,
+            embedded_driver_count: u16::try_from(self.embedded_drivers.len())
+                .expect("too many drivers"),
+            payload_item_count: u16::try_from(self.images.len()).expect("too many images"),
+        };
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&header.version.to_le_bytes());
+        bytes.extend_from_slice(&header.embedded_driver_count.to_le_bytes());
+        bytes.extend_from_slice(&header.payload_item_count.to_le_bytes());
+        // Offsets are filled in below.
+        let offsets_start = bytes.len();
+        bytes.resize(offsets_start + item_count * mem::size_of::<u64>(), 0);
+
+        let set_offset = |bytes: &mut Vec<u8>, index: usize| {
+            bytes.resize(bytes.len().next_multiple_of(8), 0);
+            let offset = (bytes.len() as u64).to_le_bytes();
+            let start = offsets_start + index * offset.len();
+            bytes[start..start + offset.len()].copy_from_slice(&offset);
+        };
+
+        for (i, driver) in self.embedded_drivers.iter().enumerate() {
+            set_offset(&mut bytes, i);
+            bytes.extend_from_slice(driver);
+        }
+        for (i, image) in self.images.iter().enumerate() {
+            set_offset(&mut bytes, self.embedded_drivers.len() + i);
+            let header = FirmwareManagementCapsuleImageHeader {
+                version: FirmwareManagementCapsuleImageHeader::INIT_VERSION,
+                update_image_type_id: image.image_type_id,
+                update_image_index: image.image_index,
+                reserved_bytes: [0; 3],
+                update_image_size: u32::try_from(image.image.len()).expect("image is too large"),
+                update_vendor_code_size: u32::try_from(image.vendor_code.len())
+                    .expect("vendor code is too large"),
+                update_hardware_instance: image.hardware_instance,
+                image_capsule_support: image.capsule_support,
+            };
+            bytes.extend_from_slice(&header.version.to_le_bytes());
+            bytes.extend_from_slice(&header.update_image_type_id.to_bytes());
+            bytes.push(header.update_image_index);
+            bytes.extend_from_slice(&header.reserved_bytes);
+            bytes.extend_from_slice(&header.update_image_size.to_le_bytes());
+            bytes.extend_from_slice(&header.update_vendor_code_size.to_le_bytes());
+            bytes.extend_from_slice(&header.update_hardware_instance.to_le_bytes());
+            bytes.extend_from_slice(&header.image_capsule_support.bits().to_le_bytes());
+            bytes.extend_from_slice(&image.image);
+            bytes.extend_from_slice(&image.vendor_code);
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guid;
+    use alloc::vec;
+
+    #[test]
+    fn test_normalize_flags() {
+        assert_eq!(
+            normalize_flags(CapsuleFlags::INITIATE_RESET),
+            CapsuleFlags::INITIATE_RESET | CapsuleFlags::PERSIST_ACROSS_RESET
+        );
+        assert_eq!(
+            normalize_flags(CapsuleFlags::TYPE_SPECIFIC_BIT_0),
+            CapsuleFlags::TYPE_SPECIFIC_BIT_0
+        );
+    }
+
+    #[test]
+    fn test_capsule_bytes() {
+        let guid = guid!("01234567-89ab-cdef-0123-456789abcdef");
+        let bytes = capsule_bytes(guid, CapsuleFlags::PERSIST_ACROSS_RESET, &[1, 2, 3]).unwrap();
+        assert_eq!(bytes[..16], guid.to_bytes());
+        assert_eq!(bytes[16..28], [28, 0, 0, 0, 0, 0, 1, 0, 31, 0, 0, 0]);
+        assert_eq!(bytes[28..], [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_chain_descriptors() {
+        let blocks: Vec<_> = (1..=5)
+            .map(|i| CapsuleBlockDescriptor {
+                length: i,
+                address: i * 0x1000,
+            })
+            .collect();
+        let mut table1 = [CapsuleBlockDescriptor::default(); 3];
+        let mut table2 = [CapsuleBlockDescriptor::default(); 3];
+        let mut table3 = [CapsuleBlockDescriptor {
+            length: 0xff,
+            address: 0xff,
+        }; 3];
+        chain_descriptors(
+            &blocks,
+            &mut [&mut table1, &mut table2, &mut table3],
+            &[0xa000, 0xb000, 0xc000],
+        );
+
+        let continuation = |address| CapsuleBlockDescriptor { length: 0, address };
+        assert_eq!(table1, [blocks[0], blocks[1], continuation(0xb000)]);
+        assert_eq!(table2, [blocks[2], blocks[3], continuation(0xc000)]);
+        assert_eq!(table3, [blocks[4], continuation(0), continuation(0)]);
+    }
+
+    #[test]
+    fn test_fmp_capsule() {
+        let image_type_id = guid!("01234567-89ab-cdef-0123-456789abcdef");
+        let mut image = FmpImage::new(image_type_id, 1, vec![0xaa; 3]);
+        image.vendor_code = vec![0xbb];
+        let capsule = FmpCapsule {
+            embedded_drivers: vec![vec![0xcc; 5]],
+            images: vec![image],
+        };
+
+        let bytes = capsule.to_bytes();
+        // Header, with offsets 24 and 32.
+        assert_eq!(bytes[..8], [1, 0, 0, 0, 1, 0, 1, 0]);
+        assert_eq!(bytes[8..16], 24u64.to_le_bytes());
+        assert_eq!(bytes[16..24], 32u64.to_le_bytes());
+        // Driver, followed by padding.
+        assert_eq!(bytes[24..32], [0xcc, 0xcc, 0xcc, 0xcc, 0xcc, 0, 0, 0]);
+        // Image header.
+        assert_eq!(bytes[32..36], [3, 0, 0, 0]);
+        assert_eq!(bytes[36..52], image_type_id.to_bytes());
+        assert_eq!(bytes[52..64], [1, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(bytes[64..80], [0; 16]);
+        // Image and vendor code.
+        assert_eq!(bytes[80..], [0xaa, 0xaa, 0xaa, 0xbb]);
+    }
+}
@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Typed access to global variables.
+//!
+//! The UEFI specification defines variables in the
+//! [`VariableVendor::GLOBAL_VARIABLE`] namespace that configure the firmware:
+//!
+//! * `PlatformLang` and `Lang` are the language of the firmware user
+//!   interface, as an RFC 4646 or ISO 639-2 code. `PlatformLangCodes` and
+//!   `LangCodes` list the supported languages. See [`get_platform_lang`].
+//! * `ConIn`, `ConOut` and `ErrOut` are the device paths of the consoles
+//!   used by default, and `ConInDev`, `ConOutDev` and `ErrOutDev` are the
+//!   device paths of all available consoles. See [`ConsoleVariable`].
+//! * `HwErrRecSupport` is the level of hardware error record persistence
+//!   supported by the platform. See [`get_hw_err_rec_support`].
+//! * `VendorKeys` is whether the Secure Boot keys are the ones provided by
+//!   the platform vendor. See [`get_vendor_keys`].
+//! * `Timeout` is the boot manager timeout. See [`get_timeout`].
+//!
+//! Variables for boot options are in [`boot_manager`], and the Secure Boot
+//! variables in [`secure_boot`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::runtime::global_variables::{self, ConsoleVariable};
+//!
+//! # fn test() -> uefi::Result {
+//! for lang in global_variables::get_platform_lang_codes()? {
+//!     log::info!("supported language: {lang}");
+//! }
+//!
+//! let con_out = global_variables::get_console(ConsoleVariable::ConOut)?;
+//! log::info!("{} output consoles", con_out.instance_iter().count());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`boot_manager`]: super::boot_manager
+//! [`secure_boot`]: super::secure_boot
+
+use super::{VariableAttributes, VariableVendor};
+use crate::proto::device_path::DevicePath;
+use crate::proto::driver::{LanguageIter, LanguageIterKind};
+use crate::{CStr16, Result, Status, cstr16, runtime};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub use super::boot_manager::{get_timeout, set_timeout};
+
+/// Attributes of the writable global variables.
+const ATTRIBUTES: VariableAttributes = VariableAttributes::NON_VOLATILE
+    .union(VariableAttributes::BOOTSERVICE_ACCESS)
+    .union(VariableAttributes::RUNTIME_ACCESS);
+
+/// Strip the null terminator and anything after it from ASCII variable
+/// data.
+fn parse_ascii(mut data: Vec<u8>) -> Result<Vec<u8>> {
+    if let Some(len) = data.iter().position(|c| *c == 0) {
+        data.truncate(len);
+    }
+    if data.is_ascii() {
+        Ok(data)
+    } else {
+        Err(Status::VOLUME_CORRUPTED.into())
+    }
+}
+
+/// Encode a language code as a null-terminated ASCII string.
+fn encode_language(language: &str) -> Result<Vec<u8>> {
+    if !language.is_ascii() || language.contains('\0') {
+        return Err(Status::INVALID_PARAMETER.into());
+    }
+    let mut data = Vec::with_capacity(language.len() + 1);
+    data.extend_from_slice(language.as_bytes());
+    data.push(0);
+    Ok(data)
+}
+
+/// Read a global variable containing an ASCII string.
+fn get_ascii(name: &CStr16) -> Result<Vec<u8>> {
+    let data = runtime::get::<Vec<u8>>(name, &VariableVendor::GLOBAL_VARIABLE)?;
+    parse_ascii(data)
+}
+
+/// Read a language code from a global variable.
+fn get_language(name: &CStr16) -> Result<String> {
+    let data = get_ascii(name)?;
+    // The data was checked to be ASCII.
+    Ok(String::from_utf8(data).unwrap())
+}
+
+/// Read a list of language codes from a global variable.
+fn get_language_codes(name: &CStr16, kind: LanguageIterKind) -> Result<Vec<String>> {
+    let data = get_ascii(name)?;
+    let languages = LanguageIter::from_bytes(&data, kind).map_err(|_| Status::VOLUME_CORRUPTED)?;
+    Ok(languages.map(String::from).collect())
+}
+
+/// Write a language code to a global variable.
+fn set_language(name: &CStr16, language: &str) -> Result {
+    let data = encode_language(language)?;
+    runtime::set_variable(name, &VariableVendor::GLOBAL_VARIABLE, ATTRIBUTES, &data)
+}
+
+/// Get the language of the firmware user interface as an RFC 4646 code,
+/// such as `en-US`, from the `PlatformLang` variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the variable does not exist.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not an ASCII string.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_platform_lang() -> Result<String> {
+    get_language(cstr16!("PlatformLang"))
+}
+
+/// Set the language of the firmware user interface to an RFC 4646 code,
+/// which must be one of [`get_platform_lang_codes`].
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `language` is not an ASCII string, or
+///   the firmware does not support it.
+/// * See [`runtime::set_variable`] for other errors.
+pub fn set_platform_lang(language: &str) -> Result {
+    set_language(cstr16!("PlatformLang"), language)
+}
+
+/// Get the RFC 4646 codes of the languages supported by the firmware, from
+/// the `PlatformLangCodes` variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the variable does not exist.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not an ASCII string.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_platform_lang_codes() -> Result<Vec<String>> {
+    get_language_codes(cstr16!("PlatformLangCodes"), LanguageIterKind::V2)
+}
+
+/// Get the language of the firmware user interface as an ISO 639-2 code,
+/// such as `eng`, from the deprecated `Lang` variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the variable does not exist.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not an ASCII string.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_lang() -> Result<String> {
+    get_language(cstr16!("Lang"))
+}
+
+/// Set the language of the firmware user interface to an ISO 639-2 code,
+/// which must be one of [`get_lang_codes`].
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `language` is not an ASCII string, or
+///   the firmware does not support it.
+/// * See [`runtime::set_variable`] for other errors.
+pub fn set_lang(language: &str) -> Result {
+    set_language(cstr16!("Lang"), language)
+}
+
+/// Get the ISO 639-2 codes of the languages supported by the firmware, from
+/// the deprecated `LangCodes` variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the variable does not exist.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not an ASCII string.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_lang_codes() -> Result<Vec<String>> {
+    get_language_codes(cstr16!("LangCodes"), LanguageIterKind::V1)
+}
+
+/// Variable containing console device paths.
+///
+/// Each variable contains a multi-instance device path, with one instance
+/// per console device. Use [`DevicePath::instance_iter`] to get the
+/// devices.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ConsoleVariable {
+    /// `ConIn`: the default input consoles.
+    ConIn,
+
+    /// `ConOut`: the default output consoles.
+    ConOut,
+
+    /// `ErrOut`: the default error output consoles.
+    ErrOut,
+
+    /// `ConInDev`: all possible input consoles. Read-only.
+    ConInDev,
+
+    /// `ConOutDev`: all possible output consoles. Read-only.
+    ConOutDev,
+
+    /// `ErrOutDev`: all possible error output consoles. Read-only.
+    ErrOutDev,
+}
+
+impl ConsoleVariable {
+    /// Name of the variable.
+    #[must_use]
+    pub const fn name(self) -> &'static CStr16 {
+        match self {
+            Self::ConIn => cstr16!("ConIn"),
+            Self::ConOut => cstr16!("ConOut"),
+            Self::ErrOut => cstr16!("ErrOut"),
+            Self::ConInDev => cstr16!("ConInDev"),
+            Self::ConOutDev => cstr16!("ConOutDev"),
+            Self::ErrOutDev => cstr16!("ErrOutDev"),
+        }
+    }
+
+    /// Whether the variable can be written with [`set_console`].
+    #[must_use]
+    pub const fn is_writable(self) -> bool {
+        matches!(self, Self::ConIn | Self::ConOut | Self::ErrOut)
+    }
+}
+
+/// Get the multi-instance device path of the consoles in `variable`.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the variable does not exist.
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a device path.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_console(variable: ConsoleVariable) -> Result<Box<DevicePath>> {
+    runtime::get(variable.name(), &VariableVendor::GLOBAL_VARIABLE)
+}
+
+/// Set the consoles in `variable` to the instances of `path`. The firmware
+/// uses the new consoles on the next boot.
+///
+/// # Errors
+///
+/// * [`Status::WRITE_PROTECTED`]: the variable is read-only, see
+///   [`ConsoleVariable::is_writable`].
+/// * See [`runtime::set_variable`] for other errors.
+pub fn set_console(variable: ConsoleVariable, path: &DevicePath) -> Result {
+    if !variable.is_writable() {
+        return Err(Status::WRITE_PROTECTED.into());
+    }
+    runtime::set_variable(
+        variable.name(),
+        &VariableVendor::GLOBAL_VARIABLE,
+        ATTRIBUTES,
+        path.as_bytes(),
+    )
+}
+
+/// Get the level of hardware error record persistence supported by the
+/// platform from the `HwErrRecSupport` variable. Zero, which is also
+/// returned if the variable does not exist, means that hardware error
+/// records are not supported.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a `u16`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_hw_err_rec_support() -> Result<u16> {
+    Ok(runtime::get_global(cstr16!("HwErrRecSupport"), runtime::get)?.unwrap_or(0))
+}
+
+/// Set the level of hardware error record persistence supported by the
+/// platform. This is set by the platform firmware or the OS.
+///
+/// # Errors
+///
+/// See [`runtime::set_variable`].
+pub fn set_hw_err_rec_support(level: u16) -> Result {
+    runtime::set(
+        cstr16!("HwErrRecSupport"),
+        &VariableVendor::GLOBAL_VARIABLE,
+        ATTRIBUTES,
+        &level,
+    )
+}
+
+/// Whether the Secure Boot keys are the defaults provided by the platform
+/// vendor, from the `VendorKeys` variable. Returns `false` if the variable
+/// does not exist.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not `0` or `1`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn get_vendor_keys() -> Result<bool> {
+    runtime::secure_boot::get_bool(cstr16!("VendorKeys"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_parse_ascii() {
+        assert_eq!(parse_ascii(b"en-US\0".to_vec()).unwrap(), b"en-US");
+        assert_eq!(parse_ascii(b"en-US".to_vec()).unwrap(), b"en-US");
+        assert_eq!(parse_ascii(b"eng\0xyz".to_vec()).unwrap(), b"eng");
+        assert_eq!(parse_ascii(vec![]).unwrap(), b"");
+        assert_eq!(
+            parse_ascii(vec![b'e', 0x80, 0]).unwrap_err().status(),
+            Status::VOLUME_CORRUPTED
+        );
+    }
+
+    #[test]
+    fn test_encode_language() {
+        assert_eq!(encode_language("en-US").unwrap(), b"en-US\0");
+        assert_eq!(encode_language("").unwrap(), b"\0");
+        assert_eq!(
+            encode_language("fr\0").unwrap_err().status(),
+            Status::INVALID_PARAMETER
+        );
+        assert_eq!(
+            encode_language("ænglisc").unwrap_err().status(),
+            Status::INVALID_PARAMETER
+        );
+    }
+
+    #[test]
+    fn test_console_variable() {
+        assert_eq!(ConsoleVariable::ConOut.name(), cstr16!("ConOut"));
+        assert_eq!(ConsoleVariable::ErrOutDev.name(), cstr16!("ErrOutDev"));
+        assert!(ConsoleVariable::ConIn.is_writable());
+        assert!(!ConsoleVariable::ConInDev.is_writable());
+    }
+}
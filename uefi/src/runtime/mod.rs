@@ -0,0 +1,1877 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! UEFI runtime services.
+//!
+//! These services are available both before and after exiting boot
+//! services. Note that various restrictions apply when calling runtime services
+//! functions after exiting boot services; see the "Calling Convention" section
+//! of the UEFI specification for details.
+
+use crate::data_types::{PhysicalAddress, VirtualAddress};
+use crate::mem::memory_map::{MemoryAttribute, MemoryMap, MemoryMapOwned};
+use crate::table::{self, Revision};
+use crate::{CStr16, Error, Result, Status, StatusExt, cstr16};
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ptr::{self, NonNull};
+use uefi_raw::table::boot::{MemoryDescriptor, PAGE_SIZE};
+
+#[cfg(feature = "alloc")]
+use {
+    crate::CString16,
+    crate::Guid,
+    crate::mem::make_boxed,
+    alloc::borrow::ToOwned,
+    alloc::boxed::Box,
+    alloc::{vec, vec::Vec},
+};
+
+#[cfg(all(feature = "unstable", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(feature = "alloc")]
+pub mod boot_manager;
+#[cfg(feature = "alloc")]
+pub mod capsule;
+#[cfg(feature = "alloc")]
+pub mod global_variables;
+pub mod secure_boot;
+#[cfg(feature = "alloc")]
+pub mod snapshot;
+mod time;
+mod value;
+
+pub use time::{Rfc3339Display, TimeParseError};
+pub use uefi_macros::VariableValue;
+pub use uefi_raw::capsule::{CapsuleBlockDescriptor, CapsuleFlags, CapsuleHeader};
+pub use uefi_raw::table::runtime::{
+    OsIndications, ResetType, TimeCapabilities, VariableAttributes, VariableVendor, WinCertificate,
+    WinCertificateType, WinCertificateUefiGuid,
+};
+pub use uefi_raw::time::Daylight;
+pub use value::VariableValue;
+
+fn runtime_services_raw_panicking() -> NonNull<uefi_raw::table::runtime::RuntimeServices> {
+    let st = table::system_table_raw_panicking();
+    // SAFETY: valid per requirements of `set_system_table`.
+    let st = unsafe { st.as_ref() };
+    NonNull::new(st.runtime_services).expect("runtime services are not active")
+}
+
+/// Query the current time and date information.
+pub fn get_time() -> Result<Time> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut time = Time::invalid();
+    let time_ptr: *mut Time = &mut time;
+    unsafe { (rt.get_time)(time_ptr.cast(), ptr::null_mut()) }.to_result_with_val(|| time)
+}
+
+/// Query the current time and date information and the RTC capabilities.
+pub fn get_time_and_caps() -> Result<(Time, TimeCapabilities)> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut time = Time::invalid();
+    let time_ptr: *mut Time = &mut time;
+    let mut caps = TimeCapabilities::default();
+    unsafe { (rt.get_time)(time_ptr.cast(), &mut caps) }.to_result_with_val(|| (time, caps))
+}
+
+/// Sets the current local time and date information
+///
+/// During runtime, if a PC-AT CMOS device is present in the platform, the
+/// caller must synchronize access to the device before calling `set_time`.
+///
+/// # Safety
+///
+/// Undefined behavior could happen if multiple tasks try to
+/// use this function at the same time without synchronisation.
+pub unsafe fn set_time(time: &Time) -> Result {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let time: *const Time = time;
+    unsafe { (rt.set_time)(time.cast()) }.to_result()
+}
+
+/// Query the wakeup alarm clock, which wakes the system at the programmed
+/// time.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: the wakeup time could not be read due to a
+///   hardware error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support the wakeup
+///   alarm clock, or not after exiting boot services.
+pub fn get_wakeup_time() -> Result<WakeupTime> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut enabled = 0u8;
+    let mut pending = 0u8;
+    let mut time = Time::invalid();
+    let time_ptr: *mut Time = &mut time;
+    unsafe { (rt.get_wakeup_time)(&mut enabled, &mut pending, time_ptr.cast()) }.to_result_with_val(
+        || WakeupTime {
+            enabled: enabled != 0,
+            pending: pending != 0,
+            time,
+        },
+    )
+}
+
+/// Enable the wakeup alarm clock at `time`, or disable it if `time` is
+/// `None`.
+///
+/// During runtime, if a PC-AT CMOS device is present in the platform, the
+/// caller must synchronize access to the device before calling
+/// `set_wakeup_time`.
+///
+/// # Safety
+///
+/// Undefined behavior could happen if multiple tasks try to
+/// use this function at the same time without synchronisation.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: `time` is not valid.
+/// * [`Status::DEVICE_ERROR`]: the wakeup time could not be set due to a
+///   hardware error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support the wakeup
+///   alarm clock, or not after exiting boot services.
+pub unsafe fn set_wakeup_time(time: Option<&Time>) -> Result {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let (enable, time) = match time {
+        Some(time) => (1, ptr::from_ref(time)),
+        None => (0, ptr::null()),
+    };
+    unsafe { (rt.set_wakeup_time)(enable, time.cast()) }.to_result()
+}
+
+/// Get the next high 32 bits of the platform's monotonic counter, and
+/// increment them.
+///
+/// Before exiting boot services, [`boot::get_next_monotonic_count`] returns
+/// all 64 bits of the counter. Use [`MonotonicCounter`] to get 64-bit
+/// values after exiting boot services.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: the counter could not be read due to a
+///   hardware error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support the monotonic
+///   counter after exiting boot services.
+///
+/// [`boot::get_next_monotonic_count`]: crate::boot::get_next_monotonic_count
+pub fn get_next_high_monotonic_count() -> Result<u32> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut high_count = 0;
+    unsafe { (rt.get_next_high_monotonic_count)(&mut high_count) }.to_result_with_val(|| high_count)
+}
+
+/// Convert a pointer from physical to virtual addressing.
+///
+/// This is used by runtime drivers to convert their pointers in the
+/// notification function of [`EventGroup::VirtualAddressChange`], which is
+/// signaled by [`set_virtual_address_map`]. Null pointers are left
+/// unchanged.
+///
+/// # Safety
+///
+/// This may only be called from a notification function of
+/// [`EventGroup::VirtualAddressChange`]. After the conversion, the pointer
+/// may only be dereferenced once the virtual address map is in use.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: the pointer is not in any memory region of the
+///   virtual address map.
+/// * [`Status::UNSUPPORTED`]: this platform does not support converting
+///   pointers after exiting boot services.
+///
+/// [`EventGroup::VirtualAddressChange`]: crate::boot::EventGroup::VirtualAddressChange
+pub unsafe fn convert_pointer<T>(pointer: &mut *mut T) -> Result {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let address: *mut *mut T = pointer;
+    unsafe {
+        (rt.convert_pointer)(
+            uefi_raw::table::runtime::RuntimeServices::OPTIONAL_POINTER,
+            address.cast(),
+        )
+    }
+    .to_result()
+}
+
+/// Checks if a variable exists.
+///
+/// Returns `Ok(true)` if the variable exists, `Ok(false)` if the variable does
+/// not exist, or `Err` if the existence of the variable could not be determined.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: variable could not be read due to a hardware error.
+/// * [`Status::SECURITY_VIOLATION`]: variable could not be read due to an
+///   authentication error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+pub fn variable_exists(name: &CStr16, vendor: &VariableVendor) -> Result<bool> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let attributes = ptr::null_mut();
+    let data = ptr::null_mut();
+    let mut data_size = 0;
+
+    let status = unsafe {
+        (rt.get_variable)(
+            name.as_ptr().cast(),
+            &vendor.0,
+            attributes,
+            &mut data_size,
+            data,
+        )
+    };
+
+    match status {
+        // If the variable exists, the status will be BUFFER_TOO_SMALL because
+        // data_size is 0. Empty variables do not exist, because setting a
+        // variable with empty data deletes the variable. In other words, the
+        // status will never be SUCCESS.
+        Status::BUFFER_TOO_SMALL => Ok(true),
+        Status::NOT_FOUND => Ok(false),
+        _ => Err(Error::from(status)),
+    }
+}
+
+/// Gets the contents and attributes of a variable. The size of `buf` must be at
+/// least as big as the variable's size, although it can be larger.
+///
+/// On success, returns a tuple containing the variable's value (a slice of
+/// `buf`) and the variable's attributes.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: variable was not found.
+/// * [`Status::BUFFER_TOO_SMALL`]: `buf` is not large enough. The required size
+///   will be returned in the error data.
+/// * [`Status::DEVICE_ERROR`]: variable could not be read due to a hardware error.
+/// * [`Status::SECURITY_VIOLATION`]: variable could not be read due to an
+///   authentication error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+pub fn get_variable<'buf>(
+    name: &CStr16,
+    vendor: &VariableVendor,
+    buf: &'buf mut [u8],
+) -> Result<(&'buf mut [u8], VariableAttributes), Option<usize>> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut attributes = VariableAttributes::empty();
+    let mut data_size = buf.len();
+    let status = unsafe {
+        (rt.get_variable)(
+            name.as_ptr().cast(),
+            &vendor.0,
+            &mut attributes,
+            &mut data_size,
+            buf.as_mut_ptr(),
+        )
+    };
+
+    match status {
+        Status::SUCCESS => Ok((&mut buf[..data_size], attributes)),
+        Status::BUFFER_TOO_SMALL => Err(Error::new(status, Some(data_size))),
+        _ => Err(Error::new(status, None)),
+    }
+}
+
+/// Gets the contents and attributes of a variable.
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: variable was not found.
+/// * [`Status::DEVICE_ERROR`]: variable could not be read due to a hardware error.
+/// * [`Status::SECURITY_VIOLATION`]: variable could not be read due to an
+///   authentication error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+#[cfg(feature = "alloc")]
+pub fn get_variable_boxed(
+    name: &CStr16,
+    vendor: &VariableVendor,
+) -> Result<(Box<[u8]>, VariableAttributes)> {
+    let mut out_attr = VariableAttributes::empty();
+    let get_var = |buf| {
+        get_variable(name, vendor, buf).map(|(val, attr)| {
+            // `make_boxed` expects only a DST value to be returned (`val` in
+            // this case), so smuggle the `attr` value out via a separate
+            // variable.
+            out_attr = attr;
+            val
+        })
+    };
+    #[cfg(not(feature = "unstable"))]
+    {
+        make_boxed(get_var).map(|val| (val, out_attr))
+    }
+    #[cfg(feature = "unstable")]
+    {
+        make_boxed(get_var, Global).map(|val| (val, out_attr))
+    }
+}
+
+/// Read a variable that must be exactly `N` bytes long. Variables of
+/// another size are reported as [`Status::VOLUME_CORRUPTED`].
+pub(crate) fn get_variable_array<const N: usize>(
+    name: &CStr16,
+    vendor: &VariableVendor,
+) -> Result<[u8; N]> {
+    let mut buf = [0; N];
+    match get_variable(name, vendor, &mut buf) {
+        Ok((data, _)) => <[u8; N]>::try_from(&*data).map_err(|_| Status::VOLUME_CORRUPTED.into()),
+        Err(err) => match err.status() {
+            Status::BUFFER_TOO_SMALL => Err(Status::VOLUME_CORRUPTED.into()),
+            status => Err(status.into()),
+        },
+    }
+}
+
+/// Read a variable in the global namespace with `read`, which is one of the
+/// `get_variable` functions. Returns `None` if the variable does not exist.
+pub(crate) fn get_global<T>(
+    name: &CStr16,
+    read: impl FnOnce(&CStr16, &VariableVendor) -> Result<T>,
+) -> Result<Option<T>> {
+    match read(name, &VariableVendor::GLOBAL_VARIABLE) {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if err.status() == Status::NOT_FOUND => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Gets each variable key (name and vendor) one at a time.
+///
+/// This is used to iterate over variable keys. See [`variable_keys`] for a more
+/// convenient interface that requires the `alloc` feature.
+///
+/// To get the first variable key, `name` must be initialized to start with a
+/// null character. The `vendor` value is arbitrary. On success, the first
+/// variable's name and vendor will be written out to `name` and `vendor`. Keep
+/// calling `get_next_variable_key` with the same `name` and `vendor` references
+/// to get the remaining variable keys.
+///
+/// All variable names should be valid strings, but this may not be enforced by
+/// firmware. To convert to a string, truncate at the first null and call
+/// [`CStr16::from_u16_with_nul`].
+///
+/// # Errors
+///
+/// * [`Status::NOT_FOUND`]: indicates end of iteration, the last variable keys
+///   was retrieved by the previous call to `get_next_variable_key`.
+/// * [`Status::BUFFER_TOO_SMALL`]: `name` is not large enough. The required
+///   size (in `u16` characters, not bytes) will be returned in the error data.
+/// * [`Status::INVALID_PARAMETER`]: `name` does not contain a null character, or
+///   the `name` and `vendor` are not an existing variable.
+/// * [`Status::DEVICE_ERROR`]: variable could not be read due to a hardware error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+pub fn get_next_variable_key(
+    name: &mut [u16],
+    vendor: &mut VariableVendor,
+) -> Result<(), Option<usize>> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut name_size_in_bytes = size_of_val(name);
+
+    let status = unsafe {
+        (rt.get_next_variable_name)(&mut name_size_in_bytes, name.as_mut_ptr(), &mut vendor.0)
+    };
+    match status {
+        Status::SUCCESS => Ok(()),
+        Status::BUFFER_TOO_SMALL => Err(Error::new(
+            status,
+            Some(name_size_in_bytes / size_of::<u16>()),
+        )),
+        _ => Err(Error::new(status, None)),
+    }
+}
+
+/// Get an iterator over all UEFI variables.
+///
+/// See [`VariableKeys`] for details.
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn variable_keys() -> VariableKeys {
+    VariableKeys::new()
+}
+
+/// Iterator over all UEFI variables.
+///
+/// Each iteration yields a `Result<`[`VariableKey`]`>`. Error values:
+///
+/// * [`Status::DEVICE_ERROR`]: variable could not be read due to a hardware error.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct VariableKeys {
+    name: Vec<u16>,
+    vendor: VariableVendor,
+    is_done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl VariableKeys {
+    fn new() -> Self {
+        // Create a name buffer with a large default size and zero
+        // initialize it. A Toshiba Satellite Pro R50-B-12P was found
+        // to not correctly update the VariableNameSize passed into
+        // GetNextVariableName and starting with a large buffer works
+        // around this issue.
+        let name = vec![0; 512];
+
+        Self {
+            name,
+            // The initial vendor GUID is arbitrary.
+            vendor: VariableVendor(Guid::default()),
+            is_done: false,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for VariableKeys {
+    type Item = Result<VariableKey>;
+
+    fn next(&mut self) -> Option<Result<VariableKey>> {
+        if self.is_done {
+            return None;
+        }
+
+        let mut result = get_next_variable_key(&mut self.name, &mut self.vendor);
+
+        // If the name buffer was too small, resize it to be big enough and call
+        // `get_next_variable_key` again.
+        if let Err(err) = &result {
+            if let Some(required_size) = err.data() {
+                self.name.resize(*required_size, 0u16);
+                result = get_next_variable_key(&mut self.name, &mut self.vendor);
+            }
+        }
+
+        match result {
+            Ok(()) => {
+                // Convert the name to a `CStr16`, yielding an error if invalid.
+                let Ok(name) = CStr16::from_u16_until_nul(&self.name) else {
+                    return Some(Err(Status::UNSUPPORTED.into()));
+                };
+
+                Some(Ok(VariableKey {
+                    name: name.to_owned(),
+                    vendor: self.vendor,
+                }))
+            }
+            Err(err) => {
+                if err.status() == Status::NOT_FOUND {
+                    // This status indicates the end of the list. The final variable
+                    // has already been yielded at this point, so return `None`.
+                    self.is_done = true;
+                    None
+                } else {
+                    // Return the error and end iteration.
+                    self.is_done = true;
+                    Some(Err(err.to_err_without_payload()))
+                }
+            }
+        }
+    }
+}
+
+/// Sets the value of a variable. This can be used to create a new variable,
+/// update an existing variable, or (when the size of `data` is zero)
+/// delete a variable.
+///
+/// # Warnings
+///
+/// The [`Status::WARN_RESET_REQUIRED`] warning will be returned when using
+/// this function to transition the Secure Boot mode to setup mode or audit
+/// mode if the firmware requires a reboot for that operation.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: invalid attributes, name, or vendor.
+/// * [`Status::OUT_OF_RESOURCES`]: not enough storage is available to hold
+///   the variable.
+/// * [`Status::WRITE_PROTECTED`]: variable is read-only.
+/// * [`Status::SECURITY_VIOLATION`]: variable could not be written due to an
+///   authentication error.
+/// * [`Status::NOT_FOUND`]: attempted to update a non-existent variable.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+pub fn set_variable(
+    name: &CStr16,
+    vendor: &VariableVendor,
+    attributes: VariableAttributes,
+    data: &[u8],
+) -> Result {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    unsafe {
+        (rt.set_variable)(
+            name.as_ptr().cast(),
+            &vendor.0,
+            attributes,
+            data.len(),
+            data.as_ptr(),
+        )
+        .to_result()
+    }
+}
+
+/// Deletes a UEFI variable.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: invalid name or vendor.
+/// * [`Status::WRITE_PROTECTED`]: variable is read-only.
+/// * [`Status::SECURITY_VIOLATION`]: variable could not be deleted due to an
+///   authentication error.
+/// * [`Status::NOT_FOUND`]: attempted to delete a non-existent variable.
+/// * [`Status::UNSUPPORTED`]: this platform does not support variable storage
+///   after exiting boot services.
+pub fn delete_variable(name: &CStr16, vendor: &VariableVendor) -> Result {
+    set_variable(name, vendor, VariableAttributes::empty(), &[])
+}
+
+/// Gets the value of a variable as a `T`.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::runtime::{self, VariableVendor};
+/// use uefi::cstr16;
+///
+/// # fn test() -> uefi::Result {
+/// let boot_order: Vec<u16> = runtime::get(cstr16!("BootOrder"), &VariableVendor::GLOBAL_VARIABLE)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the data of the variable is not a valid
+///   `T`.
+/// * See [`get_variable_boxed`] for other errors.
+#[cfg(feature = "alloc")]
+pub fn get<T: VariableValue>(name: &CStr16, vendor: &VariableVendor) -> Result<T> {
+    let (data, _) = get_variable_boxed(name, vendor)?;
+    T::decode(&data).ok_or_else(|| Status::VOLUME_CORRUPTED.into())
+}
+
+/// Sets the value of a variable to `value`.
+///
+/// # Errors
+///
+/// See [`set_variable`].
+#[cfg(feature = "alloc")]
+pub fn set<T: VariableValue>(
+    name: &CStr16,
+    vendor: &VariableVendor,
+    attributes: VariableAttributes,
+    value: &T,
+) -> Result {
+    let mut data = vec![0; value.encoded_len()];
+    value.encode(&mut data);
+    set_variable(name, vendor, attributes, &data)
+}
+
+/// Sets the value of a variable with
+/// [`VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS`], such as the
+/// Secure Boot databases `db`, `dbx` and `KEK`.
+///
+/// The `auth` header is prepended to `data`, and
+/// `TIME_BASED_AUTHENTICATED_WRITE_ACCESS` is added to `attributes`. The
+/// signature in `auth` must have been created over
+/// [`VariableAuthentication::signed_data`] with the same arguments.
+///
+/// Include [`VariableAttributes::APPEND_WRITE`] in `attributes` to append
+/// `data` to the current value instead of replacing it. For the signature
+/// databases, the firmware does not append signatures that are already
+/// present. To delete the variable, pass empty `data`.
+///
+/// # Errors
+///
+/// * [`Status::SECURITY_VIOLATION`]: the signature could not be verified,
+///   or the time stamp is not later than that of the previous write and
+///   `APPEND_WRITE` is not set.
+/// * See [`set_variable`] for other errors.
+#[cfg(feature = "alloc")]
+pub fn set_authenticated_variable(
+    name: &CStr16,
+    vendor: &VariableVendor,
+    attributes: VariableAttributes,
+    auth: &VariableAuthentication,
+    data: &[u8],
+) -> Result {
+    let mut payload = auth.to_bytes();
+    payload.extend_from_slice(data);
+    set_variable(
+        name,
+        vendor,
+        attributes | VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS,
+        &payload,
+    )
+}
+
+/// Get information about UEFI variable storage space for the type
+/// of variable specified in `attributes`.
+///
+/// This operation is only supported starting with UEFI 2.0.
+///
+/// See [`VariableStorageInfo`] for details of the information returned.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: invalid combination of variable attributes.
+/// * [`Status::UNSUPPORTED`]: the combination of variable attributes is not
+///   supported on this platform, or the UEFI version is less than 2.0.
+pub fn query_variable_info(attributes: VariableAttributes) -> Result<VariableStorageInfo> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    if rt.header.revision < Revision::EFI_2_00 {
+        return Err(Status::UNSUPPORTED.into());
+    }
+
+    let mut info = VariableStorageInfo::default();
+    unsafe {
+        (rt.query_variable_info)(
+            attributes,
+            &mut info.maximum_variable_storage_size,
+            &mut info.remaining_variable_storage_size,
+            &mut info.maximum_variable_size,
+        )
+        .to_result_with_val(|| info)
+    }
+}
+
+/// Passes capsules to the firmware.
+///
+/// Capsules are most commonly used to update system firmware.
+///
+/// # Errors
+///
+/// * [`Status::INVALID_PARAMETER`]: zero capsules were provided, or the
+///   capsules are invalid.
+/// * [`Status::DEVICE_ERROR`]: the capsule update was started but failed to a
+///   device error.
+/// * [`Status::OUT_OF_RESOURCES`]: before exiting boot services, indicates the
+///   capsule is compatible with the platform but there are insufficient
+///   resources to complete the update. After exiting boot services, indicates
+///   the capsule is compatible with the platform but can only be processed
+///   before exiting boot services.
+/// * [`Status::UNSUPPORTED`]: this platform does not support capsule updates
+///   after exiting boot services.
+pub fn update_capsule(
+    capsule_header_array: &[&CapsuleHeader],
+    capsule_block_descriptors: &[CapsuleBlockDescriptor],
+) -> Result {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    unsafe {
+        (rt.update_capsule)(
+            capsule_header_array.as_ptr().cast(),
+            capsule_header_array.len(),
+            capsule_block_descriptors.as_ptr() as PhysicalAddress,
+        )
+        .to_result()
+    }
+}
+
+/// Tests whether a capsule or capsules can be updated via [`update_capsule`].
+///
+/// See [`CapsuleInfo`] for details of the information returned.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: before exiting boot services, indicates the
+///   capsule is compatible with the platform but there are insufficient
+///   resources to complete the update. After exiting boot services, indicates
+///   the capsule is compatible with the platform but can only be processed
+///   before exiting boot services.
+/// * [`Status::UNSUPPORTED`]: either the capsule type is not supported by this
+///   platform, or the platform does not support capsule updates after exiting
+///   boot services.
+pub fn query_capsule_capabilities(capsule_header_array: &[&CapsuleHeader]) -> Result<CapsuleInfo> {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let mut info = CapsuleInfo::default();
+    unsafe {
+        (rt.query_capsule_capabilities)(
+            capsule_header_array.as_ptr().cast(),
+            capsule_header_array.len(),
+            &mut info.maximum_capsule_size,
+            &mut info.reset_type,
+        )
+        .to_result_with_val(|| info)
+    }
+}
+
+/// Query the features that the firmware supports in `OsIndications`, from
+/// the `OsIndicationsSupported` variable.
+///
+/// Returns no flags if the variable does not exist.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable does not have the size of a
+///   `u64`.
+/// * See [`get_variable`] for other errors.
+pub fn os_indications_supported() -> Result<OsIndications> {
+    let supported =
+        get_global(cstr16!("OsIndicationsSupported"), get_variable_array)?.map(u64::from_le_bytes);
+    Ok(OsIndications::from_bits_retain(supported.unwrap_or(0)))
+}
+
+/// Query the features requested from the firmware, from the `OsIndications`
+/// variable.
+///
+/// Returns no flags if the variable does not exist.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable does not have the size of a
+///   `u64`.
+/// * See [`get_variable`] for other errors.
+pub fn os_indications() -> Result<OsIndications> {
+    let indications =
+        get_global(cstr16!("OsIndications"), get_variable_array)?.map(u64::from_le_bytes);
+    Ok(OsIndications::from_bits_retain(indications.unwrap_or(0)))
+}
+
+/// Request features from the firmware by writing the `OsIndications`
+/// variable.
+///
+/// # Errors
+///
+/// See [`set_variable`].
+pub fn set_os_indications(indications: OsIndications) -> Result {
+    set_variable(
+        cstr16!("OsIndications"),
+        &VariableVendor::GLOBAL_VARIABLE,
+        VariableAttributes::NON_VOLATILE
+            | VariableAttributes::BOOTSERVICE_ACCESS
+            | VariableAttributes::RUNTIME_ACCESS,
+        &indications.bits().to_le_bytes(),
+    )
+}
+
+/// Update the `OsIndications` variable with `f`, which is passed the
+/// current flags and returns the new flags. Flags unknown to this crate are
+/// preserved. Returns the new flags.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::runtime::{self, OsIndications};
+///
+/// # fn test() -> uefi::Result {
+/// runtime::update_os_indications(|flags| flags | OsIndications::START_OS_RECOVERY)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// See [`os_indications`] and [`set_os_indications`].
+pub fn update_os_indications(
+    f: impl FnOnce(OsIndications) -> OsIndications,
+) -> Result<OsIndications> {
+    let indications = f(os_indications()?);
+    set_os_indications(indications)?;
+    Ok(indications)
+}
+
+/// Reset the computer into the firmware user interface.
+///
+/// This sets [`OsIndications::BOOT_TO_FW_UI`] and performs a cold reset.
+/// Only returns if that fails.
+///
+/// # Errors
+///
+/// * [`Status::UNSUPPORTED`]: the firmware does not support booting to its
+///   user interface.
+/// * See [`os_indications_supported`] and [`update_os_indications`] for
+///   other errors.
+#[must_use]
+pub fn reset_to_firmware_ui() -> Error {
+    let result = os_indications_supported().and_then(|supported| {
+        if !supported.contains(OsIndications::BOOT_TO_FW_UI) {
+            return Err(Status::UNSUPPORTED.into());
+        }
+        update_os_indications(|flags| flags | OsIndications::BOOT_TO_FW_UI)
+    });
+    match result {
+        Ok(_) => reset(ResetType::COLD, Status::SUCCESS, None),
+        Err(err) => err,
+    }
+}
+
+/// Request the firmware to process the capsules in the
+/// `\EFI\UpdateCapsule` directory of the EFI system partition on the
+/// next boot, by setting [`OsIndications::FILE_CAPSULE_DELIVERY_SUPPORTED`].
+///
+/// The capsules can be written with `capsule::write_capsule_file`. The
+/// system must be reset afterwards.
+///
+/// # Errors
+///
+/// * [`Status::UNSUPPORTED`]: the firmware does not support capsules on
+///   disk.
+/// * See [`os_indications_supported`] and [`update_os_indications`] for
+///   other errors.
+pub fn request_file_capsule_delivery() -> Result {
+    if !os_indications_supported()?.contains(OsIndications::FILE_CAPSULE_DELIVERY_SUPPORTED) {
+        return Err(Status::UNSUPPORTED.into());
+    }
+    update_os_indications(|flags| flags | OsIndications::FILE_CAPSULE_DELIVERY_SUPPORTED)
+        .map(|_| ())
+}
+
+/// Resets the computer.
+///
+/// See [`ResetType`] for details of the various reset types.
+///
+/// For a normal reset the value of `status` should be
+/// [`Status::SUCCESS`]. Otherwise, an error code can be used.
+///
+/// The `data` arg is usually `None`. Otherwise, it must contain a UCS-2
+/// null-terminated string followed by additional binary data. For
+/// [`ResetType::PLATFORM_SPECIFIC`], the binary data must be a vendor-specific
+/// [`Guid`] that indicates the type of reset to perform.
+///
+/// This function never returns.
+pub fn reset(reset_type: ResetType, status: Status, data: Option<&[u8]>) -> ! {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    let (size, data) = data
+        .map(|data| (data.len(), data.as_ptr()))
+        .unwrap_or((0, ptr::null()));
+
+    unsafe { (rt.reset_system)(reset_type, status, size, data) }
+}
+
+/// Changes the runtime addressing mode of EFI firmware from physical to
+/// virtual. It is up to the caller to translate the old system table address
+/// to a new virtual address and provide it for this function.
+///
+/// If successful, this function will call [`set_system_table`] with
+/// `new_system_table_virtual_addr`.
+///
+/// [`set_system_table`]: table::set_system_table
+///
+/// # Safety
+///
+/// The caller must ensure the memory map is valid.
+///
+/// # Errors
+///
+/// * [`Status::UNSUPPORTED`]: either boot services haven't been exited, the
+///   firmware's addressing mode is already virtual, or the firmware does not
+///   support this operation.
+/// * [`Status::NO_MAPPING`]: `map` is missing a required range.
+/// * [`Status::NOT_FOUND`]: `map` contains an address that is not in the
+///   current memory map.
+pub unsafe fn set_virtual_address_map(
+    map: &mut [MemoryDescriptor],
+    new_system_table_virtual_addr: *const uefi_raw::table::system::SystemTable,
+) -> Result {
+    let rt = runtime_services_raw_panicking();
+    let rt = unsafe { rt.as_ref() };
+
+    // Unsafe Code Guidelines guarantees that there is no padding in an array or a slice
+    // between its elements if the element type is `repr(C)`, which is our case.
+    //
+    // See https://rust-lang.github.io/unsafe-code-guidelines/layout/arrays-and-slices.html
+    let map_size = size_of_val(map);
+    let entry_size = size_of::<MemoryDescriptor>();
+    let entry_version = MemoryDescriptor::VERSION;
+    let map_ptr = map.as_mut_ptr();
+    unsafe { (rt.set_virtual_address_map)(map_size, entry_size, entry_version, map_ptr) }
+        .to_result()?;
+
+    // Update the global system table pointer.
+    unsafe { table::set_system_table(new_system_table_virtual_addr) };
+
+    Ok(())
+}
+
+/// Changes the runtime addressing mode of EFI firmware from physical to
+/// virtual, using the memory map returned by [`boot::exit_boot_services`].
+///
+/// The runtime memory regions of `map` are copied into `virtual_map`, with
+/// their virtual addresses chosen by `mapping`, and the filled part of
+/// `virtual_map` is returned. `map` itself is not modified. The caller must
+/// map the returned regions in its page tables before calling runtime
+/// services again.
+///
+/// Memory can no longer be allocated once boot services have been exited,
+/// so the caller provides the storage for `virtual_map`. A firmware usually
+/// has only a few dozen runtime memory regions.
+///
+/// If successful, this function will call [`set_system_table`] with the
+/// virtual address of the system table.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::boot;
+/// use uefi::mem::memory_map::MemoryDescriptor;
+/// use uefi::runtime::{self, VirtualMapping};
+///
+/// # fn test() -> uefi::Result {
+/// const KERNEL_BASE: u64 = 0xffff_8000_0000_0000;
+///
+/// let map = unsafe { boot::exit_boot_services(None) };
+/// let mut virtual_map = [MemoryDescriptor::default(); 64];
+/// let virtual_map = unsafe {
+///     runtime::set_virtual_address_map_from(
+///         &map,
+///         VirtualMapping::Offset(KERNEL_BASE),
+///         &mut virtual_map,
+///     )?
+/// };
+/// // Map the entries of `virtual_map` at their virtual addresses before
+/// // calling runtime services again.
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`boot::exit_boot_services`]: crate::boot::exit_boot_services
+/// [`set_system_table`]: table::set_system_table
+///
+/// # Safety
+///
+/// Boot services must have been exited, and `map` must be the memory map
+/// returned by [`boot::exit_boot_services`]. The virtual addresses returned
+/// by `mapping` must be page aligned and must not overlap.
+///
+/// # Errors
+///
+/// * [`Status::BUFFER_TOO_SMALL`]: `virtual_map` can't hold all runtime
+///   memory regions.
+/// * [`Status::NOT_FOUND`]: the system table is not in runtime memory, or
+///   `map` contains an address that is not in the current memory map.
+/// * See [`set_virtual_address_map`] for other errors.
+pub unsafe fn set_virtual_address_map_from<'a>(
+    map: &MemoryMapOwned,
+    mapping: VirtualMapping<'_>,
+    virtual_map: &'a mut [MemoryDescriptor],
+) -> Result<&'a mut [MemoryDescriptor]> {
+    let system_table = table::system_table_raw_panicking().as_ptr() as PhysicalAddress;
+    let (virtual_map, new_system_table) =
+        build_virtual_map(map, mapping, system_table, virtual_map)?;
+    unsafe { set_virtual_address_map(virtual_map, new_system_table as *const _) }?;
+    Ok(virtual_map)
+}
+
+/// Copies the runtime memory regions of `map` into `buffer`, with their
+/// virtual addresses chosen by `mapping`. Returns the filled part of
+/// `buffer`, and the virtual address of `system_table`.
+fn build_virtual_map<'a>(
+    map: &impl MemoryMap,
+    mut mapping: VirtualMapping<'_>,
+    system_table: PhysicalAddress,
+    buffer: &'a mut [MemoryDescriptor],
+) -> Result<(&'a mut [MemoryDescriptor], VirtualAddress)> {
+    let runtime = map
+        .entries()
+        .filter(|desc| desc.att.contains(MemoryAttribute::RUNTIME));
+    let mut len = 0;
+    let mut new_system_table = None;
+    for desc in runtime {
+        let entry = buffer.get_mut(len).ok_or(Status::BUFFER_TOO_SMALL)?;
+        *entry = MemoryDescriptor {
+            virt_start: mapping.virtual_address(desc.phys_start),
+            ..*desc
+        };
+        let size = desc.page_count.saturating_mul(PAGE_SIZE as u64);
+        if (desc.phys_start..desc.phys_start.saturating_add(size)).contains(&system_table) {
+            new_system_table = Some(entry.virt_start + (system_table - desc.phys_start));
+        }
+        len += 1;
+    }
+    let new_system_table = new_system_table.ok_or(Status::NOT_FOUND)?;
+    Ok((&mut buffer[..len], new_system_table))
+}
+
+/// Policy for choosing the virtual addresses of runtime memory regions in
+/// [`set_virtual_address_map_from`].
+pub enum VirtualMapping<'a> {
+    /// Map each region at its physical address plus a fixed offset.
+    Offset(u64),
+
+    /// Map each region at the virtual address returned by the closure for
+    /// the physical start address of the region.
+    Custom(&'a mut dyn FnMut(PhysicalAddress) -> VirtualAddress),
+}
+
+impl VirtualMapping<'_> {
+    fn virtual_address(&mut self, phys_addr: PhysicalAddress) -> VirtualAddress {
+        match self {
+            Self::Offset(offset) => phys_addr.wrapping_add(*offset),
+            Self::Custom(f) => f(phys_addr),
+        }
+    }
+}
+
+impl Debug for VirtualMapping<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Offset(offset) => f.debug_tuple("Offset").field(offset).finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Date and time representation.
+#[derive(Copy, Clone, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct Time(uefi_raw::time::Time);
+
+/// Input parameters for [`Time::new`].
+#[derive(Copy, Clone, Debug)]
+pub struct TimeParams {
+    /// Year in the range `1900..=9999`.
+    pub year: u16,
+
+    /// Month in the range `1..=12`.
+    pub month: u8,
+
+    /// Day in the range `1..=31`.
+    pub day: u8,
+
+    /// Hour in the range `0.=23`.
+    pub hour: u8,
+
+    /// Minute in the range `0..=59`.
+    pub minute: u8,
+
+    /// Second in the range `0..=59`.
+    pub second: u8,
+
+    /// Fraction of a second represented as nanoseconds in the range
+    /// `0..=999_999_999`.
+    pub nanosecond: u32,
+
+    /// Offset in minutes from UTC in the range `-1440..=1440`, or
+    /// local time if `None`.
+    pub time_zone: Option<i16>,
+
+    /// Daylight savings time information.
+    pub daylight: Daylight,
+}
+
+/// Error returned by [`Time`] methods. A bool value of `true` means
+/// the specified field is outside its valid range.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeError {
+    pub year: bool,
+    pub month: bool,
+    pub day: bool,
+    pub hour: bool,
+    pub minute: bool,
+    pub second: bool,
+    pub nanosecond: bool,
+    pub timezone: bool,
+    pub daylight: bool,
+}
+
+impl core::error::Error for TimeError {}
+
+impl Display for TimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.year {
+            writeln!(f, "year not within `1900..=9999`")?;
+        }
+        if self.month {
+            writeln!(f, "month not within `1..=12")?;
+        }
+        if self.day {
+            writeln!(f, "day not within `1..=31`")?;
+        }
+        if self.hour {
+            writeln!(f, "hour not within `0..=23`")?;
+        }
+        if self.minute {
+            writeln!(f, "minute not within `0..=59`")?;
+        }
+        if self.second {
+            writeln!(f, "second not within `0..=59`")?;
+        }
+        if self.nanosecond {
+            writeln!(f, "nanosecond not within `0..=999_999_999`")?;
+        }
+        if self.timezone {
+            writeln!(
+                f,
+                "time_zone not `Time::UNSPECIFIED_TIMEZONE` nor within `-1440..=1440`"
+            )?;
+        }
+        if self.daylight {
+            writeln!(f, "unknown bits set for daylight")?;
+        }
+        Ok(())
+    }
+}
+
+impl Time {
+    /// Unspecified Timezone/local time.
+    const UNSPECIFIED_TIMEZONE: i16 = uefi_raw::time::Time::UNSPECIFIED_TIMEZONE;
+
+    /// Create a `Time` value. If a field is not in the valid range,
+    /// [`TimeError`] is returned.
+    pub fn new(params: TimeParams) -> core::result::Result<Self, TimeError> {
+        let time = Self(uefi_raw::time::Time {
+            year: params.year,
+            month: params.month,
+            day: params.day,
+            hour: params.hour,
+            minute: params.minute,
+            second: params.second,
+            pad1: 0,
+            nanosecond: params.nanosecond,
+            time_zone: params.time_zone.unwrap_or(Self::UNSPECIFIED_TIMEZONE),
+            daylight: params.daylight,
+            pad2: 0,
+        });
+
+        time.is_valid().map(|_| time)
+    }
+
+    /// Create an invalid `Time` with all fields set to zero. This can
+    /// be used with [`FileInfo`] to indicate a field should not be
+    /// updated when calling [`File::set_info`].
+    ///
+    /// [`FileInfo`]: uefi::proto::media::file::FileInfo
+    /// [`File::set_info`]: uefi::proto::media::file::File::set_info
+    #[must_use]
+    pub const fn invalid() -> Self {
+        Self(uefi_raw::time::Time::invalid())
+    }
+
+    /// `Ok()` if all fields are within valid ranges, `Err(TimeError)` otherwise.
+    pub fn is_valid(&self) -> core::result::Result<(), TimeError> {
+        let mut err = TimeError::default();
+        if !(1900..=9999).contains(&self.year()) {
+            err.year = true;
+        }
+        if !(1..=12).contains(&self.month()) {
+            err.month = true;
+        }
+        if !(1..=31).contains(&self.day()) {
+            err.day = true;
+        }
+        if self.hour() > 23 {
+            err.hour = true;
+        }
+        if self.minute() > 59 {
+            err.minute = true;
+        }
+        if self.second() > 59 {
+            err.second = true;
+        }
+        if self.nanosecond() > 999_999_999 {
+            err.nanosecond = true;
+        }
+        if self.time_zone().is_some() && !((-1440..=1440).contains(&self.time_zone().unwrap())) {
+            err.timezone = true;
+        }
+        // All fields are false, i.e., within their valid range.
+        if err == TimeError::default() {
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+
+    /// Query the year.
+    #[must_use]
+    pub const fn year(&self) -> u16 {
+        self.0.year
+    }
+
+    /// Query the month.
+    #[must_use]
+    pub const fn month(&self) -> u8 {
+        self.0.month
+    }
+
+    /// Query the day.
+    #[must_use]
+    pub const fn day(&self) -> u8 {
+        self.0.day
+    }
+
+    /// Query the hour.
+    #[must_use]
+    pub const fn hour(&self) -> u8 {
+        self.0.hour
+    }
+
+    /// Query the minute.
+    #[must_use]
+    pub const fn minute(&self) -> u8 {
+        self.0.minute
+    }
+
+    /// Query the second.
+    #[must_use]
+    pub const fn second(&self) -> u8 {
+        self.0.second
+    }
+
+    /// Query the nanosecond.
+    #[must_use]
+    pub const fn nanosecond(&self) -> u32 {
+        self.0.nanosecond
+    }
+
+    /// Query the time offset in minutes from UTC, or None if using local time.
+    #[must_use]
+    pub const fn time_zone(&self) -> Option<i16> {
+        if self.0.time_zone == Self::UNSPECIFIED_TIMEZONE {
+            None
+        } else {
+            Some(self.0.time_zone)
+        }
+    }
+
+    /// Query the daylight savings time information.
+    #[must_use]
+    pub const fn daylight(&self) -> Daylight {
+        self.0.daylight
+    }
+
+    /// Convert to the byte representation of an `EFI_TIME`, which can be
+    /// converted back with `Time::try_from(&bytes[..])`.
+    #[must_use]
+    pub const fn to_bytes(self) -> [u8; 16] {
+        let year = self.0.year.to_le_bytes();
+        let nanosecond = self.0.nanosecond.to_le_bytes();
+        let time_zone = self.0.time_zone.to_le_bytes();
+        [
+            year[0],
+            year[1],
+            self.0.month,
+            self.0.day,
+            self.0.hour,
+            self.0.minute,
+            self.0.second,
+            self.0.pad1,
+            nanosecond[0],
+            nanosecond[1],
+            nanosecond[2],
+            nanosecond[3],
+            time_zone[0],
+            time_zone[1],
+            self.0.daylight.bits(),
+            self.0.pad2,
+        ]
+    }
+}
+
+impl Debug for Time {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} ",
+            self.0.year, self.0.month, self.0.day
+        )?;
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:09}",
+            self.0.hour, self.0.minute, self.0.second, self.0.nanosecond
+        )?;
+        if self.0.time_zone == Self::UNSPECIFIED_TIMEZONE {
+            write!(f, ", Timezone=local")?;
+        } else {
+            write!(f, ", Timezone={}", self.0.time_zone)?;
+        }
+        write!(f, ", Daylight={:?}", self.0.daylight)
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Error returned from failing to convert a byte slice into a [`Time`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeByteConversionError {
+    /// One or more fields of the converted [`Time`] is invalid.
+    InvalidFields(TimeError),
+    /// The byte slice is not large enough to hold a [`Time`].
+    InvalidSize,
+}
+
+impl Display for TimeByteConversionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidFields(error) => write!(f, "{error}"),
+            Self::InvalidSize => write!(
+                f,
+                "the byte slice is not large enough to hold a Time struct"
+            ),
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for Time {
+    type Error = TimeByteConversionError;
+
+    fn try_from(bytes: &[u8]) -> core::result::Result<Self, Self::Error> {
+        if size_of::<Self>() <= bytes.len() {
+            let year = u16::from_le_bytes(bytes[0..2].try_into().unwrap());
+            let month = bytes[2];
+            let day = bytes[3];
+            let hour = bytes[4];
+            let minute = bytes[5];
+            let second = bytes[6];
+            let nanosecond = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+            let time_zone = match i16::from_le_bytes(bytes[12..14].try_into().unwrap()) {
+                Self::UNSPECIFIED_TIMEZONE => None,
+                num => Some(num),
+            };
+            let daylight = Daylight::from_bits(bytes[14]).ok_or(
+                TimeByteConversionError::InvalidFields(TimeError {
+                    daylight: true,
+                    ..Default::default()
+                }),
+            )?;
+
+            let time_params = TimeParams {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                nanosecond,
+                time_zone,
+                daylight,
+            };
+
+            Self::new(time_params).map_err(TimeByteConversionError::InvalidFields)
+        } else {
+            Err(TimeByteConversionError::InvalidSize)
+        }
+    }
+}
+
+/// Unique key for a variable.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct VariableKey {
+    /// Unique identifier for the vendor.
+    pub vendor: VariableVendor,
+
+    /// Name of the variable, unique with the vendor namespace.
+    pub name: CString16,
+}
+
+#[cfg(feature = "alloc")]
+impl VariableKey {
+    /// Name of the variable.
+    #[deprecated = "Use the VariableKey.name field instead"]
+    #[allow(clippy::missing_const_for_fn)] // false-positive since Rust 1.86
+    pub fn name(&self) -> core::result::Result<&CStr16, crate::data_types::FromSliceWithNulError> {
+        Ok(&self.name)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for VariableKey {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "VariableKey {{ name: \"{}\", vendor: ", self.name)?;
+
+        if self.vendor == VariableVendor::GLOBAL_VARIABLE {
+            write!(f, "GLOBAL_VARIABLE")?;
+        } else {
+            write!(f, "{}", self.vendor.0)?;
+        }
+
+        write!(f, " }}")
+    }
+}
+
+/// Authentication header of a variable written with
+/// [`set_authenticated_variable`] (`EFI_VARIABLE_AUTHENTICATION_2`).
+///
+/// The header contains a time stamp and a certificate, usually a PKCS#7
+/// `SignedData` structure that signs [`signed_data`].
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::runtime::{self, Daylight, Time, TimeParams, VariableAttributes};
+/// use uefi::runtime::{VariableAuthentication, VariableVendor};
+/// use uefi::cstr16;
+///
+/// # fn sign(data: &[u8]) -> Vec<u8> { unimplemented!() }
+/// # fn test(esl: &[u8]) -> uefi::Result {
+/// let now = runtime::get_time()?;
+/// // Only the date and time are used, all other fields must be zero.
+/// let time_stamp = Time::new(TimeParams {
+///     year: now.year(),
+///     month: now.month(),
+///     day: now.day(),
+///     hour: now.hour(),
+///     minute: now.minute(),
+///     second: now.second(),
+///     nanosecond: 0,
+///     time_zone: Some(0),
+///     daylight: Daylight::empty(),
+/// })
+/// .unwrap();
+///
+/// let name = cstr16!("db");
+/// let vendor = &VariableVendor::IMAGE_SECURITY_DATABASE;
+/// let attributes = VariableAttributes::NON_VOLATILE
+///     | VariableAttributes::BOOTSERVICE_ACCESS
+///     | VariableAttributes::RUNTIME_ACCESS
+///     | VariableAttributes::APPEND_WRITE;
+///
+/// // Sign the data with a key in KEK.
+/// let signed_data =
+///     VariableAuthentication::signed_data(name, vendor, attributes, &time_stamp, esl);
+/// let auth = VariableAuthentication::new(time_stamp, sign(&signed_data));
+/// runtime::set_authenticated_variable(name, vendor, attributes, &auth, esl)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`signed_data`]: Self::signed_data
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VariableAuthentication {
+    /// Time of the write. Only the date and time fields are used, the
+    /// other fields must be zero.
+    pub time_stamp: Time,
+
+    /// Type of the certificate data, usually
+    /// [`WinCertificateUefiGuid::CERT_TYPE_PKCS7`].
+    pub cert_type: Guid,
+
+    /// The certificate data.
+    pub cert_data: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl VariableAuthentication {
+    /// Size of the header, excluding the certificate data.
+    const HEADER_SIZE: usize = size_of::<uefi_raw::table::runtime::VariableAuthentication2>();
+
+    /// Size of the certificate header, excluding the certificate data.
+    const CERT_HEADER_SIZE: usize = size_of::<WinCertificateUefiGuid>();
+
+    /// Create an authentication header with a DER-encoded PKCS#7
+    /// `SignedData` structure, created over [`signed_data`].
+    ///
+    /// [`signed_data`]: Self::signed_data
+    #[must_use]
+    pub const fn new(time_stamp: Time, pkcs7_signed_data: Vec<u8>) -> Self {
+        Self {
+            time_stamp,
+            cert_type: WinCertificateUefiGuid::CERT_TYPE_PKCS7,
+            cert_data: pkcs7_signed_data,
+        }
+    }
+
+    /// Get the data that must be signed to write `data` to a variable with
+    /// [`set_authenticated_variable`] and the same arguments.
+    ///
+    /// This is the concatenation of `name` without the null terminator,
+    /// `vendor`, `attributes` with
+    /// [`VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS`] added,
+    /// `time_stamp` and `data`.
+    #[must_use]
+    pub fn signed_data(
+        name: &CStr16,
+        vendor: &VariableVendor,
+        attributes: VariableAttributes,
+        time_stamp: &Time,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let attributes = attributes | VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS;
+        let mut bytes = Vec::with_capacity(name.num_bytes() + 36 + data.len());
+        for c in name.to_u16_slice() {
+            bytes.extend_from_slice(&c.to_le_bytes());
+        }
+        bytes.extend_from_slice(&vendor.0.to_bytes());
+        bytes.extend_from_slice(&attributes.bits().to_le_bytes());
+        bytes.extend_from_slice(&time_stamp.to_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    /// Parse the authentication header at the start of the data of an
+    /// authenticated variable write, and return it with the variable data
+    /// that follows it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VariableAuthenticationError`] if `bytes` does not start
+    /// with a valid header.
+    pub fn parse(bytes: &[u8]) -> core::result::Result<(Self, &[u8]), VariableAuthenticationError> {
+        if bytes.len() < Self::HEADER_SIZE {
+            return Err(VariableAuthenticationError::TooShort);
+        }
+        let (time_stamp, cert) = bytes.split_at(size_of::<Time>());
+        let time_stamp = Time::try_from(time_stamp)
+            .map_err(|_| VariableAuthenticationError::InvalidTimeStamp)?;
+
+        let length = u32::from_le_bytes(cert[0..4].try_into().unwrap()) as usize;
+        let revision = u16::from_le_bytes(cert[4..6].try_into().unwrap());
+        let certificate_type = u16::from_le_bytes(cert[6..8].try_into().unwrap());
+        if revision != WinCertificate::REVISION
+            || WinCertificateType(certificate_type) != WinCertificateType::EFI_GUID
+            || length < Self::CERT_HEADER_SIZE
+        {
+            return Err(VariableAuthenticationError::InvalidCertificate);
+        }
+        if length > cert.len() {
+            return Err(VariableAuthenticationError::TooShort);
+        }
+        let cert_type = Guid::from_bytes(cert[8..24].try_into().unwrap());
+        let (cert_data, data) =
+            cert[Self::CERT_HEADER_SIZE..].split_at(length - Self::CERT_HEADER_SIZE);
+
+        let auth = Self {
+            time_stamp,
+            cert_type,
+            cert_data: cert_data.to_vec(),
+        };
+        Ok((auth, data))
+    }
+
+    /// Serialize the authentication header, including the certificate
+    /// data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the certificate data is larger than 4 GiB.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let length = u32::try_from(Self::CERT_HEADER_SIZE + self.cert_data.len())
+            .expect("certificate data is too large");
+        let mut bytes = Vec::with_capacity(Self::HEADER_SIZE + self.cert_data.len());
+        bytes.extend_from_slice(&self.time_stamp.to_bytes());
+        bytes.extend_from_slice(&length.to_le_bytes());
+        bytes.extend_from_slice(&WinCertificate::REVISION.to_le_bytes());
+        bytes.extend_from_slice(&WinCertificateType::EFI_GUID.0.to_le_bytes());
+        bytes.extend_from_slice(&self.cert_type.to_bytes());
+        bytes.extend_from_slice(&self.cert_data);
+        bytes
+    }
+}
+
+/// Error returned by [`VariableAuthentication::parse`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VariableAuthenticationError {
+    /// The data ends before the end of the header.
+    TooShort,
+
+    /// The time stamp is not a valid [`Time`].
+    InvalidTimeStamp,
+
+    /// The certificate is not a `WIN_CERTIFICATE_UEFI_GUID`.
+    InvalidCertificate,
+}
+
+#[cfg(feature = "alloc")]
+impl Display for VariableAuthenticationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::TooShort => "the authentication header is too short",
+            Self::InvalidTimeStamp => "the time stamp is invalid",
+            Self::InvalidCertificate => "the certificate is not a WIN_CERTIFICATE_UEFI_GUID",
+        };
+        f.write_str(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for VariableAuthenticationError {}
+
+/// Information about UEFI variable storage space returned by
+/// [`query_variable_info`]. Note that the data here is
+/// limited to a specific type of variable (as specified by the
+/// `attributes` argument to `query_variable_info`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VariableStorageInfo {
+    /// Maximum size in bytes of the storage space available for
+    /// variables of the specified type.
+    pub maximum_variable_storage_size: u64,
+
+    /// Remaining size in bytes of the storage space available for
+    /// variables of the specified type.
+    pub remaining_variable_storage_size: u64,
+
+    /// Maximum size of an individual variable of the specified type.
+    pub maximum_variable_size: u64,
+}
+
+/// Information about UEFI variable storage space returned by
+/// [`query_capsule_capabilities`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CapsuleInfo {
+    /// The maximum size in bytes that [`update_capsule`]
+    /// can support as input. Note that the size of an update capsule is composed of
+    /// all [`CapsuleHeader`]s and [CapsuleBlockDescriptor]s.
+    pub maximum_capsule_size: u64,
+
+    /// The type of reset required for the capsule update.
+    pub reset_type: ResetType,
+}
+
+/// State of the wakeup alarm clock returned by [`get_wakeup_time`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WakeupTime {
+    /// Whether the alarm is enabled.
+    pub enabled: bool,
+
+    /// Whether the alarm has fired and is pending.
+    pub pending: bool,
+
+    /// The time of the alarm.
+    pub time: Time,
+}
+
+/// A 64-bit monotonic counter that can be used after exiting boot services.
+///
+/// The high 32 bits come from [`get_next_high_monotonic_count`], and the low
+/// 32 bits are counted by this type. The values are greater than all values
+/// returned by [`boot::get_next_monotonic_count`] before the counter was
+/// created.
+///
+/// [`boot::get_next_monotonic_count`]: crate::boot::get_next_monotonic_count
+#[derive(Debug)]
+pub struct MonotonicCounter {
+    high: u32,
+    low: u32,
+}
+
+impl MonotonicCounter {
+    /// Create a counter with the next high 32 bits of the platform's
+    /// monotonic counter.
+    ///
+    /// # Errors
+    ///
+    /// See [`get_next_high_monotonic_count`].
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            high: get_next_high_monotonic_count()?,
+            low: 0,
+        })
+    }
+
+    /// Get the next value of the counter. When the low 32 bits overflow,
+    /// the next high 32 bits are fetched from the firmware.
+    ///
+    /// # Errors
+    ///
+    /// See [`get_next_high_monotonic_count`].
+    pub fn next_count(&mut self) -> Result<u64> {
+        if self.low == u32::MAX {
+            self.high = get_next_high_monotonic_count()?;
+            self.low = 0;
+        } else {
+            self.low += 1;
+        }
+        Ok((u64::from(self.high) << 32) | u64::from(self.low))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::cstr16;
+
+    fn time_stamp() -> Time {
+        Time::new(TimeParams {
+            year: 2024,
+            month: 5,
+            day: 6,
+            hour: 7,
+            minute: 8,
+            second: 9,
+            nanosecond: 0,
+            time_zone: Some(0),
+            daylight: Daylight::empty(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_time_bytes() {
+        let time = time_stamp();
+        let bytes = time.to_bytes();
+        assert_eq!(
+            bytes,
+            [0xe8, 0x07, 5, 6, 7, 8, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(Time::try_from(&bytes[..]).unwrap(), time);
+    }
+
+    #[test]
+    fn test_variable_authentication() {
+        let auth = VariableAuthentication::new(time_stamp(), vec![0x30, 0x82]);
+        let mut bytes = auth.to_bytes();
+        assert_eq!(bytes.len(), 42);
+        // `WIN_CERTIFICATE` header.
+        assert_eq!(bytes[16..24], [26, 0, 0, 0, 0x00, 0x02, 0xf1, 0x0e]);
+        assert_eq!(
+            bytes[24..40],
+            WinCertificateUefiGuid::CERT_TYPE_PKCS7.to_bytes()
+        );
+
+        bytes.extend_from_slice(b"data");
+        let (parsed, data) = VariableAuthentication::parse(&bytes).unwrap();
+        assert_eq!(parsed, auth);
+        assert_eq!(data, b"data");
+
+        assert_eq!(
+            VariableAuthentication::parse(&bytes[..39]),
+            Err(VariableAuthenticationError::TooShort)
+        );
+        assert_eq!(
+            VariableAuthentication::parse(&bytes[..41]),
+            Err(VariableAuthenticationError::TooShort)
+        );
+        bytes[22] = 0x02;
+        assert_eq!(
+            VariableAuthentication::parse(&bytes),
+            Err(VariableAuthenticationError::InvalidCertificate)
+        );
+    }
+
+    #[test]
+    fn test_variable_authentication_signed_data() {
+        let time_stamp = time_stamp();
+        let data = VariableAuthentication::signed_data(
+            cstr16!("db"),
+            &VariableVendor::IMAGE_SECURITY_DATABASE,
+            VariableAttributes::NON_VOLATILE | VariableAttributes::APPEND_WRITE,
+            &time_stamp,
+            b"esl",
+        );
+
+        let mut expected = vec![b'd', 0, b'b', 0];
+        expected.extend_from_slice(&VariableVendor::IMAGE_SECURITY_DATABASE.0.to_bytes());
+        expected.extend_from_slice(&[0x61, 0, 0, 0]);
+        expected.extend_from_slice(&time_stamp.to_bytes());
+        expected.extend_from_slice(b"esl");
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_build_virtual_map() {
+        use crate::mem::memory_map::{MemoryMapMeta, MemoryMapRef, MemoryType};
+
+        // The firmware's descriptor size is larger than `MemoryDescriptor`.
+        const DESC_SIZE: usize = size_of::<MemoryDescriptor>() + 8;
+        let descriptors = [
+            (MemoryType::RUNTIME_SERVICES_CODE, 0x1000, 2),
+            (MemoryType::CONVENTIONAL, 0x3000, 2),
+            (MemoryType::RUNTIME_SERVICES_DATA, 0x5000, 4),
+        ];
+        let mut memory = [0u64; 3 * DESC_SIZE / 8];
+        let len = size_of_val(&memory);
+        let buf = unsafe { core::slice::from_raw_parts_mut(memory.as_mut_ptr().cast::<u8>(), len) };
+        for (i, (ty, phys_start, page_count)) in descriptors.into_iter().enumerate() {
+            let att = if ty == MemoryType::CONVENTIONAL {
+                MemoryAttribute::WRITE_BACK
+            } else {
+                MemoryAttribute::WRITE_BACK | MemoryAttribute::RUNTIME
+            };
+            let desc = MemoryDescriptor {
+                ty,
+                phys_start,
+                virt_start: 0,
+                page_count,
+                att,
+            };
+            unsafe {
+                buf.as_mut_ptr()
+                    .add(i * DESC_SIZE)
+                    .cast::<MemoryDescriptor>()
+                    .write(desc)
+            };
+        }
+        let meta = MemoryMapMeta {
+            map_size: len,
+            desc_size: DESC_SIZE,
+            map_key: Default::default(),
+            desc_version: MemoryDescriptor::VERSION,
+        };
+        let map = MemoryMapRef::new(buf, meta).unwrap();
+
+        // Pack the regions at consecutive virtual addresses.
+        let mut next = 0x8000_0000;
+        let mut pack = |phys_addr| {
+            let virt_addr = next;
+            next += if phys_addr == 0x1000 { 0x2000 } else { 0x4000 };
+            virt_addr
+        };
+        let mut buffer = [MemoryDescriptor::default(); 4];
+        let (virtual_map, new_system_table) =
+            build_virtual_map(&map, VirtualMapping::Custom(&mut pack), 0x6010, &mut buffer)
+                .unwrap();
+        assert_eq!(virtual_map.len(), 2);
+        assert_eq!(
+            (virtual_map[0].phys_start, virtual_map[0].virt_start),
+            (0x1000, 0x8000_0000)
+        );
+        assert_eq!(
+            (virtual_map[1].phys_start, virtual_map[1].virt_start),
+            (0x5000, 0x8000_2000)
+        );
+        assert_eq!(new_system_table, 0x8000_3010);
+
+        // The memory map is not modified.
+        assert_eq!(map.len(), 3);
+        assert!(map.entries().all(|desc| desc.virt_start == 0));
+
+        // The system table is not in runtime memory.
+        let mapping = VirtualMapping::Offset(0xffff_8000_0000_0000);
+        assert_eq!(
+            build_virtual_map(&map, mapping, 0x3000, &mut buffer).unwrap_err(),
+            Status::NOT_FOUND.into()
+        );
+
+        // The buffer is too small.
+        let mapping = VirtualMapping::Offset(0);
+        assert_eq!(
+            build_virtual_map(&map, mapping, 0x1000, &mut buffer[..1]).unwrap_err(),
+            Status::BUFFER_TOO_SMALL.into()
+        );
+    }
+}
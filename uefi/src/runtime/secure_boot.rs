@@ -0,0 +1,677 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Secure Boot state and signature databases.
+//!
+//! The Secure Boot state is reported by the `SecureBoot`, `SetupMode`,
+//! `AuditMode` and `DeployedMode` variables, see [`mode`].
+//!
+//! The keys and hashes that Secure Boot uses to verify images are stored in
+//! signature databases, see [`Database`]. With the `alloc` feature, they can
+//! be read with `get_database`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::runtime::secure_boot::{self, SecureBootMode};
+//!
+//! # fn test() -> uefi::Result {
+//! if secure_boot::is_enabled()? && secure_boot::mode()? == SecureBootMode::User {
+//!     // Images are verified with the enrolled keys.
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::VariableVendor;
+use crate::{CStr16, Result, Status, cstr16, runtime};
+
+#[cfg(feature = "alloc")]
+use {
+    super::Time,
+    crate::Guid,
+    alloc::vec::Vec,
+    core::fmt::{self, Display, Formatter},
+    core::{mem, slice},
+    uefi_raw::signature::CertX509Sha256,
+};
+
+pub use uefi_raw::signature::SignatureType;
+
+/// Read a variable that must be a single byte of `0` or `1`. Returns `false`
+/// if the variable does not exist.
+pub(crate) fn get_bool(name: &CStr16) -> Result<bool> {
+    match runtime::get_global(name, runtime::get_variable_array)? {
+        None | Some([0]) => Ok(false),
+        Some([1]) => Ok(true),
+        Some(_) => Err(Status::VOLUME_CORRUPTED.into()),
+    }
+}
+
+/// Whether Secure Boot is enabled, from the `SecureBoot` variable.
+///
+/// Returns `false` if the firmware does not support Secure Boot.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not `0` or `1`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn is_enabled() -> Result<bool> {
+    get_bool(cstr16!("SecureBoot"))
+}
+
+/// Whether the platform is in setup mode, from the `SetupMode` variable.
+///
+/// In setup mode, no platform key is enrolled and the signature databases
+/// can be modified without authentication.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not `0` or `1`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn is_setup_mode() -> Result<bool> {
+    get_bool(cstr16!("SetupMode"))
+}
+
+/// Whether the platform is in audit mode, from the `AuditMode` variable.
+///
+/// Returns `false` if the firmware does not support audit mode.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not `0` or `1`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn is_audit_mode() -> Result<bool> {
+    get_bool(cstr16!("AuditMode"))
+}
+
+/// Whether the platform is in deployed mode, from the `DeployedMode`
+/// variable.
+///
+/// Returns `false` if the firmware does not support deployed mode.
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not `0` or `1`.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn is_deployed_mode() -> Result<bool> {
+    get_bool(cstr16!("DeployedMode"))
+}
+
+/// Secure Boot mode of the platform, see [`mode`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SecureBootMode {
+    /// No platform key is enrolled.
+    Setup,
+
+    /// A platform key is enrolled, and images are verified.
+    User,
+
+    /// No platform key is enrolled, and the results of image verification
+    /// are logged but not enforced.
+    Audit,
+
+    /// A platform key is enrolled, and the mode can't be changed without
+    /// a platform specific method.
+    Deployed,
+}
+
+/// Get the Secure Boot mode of the platform from the `SetupMode`,
+/// `AuditMode` and `DeployedMode` variables.
+///
+/// Note that images are only verified if Secure Boot [`is_enabled`].
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: a variable is not `0` or `1`, or the
+///   combination of variables is not valid.
+/// * See [`runtime::get_variable`] for other errors.
+pub fn mode() -> Result<SecureBootMode> {
+    match (is_setup_mode()?, is_audit_mode()?, is_deployed_mode()?) {
+        (true, false, false) => Ok(SecureBootMode::Setup),
+        (false, false, false) => Ok(SecureBootMode::User),
+        (true, true, false) => Ok(SecureBootMode::Audit),
+        (false, false, true) => Ok(SecureBootMode::Deployed),
+        _ => Err(Status::VOLUME_CORRUPTED.into()),
+    }
+}
+
+/// A signature database variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Database {
+    /// `PK`, the platform key.
+    Pk,
+
+    /// `KEK`, the keys that may update `db` and `dbx`.
+    Kek,
+
+    /// `db`, the keys and hashes of allowed images.
+    Db,
+
+    /// `dbx`, the keys and hashes of revoked images.
+    Dbx,
+
+    /// `dbt`, the keys used to verify timestamps.
+    Dbt,
+
+    /// `dbr`, the keys used to verify OS recovery images.
+    Dbr,
+}
+
+impl Database {
+    /// Name of the variable.
+    #[must_use]
+    pub const fn name(self) -> &'static CStr16 {
+        match self {
+            Self::Pk => cstr16!("PK"),
+            Self::Kek => cstr16!("KEK"),
+            Self::Db => cstr16!("db"),
+            Self::Dbx => cstr16!("dbx"),
+            Self::Dbt => cstr16!("dbt"),
+            Self::Dbr => cstr16!("dbr"),
+        }
+    }
+
+    /// Vendor of the variable.
+    #[must_use]
+    pub const fn vendor(self) -> &'static VariableVendor {
+        match self {
+            Self::Pk | Self::Kek => &VariableVendor::GLOBAL_VARIABLE,
+            Self::Db | Self::Dbx | Self::Dbt | Self::Dbr => {
+                &VariableVendor::IMAGE_SECURITY_DATABASE
+            }
+        }
+    }
+}
+
+/// Read a signature database. Returns an empty database if the variable
+/// does not exist.
+///
+/// # Example
+///
+/// ```no_run
+/// use uefi::runtime::secure_boot::{self, Database};
+///
+/// # fn test(image_hash: &[u8; 32]) -> uefi::Result {
+/// let dbx = secure_boot::get_database(Database::Dbx)?;
+/// if dbx.contains_sha256(image_hash) {
+///     // The image is revoked.
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// * [`Status::VOLUME_CORRUPTED`]: the variable is not a valid signature
+///   database.
+/// * See [`runtime::get_variable`] for other errors.
+#[cfg(feature = "alloc")]
+pub fn get_database(database: Database) -> Result<SignatureDatabase> {
+    match runtime::get_variable_boxed(database.name(), database.vendor()) {
+        Ok((data, _)) => {
+            SignatureDatabase::parse(&data).map_err(|_| Status::VOLUME_CORRUPTED.into())
+        }
+        Err(err) if err.status() == Status::NOT_FOUND => Ok(SignatureDatabase::default()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Error returned when parsing or serializing a [`SignatureDatabase`]
+/// fails.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureListError {
+    /// The data ends before the end of a signature list.
+    TooShort,
+
+    /// The sizes in a signature list header are not consistent, or the
+    /// signature size is not valid for the signature type.
+    InvalidSize,
+
+    /// A [`Signature`] does not match the type of its list.
+    MismatchedType,
+
+    /// The signatures of a list do not all have the same size.
+    MismatchedSize,
+}
+
+#[cfg(feature = "alloc")]
+impl Display for SignatureListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::TooShort => "the signature list data is too short",
+            Self::InvalidSize => "the signature list sizes are invalid",
+            Self::MismatchedType => "a signature does not match the type of its list",
+            Self::MismatchedSize => "the signatures of a list have different sizes",
+        };
+        f.write_str(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for SignatureListError {}
+
+/// Contents of a signature database variable, a sequence of
+/// [`SignatureList`]s.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SignatureDatabase {
+    /// The signature lists in the database.
+    pub lists: Vec<SignatureList>,
+}
+
+#[cfg(feature = "alloc")]
+impl SignatureDatabase {
+    /// Parse a signature database from the data of its variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SignatureListError`] if `bytes` is not a valid sequence
+    /// of signature lists.
+    pub fn parse(mut bytes: &[u8]) -> core::result::Result<Self, SignatureListError> {
+        let mut lists = Vec::new();
+        while !bytes.is_empty() {
+            let (list, rest) = SignatureList::parse(bytes)?;
+            lists.push(list);
+            bytes = rest;
+        }
+        Ok(Self { lists })
+    }
+
+    /// Serialize the signature database to the data of its variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SignatureListError`] if a list is not valid, see
+    /// [`SignatureList::to_bytes`].
+    pub fn to_bytes(&self) -> core::result::Result<Vec<u8>, SignatureListError> {
+        let mut bytes = Vec::new();
+        for list in &self.lists {
+            bytes.extend(list.to_bytes()?);
+        }
+        Ok(bytes)
+    }
+
+    /// Iterator over the signatures of all lists.
+    pub fn signatures(&self) -> impl Iterator<Item = &SignatureData> {
+        self.lists.iter().flat_map(|list| &list.signatures)
+    }
+
+    /// Whether the database contains the SHA-256 hash of an image.
+    #[must_use]
+    pub fn contains_sha256(&self, hash: &[u8; 32]) -> bool {
+        self.signatures()
+            .any(|data| data.signature == Signature::Sha256(*hash))
+    }
+}
+
+/// A list of signatures of the same type (`EFI_SIGNATURE_LIST`).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignatureList {
+    /// Type of the signatures.
+    pub signature_type: SignatureType,
+
+    /// Header data, whose meaning depends on the signature type. None of
+    /// the types defined by the UEFI specification have header data.
+    pub header: Vec<u8>,
+
+    /// The signatures in the list.
+    pub signatures: Vec<SignatureData>,
+}
+
+#[cfg(feature = "alloc")]
+impl SignatureList {
+    /// Size of the list header.
+    const HEADER_SIZE: usize = mem::size_of::<uefi_raw::signature::SignatureList>();
+
+    /// Size of the owner of a signature.
+    const OWNER_SIZE: usize = mem::size_of::<Guid>();
+
+    /// Create an empty list of signatures of type `signature_type`.
+    #[must_use]
+    pub const fn new(signature_type: SignatureType) -> Self {
+        Self {
+            signature_type,
+            header: Vec::new(),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Parse the signature list at the start of `bytes`, and return it with
+    /// the remaining bytes.
+    fn parse(bytes: &[u8]) -> core::result::Result<(Self, &[u8]), SignatureListError> {
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize
+        };
+
+        if bytes.len() < Self::HEADER_SIZE {
+            return Err(SignatureListError::TooShort);
+        }
+        let signature_type = SignatureType(Guid::from_bytes(bytes[..16].try_into().unwrap()));
+        let list_size = read_u32(16);
+        let header_size = read_u32(20);
+        let signature_size = read_u32(24);
+
+        if list_size > bytes.len() {
+            return Err(SignatureListError::TooShort);
+        }
+        // Written so that a corrupt header size can't overflow on 32-bit
+        // targets.
+        if list_size < Self::HEADER_SIZE || header_size > list_size - Self::HEADER_SIZE {
+            return Err(SignatureListError::InvalidSize);
+        }
+        let (list, rest) = bytes.split_at(list_size);
+        let (header, signatures) = list[Self::HEADER_SIZE..].split_at(header_size);
+        if signature_size < Self::OWNER_SIZE || signatures.len() % signature_size != 0 {
+            return Err(SignatureListError::InvalidSize);
+        }
+
+        let signatures = signatures
+            .chunks_exact(signature_size)
+            .map(|data| {
+                let (owner, data) = data.split_at(Self::OWNER_SIZE);
+                Ok(SignatureData {
+                    owner: Guid::from_bytes(owner.try_into().unwrap()),
+                    signature: Signature::parse(signature_type, data)?,
+                })
+            })
+            .collect::<core::result::Result<_, _>>()?;
+
+        let list = Self {
+            signature_type,
+            header: header.to_vec(),
+            signatures,
+        };
+        Ok((list, rest))
+    }
+
+    /// Serialize the signature list.
+    ///
+    /// # Errors
+    ///
+    /// * [`SignatureListError::MismatchedType`]: a [`Signature`] does not
+    ///   match [`signature_type`], for example a [`Signature::Sha256`] in a
+    ///   list of [`SignatureType::X509`].
+    /// * [`SignatureListError::MismatchedSize`]: the signatures do not all
+    ///   have the same size.
+    ///
+    /// [`signature_type`]: Self::signature_type
+    pub fn to_bytes(&self) -> core::result::Result<Vec<u8>, SignatureListError> {
+        let mut data_size = Signature::size_for_type(self.signature_type);
+        for data in &self.signatures {
+            let type_matches = match data.signature.signature_type() {
+                Some(ty) => ty == self.signature_type,
+                None => !Signature::has_variant(self.signature_type),
+            };
+            if !type_matches {
+                return Err(SignatureListError::MismatchedType);
+            }
+            let size = data.signature.size();
+            if *data_size.get_or_insert(size) != size {
+                return Err(SignatureListError::MismatchedSize);
+            }
+        }
+
+        let signature_size = Self::OWNER_SIZE + data_size.unwrap_or(0);
+        let list_size =
+            Self::HEADER_SIZE + self.header.len() + signature_size * self.signatures.len();
+        let to_u32 = |n: usize| u32::try_from(n).map_err(|_| SignatureListError::InvalidSize);
+
+        let mut bytes = Vec::with_capacity(list_size);
+        bytes.extend_from_slice(&self.signature_type.0.to_bytes());
+        bytes.extend_from_slice(&to_u32(list_size)?.to_le_bytes());
+        bytes.extend_from_slice(&to_u32(self.header.len())?.to_le_bytes());
+        bytes.extend_from_slice(&to_u32(signature_size)?.to_le_bytes());
+        bytes.extend_from_slice(&self.header);
+        for data in &self.signatures {
+            bytes.extend_from_slice(&data.owner.to_bytes());
+            data.signature.write_to(&mut bytes);
+        }
+        Ok(bytes)
+    }
+}
+
+/// A signature and its owner (`EFI_SIGNATURE_DATA`).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignatureData {
+    /// Identifies the agent that added the signature.
+    pub owner: Guid,
+
+    /// The signature.
+    pub signature: Signature,
+}
+
+/// A signature in a [`SignatureList`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Signature {
+    /// [`SignatureType::SHA256`]: SHA-256 hash of an image.
+    Sha256([u8; 32]),
+
+    /// [`SignatureType::X509`]: DER-encoded X.509 certificate.
+    X509(Vec<u8>),
+
+    /// [`SignatureType::X509_SHA256`]: hash of a revoked certificate.
+    X509Sha256 {
+        /// SHA-256 hash of the to-be-signed part of the certificate.
+        to_be_signed_hash: [u8; 32],
+
+        /// Time of the revocation of the certificate. The certificate is
+        /// always revoked if all fields are zero.
+        time_of_revocation: Time,
+    },
+
+    /// Signature of a type that has no specific variant, such as
+    /// [`SignatureType::SHA384`].
+    Other(Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+impl Signature {
+    /// Parse signature data of type `signature_type`, excluding the owner.
+    fn parse(
+        signature_type: SignatureType,
+        data: &[u8],
+    ) -> core::result::Result<Self, SignatureListError> {
+        if let Some(size) = Self::size_for_type(signature_type) {
+            if data.len() != size {
+                return Err(SignatureListError::InvalidSize);
+            }
+        }
+        Ok(match signature_type {
+            SignatureType::SHA256 => Self::Sha256(data.try_into().unwrap()),
+            SignatureType::X509 => Self::X509(data.to_vec()),
+            SignatureType::X509_SHA256 => {
+                // SAFETY: the size of `data` is checked above, and any bit
+                // pattern is a valid `CertX509Sha256`.
+                let cert = unsafe { data.as_ptr().cast::<CertX509Sha256>().read_unaligned() };
+                Self::X509Sha256 {
+                    to_be_signed_hash: cert.to_be_signed_hash,
+                    time_of_revocation: Time(cert.time_of_revocation),
+                }
+            }
+            _ => Self::Other(data.to_vec()),
+        })
+    }
+
+    /// Size of the signatures of `signature_type` that have a fixed size
+    /// and are parsed into a specific variant.
+    const fn size_for_type(signature_type: SignatureType) -> Option<usize> {
+        match signature_type {
+            SignatureType::SHA256 => Some(32),
+            SignatureType::X509_SHA256 => Some(mem::size_of::<CertX509Sha256>()),
+            _ => None,
+        }
+    }
+
+    /// Type of the signature, or `None` for [`Signature::Other`].
+    #[must_use]
+    pub const fn signature_type(&self) -> Option<SignatureType> {
+        match self {
+            Self::Sha256(_) => Some(SignatureType::SHA256),
+            Self::X509(_) => Some(SignatureType::X509),
+            Self::X509Sha256 { .. } => Some(SignatureType::X509_SHA256),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Whether signatures of `signature_type` are parsed into a specific
+    /// variant instead of [`Signature::Other`].
+    const fn has_variant(signature_type: SignatureType) -> bool {
+        matches!(
+            signature_type,
+            SignatureType::SHA256 | SignatureType::X509 | SignatureType::X509_SHA256
+        )
+    }
+
+    /// Size of the signature data, excluding the owner.
+    fn size(&self) -> usize {
+        match self {
+            Self::Sha256(hash) => hash.len(),
+            Self::X509(data) | Self::Other(data) => data.len(),
+            Self::X509Sha256 { .. } => mem::size_of::<CertX509Sha256>(),
+        }
+    }
+
+    /// Append the signature data, excluding the owner, to `bytes`.
+    fn write_to(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Sha256(hash) => bytes.extend_from_slice(hash),
+            Self::X509(data) | Self::Other(data) => bytes.extend_from_slice(data),
+            Self::X509Sha256 {
+                to_be_signed_hash,
+                time_of_revocation,
+            } => {
+                let cert = CertX509Sha256 {
+                    to_be_signed_hash: *to_be_signed_hash,
+                    time_of_revocation: time_of_revocation.0,
+                };
+                let ptr: *const CertX509Sha256 = &cert;
+                // SAFETY: `CertX509Sha256` is packed and has no
+                // uninitialized padding.
+                let data = unsafe {
+                    slice::from_raw_parts(ptr.cast::<u8>(), mem::size_of::<CertX509Sha256>())
+                };
+                bytes.extend_from_slice(data);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::guid;
+    use alloc::vec;
+    use uefi_raw::time::Daylight;
+
+    const OWNER: Guid = guid!("77fa9abd-0359-4d32-bd60-28f4e78f784b");
+
+    #[test]
+    fn test_signature_database() {
+        let mut time = uefi_raw::time::Time::invalid();
+        time.year = 2024;
+        time.month = 2;
+        time.day = 3;
+        time.daylight = Daylight::IN_DAYLIGHT;
+
+        let mut sha256 = SignatureList::new(SignatureType::SHA256);
+        for i in 0..2 {
+            sha256.signatures.push(SignatureData {
+                owner: OWNER,
+                signature: Signature::Sha256([i; 32]),
+            });
+        }
+        let mut x509 = SignatureList::new(SignatureType::X509);
+        x509.signatures.push(SignatureData {
+            owner: OWNER,
+            signature: Signature::X509(vec![0x30, 0x82, 0x01]),
+        });
+        let mut x509_sha256 = SignatureList::new(SignatureType::X509_SHA256);
+        x509_sha256.signatures.push(SignatureData {
+            owner: OWNER,
+            signature: Signature::X509Sha256 {
+                to_be_signed_hash: [0xaa; 32],
+                time_of_revocation: Time(time),
+            },
+        });
+        let mut other = SignatureList::new(SignatureType::SHA384);
+        other.header = vec![1, 2];
+        other.signatures.push(SignatureData {
+            owner: OWNER,
+            signature: Signature::Other(vec![0xbb; 48]),
+        });
+
+        let db = SignatureDatabase {
+            lists: vec![sha256, x509, x509_sha256, other],
+        };
+        let bytes = db.to_bytes().unwrap();
+        assert_eq!(
+            bytes.len(),
+            (28 + 2 * 48) + (28 + 19) + (28 + 64) + (28 + 2 + 64)
+        );
+        // Header of the first list.
+        assert_eq!(&bytes[..16], SignatureType::SHA256.0.to_bytes());
+        assert_eq!(bytes[16..28], [124, 0, 0, 0, 0, 0, 0, 0, 48, 0, 0, 0]);
+
+        let parsed = SignatureDatabase::parse(&bytes).unwrap();
+        assert_eq!(parsed, db);
+        assert!(parsed.contains_sha256(&[1; 32]));
+        assert!(!parsed.contains_sha256(&[2; 32]));
+        assert_eq!(parsed.signatures().count(), 5);
+    }
+
+    #[test]
+    fn test_signature_database_errors() {
+        let mut list = SignatureList::new(SignatureType::SHA256);
+        list.signatures.push(SignatureData {
+            owner: OWNER,
+            signature: Signature::X509(vec![0; 32]),
+        });
+        assert_eq!(list.to_bytes(), Err(SignatureListError::MismatchedType));
+
+        let mut list = SignatureList::new(SignatureType::X509);
+        for len in [3, 4] {
+            list.signatures.push(SignatureData {
+                owner: OWNER,
+                signature: Signature::X509(vec![0; len]),
+            });
+        }
+        assert_eq!(list.to_bytes(), Err(SignatureListError::MismatchedSize));
+
+        list.signatures.pop();
+        let mut bytes = list.to_bytes().unwrap();
+        assert_eq!(
+            SignatureDatabase::parse(&bytes[..bytes.len() - 1]),
+            Err(SignatureListError::TooShort)
+        );
+        assert_eq!(
+            SignatureDatabase::parse(&bytes[..27]),
+            Err(SignatureListError::TooShort)
+        );
+
+        // A signature size that doesn't divide the list.
+        bytes[24] = 18;
+        assert_eq!(
+            SignatureDatabase::parse(&bytes),
+            Err(SignatureListError::InvalidSize)
+        );
+
+        // A list size that is smaller than the header.
+        bytes[16] = 0;
+        assert_eq!(
+            SignatureDatabase::parse(&bytes),
+            Err(SignatureListError::InvalidSize)
+        );
+
+        // A header size that is larger than the list.
+        let mut bytes = list.to_bytes().unwrap();
+        bytes[20..24].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            SignatureDatabase::parse(&bytes),
+            Err(SignatureListError::InvalidSize)
+        );
+    }
+}
@@ -0,0 +1,681 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Snapshots of the UEFI variable store.
+//!
+//! A [`VariableSnapshot`] records the name, vendor, attributes and data of
+//! variables. [`VariableSnapshot::capture`] reads all variables of the
+//! platform, and the snapshot can be compared with another one with
+//! [`VariableSnapshot::diff`], or written back with
+//! [`VariableSnapshot::restore`].
+//!
+//! # Binary format
+//!
+//! [`VariableSnapshot::to_bytes`] serializes a snapshot so that it can be
+//! stored in a file, for example on the EFI system partition. All integers
+//! are little-endian, and there is no padding. The snapshot starts with a
+//! header:
+//!
+//! | Offset | Size | Field                                  |
+//! |--------|------|----------------------------------------|
+//! | 0      | 8    | Magic, the ASCII string `UEFIVARS`     |
+//! | 8      | 4    | Format version, currently `1`          |
+//! | 12     | 4    | Number of variables                    |
+//!
+//! The variables follow. They may be in any order, but each combination of
+//! vendor and name may only appear once. [`VariableSnapshot::to_bytes`]
+//! writes them in the order of [`VariableSnapshot::variables`], so equal
+//! snapshots are serialized to equal bytes.
+//!
+//!
+//! | Offset | Size | Field                                          |
+//! |--------|------|------------------------------------------------|
+//! | 0      | 16   | Vendor GUID, in the UEFI byte order            |
+//! | 16     | 4    | [`VariableAttributes`]                         |
+//! | 20     | 4    | Size `N` of the name in bytes                  |
+//! | 24     | 4    | Size `D` of the data in bytes                  |
+//! | 28     | `N`  | Name, as a null-terminated UCS-2 string        |
+//! | 28+`N` | `D`  | Data                                           |
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::fs::FileSystem;
+//! use uefi::runtime::snapshot::{VariableChange, VariableSnapshot};
+//! use uefi::{boot, cstr16};
+//!
+//! # fn test() -> Result<(), Box<dyn core::error::Error>> {
+//! let esp = boot::get_image_file_system(boot::image_handle())?;
+//! let mut fs = FileSystem::new(esp);
+//!
+//! // Compare the variables with a snapshot saved earlier.
+//! let saved = VariableSnapshot::from_bytes(&fs.read(cstr16!("\\vars.bin"))?)?;
+//! let current = VariableSnapshot::capture()?;
+//! for change in saved.diff(&current) {
+//!     match change {
+//!         VariableChange::Added(var) => log::info!("added: {}", var.key),
+//!         VariableChange::Removed(var) => log::info!("removed: {}", var.key),
+//!         VariableChange::Modified { new, .. } => log::info!("modified: {}", new.key),
+//!     }
+//! }
+//!
+//! fs.write(cstr16!("\\vars.bin"), current.to_bytes())?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{VariableAttributes, VariableKey, VariableVendor};
+use crate::{CStr16, Guid, Result, Status, runtime};
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Display, Formatter};
+
+/// Magic at the start of a serialized snapshot.
+const MAGIC: [u8; 8] = *b"UEFIVARS";
+
+/// Current version of the binary format.
+const VERSION: u32 = 1;
+
+/// Size of the snapshot header.
+const HEADER_SIZE: usize = 16;
+
+/// Size of the fixed part of a serialized variable.
+const VARIABLE_HEADER_SIZE: usize = 28;
+
+/// Attributes of variables that can only be written with an authentication
+/// header.
+const AUTHENTICATED: VariableAttributes = VariableAttributes::AUTHENTICATED_WRITE_ACCESS
+    .union(VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS)
+    .union(VariableAttributes::ENHANCED_AUTHENTICATED_ACCESS);
+
+/// A variable recorded in a [`VariableSnapshot`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SnapshotVariable {
+    /// Name and vendor of the variable.
+    pub key: VariableKey,
+
+    /// Attributes of the variable.
+    pub attributes: VariableAttributes,
+
+    /// Contents of the variable.
+    pub data: Vec<u8>,
+}
+
+/// Snapshot of UEFI variables.
+///
+/// The variables are sorted by key, and each key appears at most once. See
+/// the [module documentation](self) for the binary format.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VariableSnapshot {
+    variables: Vec<SnapshotVariable>,
+}
+
+impl VariableSnapshot {
+    /// Create an empty snapshot.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            variables: Vec::new(),
+        }
+    }
+
+    /// Read all variables of the platform into a snapshot.
+    ///
+    /// Variables deleted while the snapshot is taken are not included.
+    ///
+    /// # Errors
+    ///
+    /// See [`runtime::variable_keys`] and [`runtime::get_variable_boxed`].
+    pub fn capture() -> Result<Self> {
+        let mut snapshot = Self::new();
+        for key in runtime::variable_keys() {
+            let key = key?;
+            match runtime::get_variable_boxed(&key.name, &key.vendor) {
+                Ok((data, attributes)) => {
+                    snapshot.insert(SnapshotVariable {
+                        key,
+                        attributes,
+                        data: data.into_vec(),
+                    });
+                }
+                Err(err) if err.status() == Status::NOT_FOUND => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Variables in the snapshot, sorted by key.
+    #[must_use]
+    pub fn variables(&self) -> &[SnapshotVariable] {
+        &self.variables
+    }
+
+    /// Number of variables in the snapshot.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    /// Whether the snapshot contains no variables.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    /// Get the variable with `key`.
+    #[must_use]
+    pub fn get(&self, key: &VariableKey) -> Option<&SnapshotVariable> {
+        let index = self.find(key).ok()?;
+        Some(&self.variables[index])
+    }
+
+    /// Add `variable` to the snapshot. If the snapshot already contains a
+    /// variable with the same key, it is replaced and returned.
+    pub fn insert(&mut self, variable: SnapshotVariable) -> Option<SnapshotVariable> {
+        match self.find(&variable.key) {
+            Ok(index) => Some(core::mem::replace(&mut self.variables[index], variable)),
+            Err(index) => {
+                self.variables.insert(index, variable);
+                None
+            }
+        }
+    }
+
+    /// Remove the variable with `key` from the snapshot.
+    pub fn remove(&mut self, key: &VariableKey) -> Option<SnapshotVariable> {
+        let index = self.find(key).ok()?;
+        Some(self.variables.remove(index))
+    }
+
+    fn find(&self, key: &VariableKey) -> core::result::Result<usize, usize> {
+        self.variables.binary_search_by(|var| var.key.cmp(key))
+    }
+
+    /// Serialize the snapshot to the binary format described in the
+    /// [module documentation](self).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `u32::MAX` variables, or if a variable
+    /// is larger than 4 GiB.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&u32_len(self.variables.len()).to_le_bytes());
+
+        for var in &self.variables {
+            let name = var.key.name.to_u16_slice_with_nul();
+            bytes.extend_from_slice(&var.key.vendor.0.to_bytes());
+            bytes.extend_from_slice(&var.attributes.bits().to_le_bytes());
+            bytes.extend_from_slice(&u32_len(size_of_val(name)).to_le_bytes());
+            bytes.extend_from_slice(&u32_len(var.data.len()).to_le_bytes());
+            for c in name {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+            bytes.extend_from_slice(&var.data);
+        }
+        bytes
+    }
+
+    /// Parse a snapshot from the binary format described in the
+    /// [module documentation](self).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SnapshotParseError`] if `bytes` is not a valid snapshot.
+    pub fn from_bytes(bytes: &[u8]) -> core::result::Result<Self, SnapshotParseError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(SnapshotParseError::InvalidMagic);
+        }
+        let version = reader.u32()?;
+        if version != VERSION {
+            return Err(SnapshotParseError::UnsupportedVersion(version));
+        }
+        let count = reader.u32()?;
+
+        // Do not trust `count` for the allocation size.
+        let max_count = bytes.len().saturating_sub(HEADER_SIZE) / VARIABLE_HEADER_SIZE;
+        let mut variables = Vec::with_capacity(max_count.min(count as usize));
+        for _ in 0..count {
+            let vendor = Guid::from_bytes(reader.take(16)?.try_into().unwrap());
+            let attributes = VariableAttributes::from_bits_retain(reader.u32()?);
+            let name_size = reader.u32()? as usize;
+            let data_size = reader.u32()? as usize;
+
+            let name = reader.take(name_size)?;
+            if name_size % 2 != 0 {
+                return Err(SnapshotParseError::InvalidName);
+            }
+            let name: Vec<u16> = name
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            let name = CStr16::from_u16_with_nul(&name)
+                .map_err(|_| SnapshotParseError::InvalidName)?
+                .to_owned();
+            let data = reader.take(data_size)?.to_vec();
+
+            let key = VariableKey {
+                vendor: VariableVendor(vendor),
+                name,
+            };
+            variables.push(SnapshotVariable {
+                key,
+                attributes,
+                data,
+            });
+        }
+
+        if !reader.0.is_empty() {
+            return Err(SnapshotParseError::TrailingData);
+        }
+
+        variables.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        if variables.windows(2).any(|pair| pair[0].key == pair[1].key) {
+            return Err(SnapshotParseError::DuplicateVariable);
+        }
+        Ok(Self { variables })
+    }
+
+    /// Get the changes from `self` to `other`.
+    ///
+    /// Variables only in `other` are [`VariableChange::Added`], variables
+    /// only in `self` are [`VariableChange::Removed`], and variables whose
+    /// attributes or data differ are [`VariableChange::Modified`]. The
+    /// changes are sorted by key.
+    #[must_use]
+    pub fn diff<'a>(&'a self, other: &'a Self) -> Vec<VariableChange<'a>> {
+        let mut changes = Vec::new();
+        let mut old = self.variables.iter().peekable();
+        let mut new = other.variables.iter().peekable();
+        loop {
+            let order = match (old.peek(), new.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(o), Some(n)) => o.key.cmp(&n.key),
+            };
+            match order {
+                Ordering::Less => changes.push(VariableChange::Removed(old.next().unwrap())),
+                Ordering::Greater => changes.push(VariableChange::Added(new.next().unwrap())),
+                Ordering::Equal => {
+                    let old = old.next().unwrap();
+                    let new = new.next().unwrap();
+                    if old != new {
+                        changes.push(VariableChange::Modified { old, new });
+                    }
+                }
+            }
+        }
+        changes
+    }
+
+    /// Write the variables of the snapshot back to the variable store.
+    ///
+    /// Variables whose current value and attributes already match the
+    /// snapshot are not written. Variables that are not in the snapshot are
+    /// left unchanged.
+    ///
+    /// Some variables cannot be restored, and are reported in
+    /// [`RestoreReport::skipped`]:
+    ///
+    /// * Authenticated variables, such as the Secure Boot key databases,
+    ///   since writing them requires a new signed authentication header.
+    /// * Volatile variables, which the firmware recreates on each boot.
+    ///   This includes the read-only global variables, such as
+    ///   `SecureBoot` or `BootCurrent`.
+    /// * Variables that the firmware refused to write.
+    #[must_use]
+    pub fn restore(&self) -> RestoreReport {
+        let mut report = RestoreReport::default();
+        for var in &self.variables {
+            let key = var.key.clone();
+            if var.attributes.intersects(AUTHENTICATED) {
+                report.skipped.push((key, RestoreSkipReason::Authenticated));
+                continue;
+            }
+            if !var.attributes.contains(VariableAttributes::NON_VOLATILE) {
+                report.skipped.push((key, RestoreSkipReason::Volatile));
+                continue;
+            }
+
+            match runtime::get_variable_boxed(&key.name, &key.vendor) {
+                Ok((data, attributes)) if attributes == var.attributes && *data == *var.data => {
+                    report.unchanged.push(key);
+                    continue;
+                }
+                Ok(_) => {}
+                Err(err) if err.status() == Status::NOT_FOUND => {}
+                Err(err) => {
+                    report
+                        .skipped
+                        .push((key, RestoreSkipReason::Error(err.status())));
+                    continue;
+                }
+            }
+
+            match runtime::set_variable(&key.name, &key.vendor, var.attributes, &var.data) {
+                Ok(()) => report.restored.push(key),
+                Err(err) if err.status() == Status::WRITE_PROTECTED => {
+                    report.skipped.push((key, RestoreSkipReason::ReadOnly));
+                }
+                Err(err) => {
+                    report
+                        .skipped
+                        .push((key, RestoreSkipReason::Error(err.status())));
+                }
+            }
+        }
+        report
+    }
+}
+
+/// Convert a length to `u32` for the binary format.
+fn u32_len(len: usize) -> u32 {
+    u32::try_from(len).expect("snapshot field is larger than 4 GiB")
+}
+
+/// Reads the fields of a serialized snapshot.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> core::result::Result<&'a [u8], SnapshotParseError> {
+        let (head, tail) = self
+            .0
+            .split_at_checked(len)
+            .ok_or(SnapshotParseError::TooShort)?;
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> core::result::Result<u32, SnapshotParseError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+/// Difference between two [`VariableSnapshot`]s, see
+/// [`VariableSnapshot::diff`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VariableChange<'a> {
+    /// The variable was added.
+    Added(&'a SnapshotVariable),
+
+    /// The variable was removed.
+    Removed(&'a SnapshotVariable),
+
+    /// The attributes or data of the variable changed.
+    Modified {
+        /// The variable in the older snapshot.
+        old: &'a SnapshotVariable,
+
+        /// The variable in the newer snapshot.
+        new: &'a SnapshotVariable,
+    },
+}
+
+impl VariableChange<'_> {
+    /// Key of the changed variable.
+    #[must_use]
+    pub const fn key(&self) -> &VariableKey {
+        match self {
+            Self::Added(var) | Self::Removed(var) | Self::Modified { new: var, .. } => &var.key,
+        }
+    }
+}
+
+/// Outcome of [`VariableSnapshot::restore`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RestoreReport {
+    /// Variables that were written.
+    pub restored: Vec<VariableKey>,
+
+    /// Variables that already had the value of the snapshot.
+    pub unchanged: Vec<VariableKey>,
+
+    /// Variables that could not be restored, with the reason.
+    pub skipped: Vec<(VariableKey, RestoreSkipReason)>,
+}
+
+impl RestoreReport {
+    /// Whether all variables of the snapshot were restored.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
+
+/// Reason why [`VariableSnapshot::restore`] did not restore a variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum RestoreSkipReason {
+    /// The variable is authenticated.
+    Authenticated,
+
+    /// The variable is volatile.
+    Volatile,
+
+    /// The firmware returned [`Status::WRITE_PROTECTED`].
+    ReadOnly,
+
+    /// Reading or writing the variable failed with this status.
+    Error(Status),
+}
+
+/// Error returned by [`VariableSnapshot::from_bytes`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SnapshotParseError {
+    /// The data does not start with the snapshot magic.
+    InvalidMagic,
+
+    /// The format version is not supported.
+    UnsupportedVersion(u32),
+
+    /// The data ends before the end of the snapshot.
+    TooShort,
+
+    /// A variable name is not a null-terminated UCS-2 string.
+    InvalidName,
+
+    /// A variable appears twice.
+    DuplicateVariable,
+
+    /// There is data after the last variable.
+    TrailingData,
+}
+
+impl Display for SnapshotParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => f.write_str("the data is not a variable snapshot"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported variable snapshot version {version}")
+            }
+            Self::TooShort => f.write_str("the snapshot data is too short"),
+            Self::InvalidName => f.write_str("a variable name is not a valid UCS-2 string"),
+            Self::DuplicateVariable => f.write_str("a variable appears twice in the snapshot"),
+            Self::TrailingData => f.write_str("the snapshot has trailing data"),
+        }
+    }
+}
+
+impl core::error::Error for SnapshotParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cstr16, guid};
+    use alloc::vec;
+
+    fn var(name: &CStr16, attributes: VariableAttributes, data: &[u8]) -> SnapshotVariable {
+        SnapshotVariable {
+            key: VariableKey {
+                vendor: VariableVendor(guid!("01234567-89ab-cdef-0123-456789abcdef")),
+                name: name.to_owned(),
+            },
+            attributes,
+            data: data.to_vec(),
+        }
+    }
+
+    fn snapshot(vars: &[SnapshotVariable]) -> VariableSnapshot {
+        let mut snapshot = VariableSnapshot::new();
+        for var in vars {
+            assert!(snapshot.insert(var.clone()).is_none());
+        }
+        snapshot
+    }
+
+    const NV_BS: VariableAttributes =
+        VariableAttributes::NON_VOLATILE.union(VariableAttributes::BOOTSERVICE_ACCESS);
+
+    #[test]
+    fn test_to_bytes() {
+        let snapshot = snapshot(&[var(cstr16!("A"), NV_BS, &[1, 2])]);
+        assert_eq!(
+            snapshot.to_bytes(),
+            [
+                b'U', b'E', b'F', b'I', b'V', b'A', b'R', b'S', // Magic.
+                1, 0, 0, 0, // Version.
+                1, 0, 0, 0, // Count.
+                0x67, 0x45, 0x23, 0x01, 0xab, 0x89, 0xef, 0xcd, // Vendor.
+                0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, //
+                3, 0, 0, 0, // Attributes.
+                4, 0, 0, 0, // Name size.
+                2, 0, 0, 0, // Data size.
+                b'A', 0, 0, 0, // Name.
+                1, 2, // Data.
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let snapshot = snapshot(&[
+            var(cstr16!("Zeta"), NV_BS, b"z"),
+            var(
+                cstr16!("Alpha"),
+                VariableAttributes::BOOTSERVICE_ACCESS,
+                &[],
+            ),
+            var(cstr16!("Boot0001"), NV_BS, &[0xff; 100]),
+        ]);
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(snapshot.variables()[0].key.name, cstr16!("Alpha"));
+        let bytes = snapshot.to_bytes();
+        assert_eq!(VariableSnapshot::from_bytes(&bytes).unwrap(), snapshot);
+
+        // Variables written by other tools may be in any order.
+        let alpha_size = VARIABLE_HEADER_SIZE + 12;
+        let mut reordered = bytes[..HEADER_SIZE].to_vec();
+        reordered.extend_from_slice(&bytes[HEADER_SIZE + alpha_size..]);
+        reordered.extend_from_slice(&bytes[HEADER_SIZE..HEADER_SIZE + alpha_size]);
+        assert_ne!(reordered, bytes);
+        assert_eq!(VariableSnapshot::from_bytes(&reordered).unwrap(), snapshot);
+
+        let empty = VariableSnapshot::new();
+        assert_eq!(
+            VariableSnapshot::from_bytes(&empty.to_bytes()).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_errors() {
+        let snapshot = snapshot(&[var(cstr16!("A"), NV_BS, &[1, 2])]);
+        let bytes = snapshot.to_bytes();
+
+        let mut invalid = bytes.clone();
+        invalid[0] = b'X';
+        assert_eq!(
+            VariableSnapshot::from_bytes(&invalid),
+            Err(SnapshotParseError::InvalidMagic)
+        );
+
+        let mut invalid = bytes.clone();
+        invalid[8] = 2;
+        assert_eq!(
+            VariableSnapshot::from_bytes(&invalid),
+            Err(SnapshotParseError::UnsupportedVersion(2))
+        );
+
+        for len in 0..bytes.len() {
+            assert_eq!(
+                VariableSnapshot::from_bytes(&bytes[..len]),
+                Err(SnapshotParseError::TooShort)
+            );
+        }
+
+        let mut invalid = bytes.clone();
+        invalid.push(0);
+        assert_eq!(
+            VariableSnapshot::from_bytes(&invalid),
+            Err(SnapshotParseError::TrailingData)
+        );
+
+        // Name without a null terminator.
+        let mut invalid = bytes.clone();
+        invalid[46] = b'B';
+        assert_eq!(
+            VariableSnapshot::from_bytes(&invalid),
+            Err(SnapshotParseError::InvalidName)
+        );
+
+        // The same variable twice.
+        let mut invalid = bytes.clone();
+        invalid[12] = 2;
+        invalid.extend_from_slice(&bytes[HEADER_SIZE..]);
+        assert_eq!(
+            VariableSnapshot::from_bytes(&invalid),
+            Err(SnapshotParseError::DuplicateVariable)
+        );
+
+        // Huge count.
+        let mut invalid = bytes;
+        invalid[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            VariableSnapshot::from_bytes(&invalid),
+            Err(SnapshotParseError::TooShort)
+        );
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut snapshot = snapshot(&[var(cstr16!("A"), NV_BS, &[1])]);
+        let old = snapshot.insert(var(cstr16!("A"), NV_BS, &[2])).unwrap();
+        assert_eq!(old.data, [1]);
+        let key = old.key;
+        assert_eq!(snapshot.get(&key).unwrap().data, [2]);
+        assert_eq!(snapshot.remove(&key).unwrap().data, [2]);
+        assert!(snapshot.get(&key).is_none());
+        assert!(snapshot.is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = var(cstr16!("A"), NV_BS, &[1]);
+        let b = var(cstr16!("B"), NV_BS, &[2]);
+        let b2 = var(cstr16!("B"), NV_BS, &[3]);
+        let c = var(cstr16!("C"), NV_BS, &[4]);
+        let c2 = var(cstr16!("C"), VariableAttributes::BOOTSERVICE_ACCESS, &[4]);
+        let d = var(cstr16!("D"), NV_BS, &[5]);
+
+        let old = snapshot(&[a.clone(), b.clone(), c.clone(), d.clone()]);
+        let new = snapshot(&[b2.clone(), c2.clone(), d.clone()]);
+        let changes = old.diff(&new);
+        assert_eq!(
+            changes,
+            vec![
+                VariableChange::Removed(&a),
+                VariableChange::Modified { old: &b, new: &b2 },
+                VariableChange::Modified { old: &c, new: &c2 },
+            ]
+        );
+        assert_eq!(changes[1].key().name, cstr16!("B"));
+
+        assert_eq!(new.diff(&old)[0], VariableChange::Added(&a),);
+        assert!(old.diff(&old).is_empty());
+    }
+}
@@ -0,0 +1,676 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Conversion, arithmetic and formatting of [`Time`].
+
+use super::{Daylight, Time, TimeError, TimeParams};
+use core::cmp::Ordering;
+use core::fmt::{self, Display, Formatter};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::str::FromStr;
+use core::time::Duration;
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Days since 1970-01-01 of the first and last day representable by a
+/// [`Time`].
+const MIN_DAYS: i64 = days_from_civil(1900, 1, 1);
+const MAX_DAYS: i64 = days_from_civil(9999, 12, 31);
+
+/// Number of days since 1970-01-01 of a date in the proleptic Gregorian
+/// calendar. Days past the end of the month roll over into the next month.
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    // Months starting in March, so that the leap day is at the end.
+    let month = (month + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`], returning the year, month and day.
+const fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u8, day as u8)
+}
+
+impl Time {
+    /// 1970-01-01T00:00:00Z, the start of Unix time.
+    pub const UNIX_EPOCH: Self = Self(uefi_raw::time::Time {
+        year: 1970,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        pad1: 0,
+        nanosecond: 0,
+        time_zone: 0,
+        daylight: Daylight::empty(),
+        pad2: 0,
+    });
+
+    /// Create a UTC time from seconds and nanoseconds since the
+    /// [Unix epoch].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeError`] if the nanoseconds are not within
+    /// `0..=999_999_999`, or the year is not within `1900..=9999`.
+    ///
+    /// [Unix epoch]: Self::UNIX_EPOCH
+    pub fn from_unix_timestamp(seconds: i64, nanosecond: u32) -> Result<Self, TimeError> {
+        if nanosecond > 999_999_999 {
+            return Err(TimeError {
+                nanosecond: true,
+                ..Default::default()
+            });
+        }
+        let nanos = i128::from(seconds) * NANOS_PER_SEC + i128::from(nanosecond);
+        Self::from_unix_nanos(nanos)
+    }
+
+    /// Create a UTC time from nanoseconds since the [Unix epoch].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeError`] if the year is not within `1900..=9999`.
+    ///
+    /// [Unix epoch]: Self::UNIX_EPOCH
+    pub fn from_unix_nanos(nanos: i128) -> Result<Self, TimeError> {
+        Self::from_instant(nanos, Some(0), Daylight::empty())
+    }
+
+    /// Seconds since the [Unix epoch], rounded down. The nanoseconds are
+    /// returned by [`nanosecond`].
+    ///
+    /// The time is converted to UTC as described in [`to_utc`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeError`] if the time is not valid.
+    ///
+    /// [Unix epoch]: Self::UNIX_EPOCH
+    /// [`nanosecond`]: Self::nanosecond
+    /// [`to_utc`]: Self::to_utc
+    pub fn to_unix_timestamp(&self) -> Result<i64, TimeError> {
+        // The instant of a valid time fits into an `i64` of seconds.
+        Ok((self.to_unix_nanos()?.div_euclid(NANOS_PER_SEC)) as i64)
+    }
+
+    /// Nanoseconds since the [Unix epoch].
+    ///
+    /// The time is converted to UTC as described in [`to_utc`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeError`] if the time is not valid.
+    ///
+    /// [Unix epoch]: Self::UNIX_EPOCH
+    /// [`to_utc`]: Self::to_utc
+    pub fn to_unix_nanos(&self) -> Result<i128, TimeError> {
+        self.is_valid()?;
+        Ok(self.instant())
+    }
+
+    /// Convert the time to UTC, with a time zone of zero and no daylight
+    /// savings time information.
+    ///
+    /// [`Daylight::IN_DAYLIGHT`] means that the local time has been
+    /// adjusted by one hour for daylight savings time, in addition to the
+    /// time zone offset. A time without a time zone is in an unknown local
+    /// time, and is treated as UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TimeError`] if the time is not valid, or the year of the
+    /// UTC time is not within `1900..=9999`.
+    pub fn to_utc(&self) -> Result<Self, TimeError> {
+        Self::from_instant(self.to_unix_nanos()?, Some(0), Daylight::empty())
+    }
+
+    /// Add a duration, keeping the time zone and daylight savings time
+    /// information. Returns `None` if the time is not valid or the result
+    /// is not representable.
+    #[must_use]
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        let nanos = self
+            .to_unix_nanos()
+            .ok()?
+            .checked_add(i128::try_from(duration.as_nanos()).ok()?)?;
+        Self::from_instant(nanos, self.time_zone(), self.daylight()).ok()
+    }
+
+    /// Subtract a duration, keeping the time zone and daylight savings time
+    /// information. Returns `None` if the time is not valid or the result
+    /// is not representable.
+    #[must_use]
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        let nanos = self
+            .to_unix_nanos()
+            .ok()?
+            .checked_sub(i128::try_from(duration.as_nanos()).ok()?)?;
+        Self::from_instant(nanos, self.time_zone(), self.daylight()).ok()
+    }
+
+    /// The duration from `earlier` to this time. Returns `None` if either
+    /// time is not valid, or `earlier` is later than this time.
+    #[must_use]
+    pub fn duration_since(&self, earlier: &Self) -> Option<Duration> {
+        let nanos = self.to_unix_nanos().ok()? - earlier.to_unix_nanos().ok()?;
+        let nanos = u128::try_from(nanos).ok()?;
+        Some(Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        ))
+    }
+
+    /// Format the time as an [RFC 3339] timestamp, such as
+    /// `2024-01-02T03:04:05.5+01:00`. Times without a time zone are
+    /// formatted with an offset of `-00:00`, which means that the offset
+    /// to local time is unknown.
+    ///
+    /// The result can be parsed with [`str::parse`].
+    ///
+    /// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+    #[must_use]
+    pub const fn rfc3339(self) -> Rfc3339Display {
+        Rfc3339Display(self)
+    }
+
+    /// Offset of the local time from UTC in minutes, including daylight
+    /// savings time.
+    fn utc_offset(&self) -> Option<i64> {
+        let dst = if self.daylight().contains(Daylight::IN_DAYLIGHT) {
+            60
+        } else {
+            0
+        };
+        self.time_zone().map(|time_zone| i64::from(time_zone) + dst)
+    }
+
+    /// Nanoseconds since the Unix epoch. This does not overflow for
+    /// invalid times, but the result is meaningless.
+    fn instant(&self) -> i128 {
+        let days = days_from_civil(
+            i64::from(self.year()),
+            i64::from(self.month()),
+            i64::from(self.day()),
+        );
+        let seconds = days * SECS_PER_DAY
+            + i64::from(self.hour()) * 3600
+            + i64::from(self.minute()) * 60
+            + i64::from(self.second())
+            - self.utc_offset().unwrap_or(0) * 60;
+        i128::from(seconds) * NANOS_PER_SEC + i128::from(self.nanosecond())
+    }
+
+    /// Create a time from nanoseconds since the Unix epoch, in the given
+    /// time zone.
+    fn from_instant(
+        nanos: i128,
+        time_zone: Option<i16>,
+        daylight: Daylight,
+    ) -> Result<Self, TimeError> {
+        let mut time = Self(uefi_raw::time::Time {
+            time_zone: time_zone.unwrap_or(Self::UNSPECIFIED_TIMEZONE),
+            daylight,
+            ..uefi_raw::time::Time::invalid()
+        });
+        let local = nanos + i128::from(time.utc_offset().unwrap_or(0)) * 60 * NANOS_PER_SEC;
+        let seconds = local.div_euclid(NANOS_PER_SEC);
+        let days = seconds.div_euclid(SECS_PER_DAY.into());
+        if !(i128::from(MIN_DAYS)..=i128::from(MAX_DAYS)).contains(&days) {
+            return Err(TimeError {
+                year: true,
+                ..Default::default()
+            });
+        }
+
+        let (year, month, day) = civil_from_days(days as i64);
+        let second_of_day = seconds.rem_euclid(SECS_PER_DAY.into()) as u32;
+        time.0.year = year as u16;
+        time.0.month = month;
+        time.0.day = day;
+        time.0.hour = (second_of_day / 3600) as u8;
+        time.0.minute = (second_of_day / 60 % 60) as u8;
+        time.0.second = (second_of_day % 60) as u8;
+        time.0.nanosecond = local.rem_euclid(NANOS_PER_SEC) as u32;
+        time.is_valid().map(|_| time)
+    }
+}
+
+/// Times are ordered by the instant they represent, as returned by
+/// [`Time::to_unix_nanos`]. Times that represent the same instant in
+/// different time zones are ordered by their fields. Like [`PartialEq`],
+/// the ordering ignores the padding fields of the raw `EFI_TIME`.
+impl Ord for Time {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let fields = |time: &Self| {
+            (
+                time.year(),
+                time.month(),
+                time.day(),
+                time.hour(),
+                time.minute(),
+                time.second(),
+                time.nanosecond(),
+                time.0.time_zone,
+                time.daylight(),
+            )
+        };
+        self.instant()
+            .cmp(&other.instant())
+            .then_with(|| fields(self).cmp(&fields(other)))
+    }
+}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add<Duration> for Time {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if the time is not valid or the result is not representable.
+    /// See [`Time::checked_add`] for a version without panics.
+    fn add(self, duration: Duration) -> Self {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to time")
+    }
+}
+
+impl AddAssign<Duration> for Time {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Time {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if the time is not valid or the result is not representable.
+    /// See [`Time::checked_sub`] for a version without panics.
+    fn sub(self, duration: Duration) -> Self {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from time")
+    }
+}
+
+impl SubAssign<Duration> for Time {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+impl From<Time> for TimeParams {
+    fn from(time: Time) -> Self {
+        Self {
+            year: time.year(),
+            month: time.month(),
+            day: time.day(),
+            hour: time.hour(),
+            minute: time.minute(),
+            second: time.second(),
+            nanosecond: time.nanosecond(),
+            time_zone: time.time_zone(),
+            daylight: time.daylight(),
+        }
+    }
+}
+
+impl TryFrom<TimeParams> for Time {
+    type Error = TimeError;
+
+    fn try_from(params: TimeParams) -> Result<Self, TimeError> {
+        Self::new(params)
+    }
+}
+
+/// Formats a [`Time`] as an RFC 3339 timestamp. Created with
+/// [`Time::rfc3339`].
+#[derive(Clone, Copy, Debug)]
+pub struct Rfc3339Display(Time);
+
+impl Display for Rfc3339Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let time = &self.0;
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            time.year(),
+            time.month(),
+            time.day(),
+            time.hour(),
+            time.minute(),
+            time.second()
+        )?;
+
+        let mut nanosecond = time.nanosecond();
+        if nanosecond != 0 {
+            let mut width = 9;
+            while nanosecond % 10 == 0 {
+                nanosecond /= 10;
+                width -= 1;
+            }
+            write!(f, ".{nanosecond:0width$}")?;
+        }
+
+        match time.utc_offset() {
+            None => write!(f, "-00:00"),
+            Some(0) => write!(f, "Z"),
+            Some(offset) => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                write!(f, "{sign}{:02}:{:02}", offset / 60, offset % 60)
+            }
+        }
+    }
+}
+
+/// Error returned when parsing a [`Time`] from an RFC 3339 timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeParseError {
+    /// The string is not an RFC 3339 timestamp.
+    InvalidFormat,
+
+    /// One or more fields of the time are invalid.
+    InvalidFields(TimeError),
+}
+
+impl Display for TimeParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "the string is not an RFC 3339 timestamp"),
+            Self::InvalidFields(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl core::error::Error for TimeParseError {}
+
+/// Parser for the fields of an RFC 3339 timestamp.
+struct Parser<'a>(&'a [u8]);
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<u8> {
+        let (&first, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(first)
+    }
+
+    fn expect(&mut self, expected: &[u8]) -> Result<(), TimeParseError> {
+        match self.next() {
+            Some(c) if expected.contains(&c) => Ok(()),
+            _ => Err(TimeParseError::InvalidFormat),
+        }
+    }
+
+    fn digit(&mut self) -> Option<u32> {
+        let digit = char::from(*self.0.first()?).to_digit(10)?;
+        self.0 = &self.0[1..];
+        Some(digit)
+    }
+
+    fn number(&mut self, digits: usize) -> Result<u32, TimeParseError> {
+        (0..digits).try_fold(0, |n, _| {
+            let digit = self.digit().ok_or(TimeParseError::InvalidFormat)?;
+            Ok(n * 10 + digit)
+        })
+    }
+}
+
+/// Parses an [RFC 3339] timestamp, such as `2024-01-02T03:04:05.5+01:00`.
+///
+/// An offset of `-00:00` is parsed as a time without a time zone. Digits
+/// of the fractional second after the ninth are ignored. Leap seconds are
+/// not supported.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+impl FromStr for Time {
+    type Err = TimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser(s.as_bytes());
+        let year = parser.number(4)?;
+        parser.expect(b"-")?;
+        let month = parser.number(2)?;
+        parser.expect(b"-")?;
+        let day = parser.number(2)?;
+        parser.expect(b"Tt ")?;
+        let hour = parser.number(2)?;
+        parser.expect(b":")?;
+        let minute = parser.number(2)?;
+        parser.expect(b":")?;
+        let second = parser.number(2)?;
+
+        let mut nanosecond = 0;
+        if parser.0.first() == Some(&b'.') {
+            parser.next();
+            let mut digits = 0;
+            while let Some(digit) = parser.digit() {
+                if digits < 9 {
+                    nanosecond = nanosecond * 10 + digit;
+                }
+                digits += 1;
+            }
+            if digits == 0 {
+                return Err(TimeParseError::InvalidFormat);
+            }
+            nanosecond *= 10u32.pow(9u32.saturating_sub(digits));
+        }
+
+        let time_zone = match parser.next() {
+            Some(b'Z' | b'z') => Some(0),
+            Some(sign @ (b'+' | b'-')) => {
+                let hours = parser.number(2)?;
+                parser.expect(b":")?;
+                let minutes = parser.number(2)?;
+                let offset = (hours * 60 + minutes) as i16;
+                match sign {
+                    b'-' if offset == 0 => None,
+                    b'-' => Some(-offset),
+                    _ => Some(offset),
+                }
+            }
+            _ => return Err(TimeParseError::InvalidFormat),
+        };
+        if !parser.0.is_empty() {
+            return Err(TimeParseError::InvalidFormat);
+        }
+
+        Self::new(TimeParams {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+            time_zone,
+            daylight: Daylight::empty(),
+        })
+        .map_err(TimeParseError::InvalidFields)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn time(s: &str) -> Time {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(MIN_DAYS, -25_567);
+        for days in [MIN_DAYS, -1, 0, 59, 11_016, 11_017, MAX_DAYS] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(
+                days_from_civil(year, i64::from(month), i64::from(day)),
+                days
+            );
+        }
+        // Days past the end of the month roll over.
+        assert_eq!(days_from_civil(2023, 2, 29), days_from_civil(2023, 3, 1));
+    }
+
+    #[test]
+    fn test_unix_timestamp() {
+        assert_eq!(Time::UNIX_EPOCH.to_unix_timestamp(), Ok(0));
+        assert_eq!(Time::from_unix_timestamp(0, 0), Ok(Time::UNIX_EPOCH));
+
+        let t = time("2024-02-29T12:34:56.789Z");
+        assert_eq!(t.to_unix_timestamp(), Ok(1_709_210_096));
+        assert_eq!(t.to_unix_nanos(), Ok(1_709_210_096_789_000_000));
+        assert_eq!(Time::from_unix_timestamp(1_709_210_096, 789_000_000), Ok(t));
+
+        let t = time("1969-12-31T23:59:59.5Z");
+        assert_eq!(t.to_unix_timestamp(), Ok(-1));
+        assert_eq!(Time::from_unix_nanos(-500_000_000), Ok(t));
+
+        assert_eq!(time("1970-01-01T01:00:00+01:00").to_unix_timestamp(), Ok(0));
+        assert_eq!(time("1969-12-31T22:30:00-01:30").to_unix_timestamp(), Ok(0));
+
+        assert!(
+            Time::from_unix_timestamp(0, 1_000_000_000)
+                .unwrap_err()
+                .nanosecond
+        );
+        assert!(Time::from_unix_nanos(i128::MAX).unwrap_err().year);
+        assert!(
+            Time::from_unix_timestamp(-2_208_988_801, 0)
+                .unwrap_err()
+                .year
+        );
+        assert!(Time::invalid().to_unix_timestamp().is_err());
+    }
+
+    #[test]
+    fn test_utc() {
+        let t = time("2024-01-01T00:30:00+01:00");
+        assert_eq!(t.to_utc(), Ok(time("2023-12-31T23:30:00Z")));
+
+        // Daylight savings time adds an hour to the offset.
+        let mut params = TimeParams::from(t);
+        params.daylight = Daylight::ADJUST_DAYLIGHT | Daylight::IN_DAYLIGHT;
+        let dst = Time::try_from(params).unwrap();
+        assert_eq!(dst.to_utc(), Ok(time("2023-12-31T22:30:00Z")));
+        assert_eq!(dst.rfc3339().to_string(), "2024-01-01T00:30:00+02:00");
+
+        // Local time is treated as UTC.
+        let local = time("2024-01-01T00:30:00-00:00");
+        assert_eq!(local.time_zone(), None);
+        assert_eq!(local.to_utc(), Ok(time("2024-01-01T00:30:00Z")));
+
+        assert!(time("1900-01-01T00:00:00+00:01").to_utc().is_err());
+    }
+
+    #[test]
+    fn test_ord() {
+        let utc = time("2024-01-01T00:00:00Z");
+        let plus_one = time("2024-01-01T01:00:00+01:00");
+        assert_eq!(utc.to_unix_nanos(), plus_one.to_unix_nanos());
+        assert_ne!(utc, plus_one);
+        assert_ne!(utc.cmp(&plus_one), Ordering::Equal);
+        assert_eq!(utc.cmp(&utc), Ordering::Equal);
+
+        assert!(time("2024-01-01T00:30:00+01:00") < utc);
+        assert!(time("2024-01-01T00:30:00-01:00") > utc);
+        assert!(Time::invalid() < Time::UNIX_EPOCH);
+
+        // Padding is ignored by both `Eq` and `Ord`.
+        let mut padded = utc;
+        padded.0.pad1 = 1;
+        padded.0.pad2 = 2;
+        assert_eq!(utc, padded);
+        assert_eq!(utc.cmp(&padded), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_duration() {
+        let t = time("2024-02-28T23:00:00+05:30");
+        let later = t + Duration::from_secs(2 * 3600);
+        assert_eq!(later, time("2024-02-29T01:00:00+05:30"));
+        assert_eq!(later - Duration::from_secs(2 * 3600), t);
+        assert_eq!(later.duration_since(&t), Some(Duration::from_secs(7200)));
+        assert_eq!(t.duration_since(&later), None);
+
+        let mut t = time("2023-12-31T23:59:59.75Z");
+        t += Duration::from_millis(500);
+        assert_eq!(t, time("2024-01-01T00:00:00.25Z"));
+        t -= Duration::from_millis(250);
+        assert_eq!(t, time("2024-01-01T00:00:00Z"));
+
+        assert_eq!(
+            time("9999-12-31T23:59:59Z").checked_add(Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(Time::invalid().checked_sub(Duration::ZERO), None);
+        assert_eq!(Time::UNIX_EPOCH.checked_add(Duration::MAX), None);
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        for s in [
+            "2024-01-02T03:04:05Z",
+            "2024-01-02T03:04:05.5+01:00",
+            "2024-01-02T03:04:05.000000001-09:30",
+            "1900-01-01T00:00:00.123456789-00:00",
+        ] {
+            assert_eq!(time(s).rfc3339().to_string(), s);
+        }
+
+        let t = time("2024-01-02t03:04:05.1234567891z");
+        assert_eq!(t.nanosecond(), 123_456_789);
+        assert_eq!(t, time("2024-01-02 03:04:05.123456789Z"));
+
+        for s in [
+            "",
+            "2024-01-02",
+            "2024-01-02T03:04:05",
+            "2024-01-02T03:04:05.Z",
+            "2024-01-02T03:04:05+0100",
+            "2024-1-02T03:04:05Z",
+            "2024-01-02T03:04:05Z ",
+        ] {
+            assert_eq!(s.parse::<Time>(), Err(TimeParseError::InvalidFormat));
+        }
+        assert!(matches!(
+            "2024-13-02T03:04:60Z".parse::<Time>(),
+            Err(TimeParseError::InvalidFields(TimeError {
+                month: true,
+                second: true,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            "2024-01-02T03:04:05+25:00".parse::<Time>(),
+            Err(TimeParseError::InvalidFields(TimeError {
+                timezone: true,
+                ..
+            }))
+        ));
+    }
+}
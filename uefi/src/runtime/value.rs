@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Typed values of UEFI variables.
+
+use super::Time;
+use crate::Guid;
+
+#[cfg(feature = "alloc")]
+use {
+    crate::CString16,
+    crate::proto::device_path::DevicePath,
+    alloc::{boxed::Box, vec::Vec},
+};
+
+/// A value that can be stored in a UEFI variable.
+///
+/// This is implemented for integers, `bool`, byte arrays, [`Guid`] and
+/// [`Time`], and with the `alloc` feature for [`CString16`],
+/// `Box<DevicePath>` and `Vec<T>` of fixed size values. Values are stored
+/// in little-endian byte order, as required by the UEFI specification.
+///
+/// The trait can be derived for `#[repr(C)]` and `#[repr(C, packed)]`
+/// structs whose fields all have a fixed size. The derived implementation
+/// stores the struct with the same layout as in memory, with zeroed
+/// padding.
+///
+/// Use [`runtime::get`] and [`runtime::set`] to read and write variables
+/// with a typed value.
+///
+/// # Example
+///
+/// ```
+/// use uefi::runtime::VariableValue;
+///
+/// #[derive(Debug, PartialEq, VariableValue)]
+/// #[repr(C)]
+/// struct Settings {
+///     version: u8,
+///     timeout: u32,
+/// }
+///
+/// let settings = Settings { version: 1, timeout: 5 };
+/// let mut data = [0xff; 8];
+/// settings.encode(&mut data);
+/// assert_eq!(data, [1, 0, 0, 0, 5, 0, 0, 0]);
+/// assert_eq!(Settings::decode(&data), Some(settings));
+/// ```
+///
+/// [`CString16`]: crate::CString16
+/// [`runtime::get`]: super::get
+/// [`runtime::set`]: super::set
+pub trait VariableValue: Sized {
+    /// Size of the encoded value in bytes if all values of the type have
+    /// the same size, or `None` otherwise.
+    const SIZE: Option<usize> = None;
+
+    /// Size of the encoded value in bytes.
+    #[must_use]
+    fn encoded_len(&self) -> usize;
+
+    /// Encode the value into `out`, which is [`encoded_len`] bytes long.
+    ///
+    /// [`encoded_len`]: Self::encoded_len
+    fn encode(&self, out: &mut [u8]);
+
+    /// Decode a value from the data of a variable. Returns `None` if `data`
+    /// is not a valid encoding of a value.
+    #[must_use]
+    fn decode(data: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_variable_value_for_int {
+    ($($ty:ty),*) => {
+        $(
+            impl VariableValue for $ty {
+                const SIZE: Option<usize> = Some(size_of::<Self>());
+
+                fn encoded_len(&self) -> usize {
+                    size_of::<Self>()
+                }
+
+                fn encode(&self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(data: &[u8]) -> Option<Self> {
+                    Some(Self::from_le_bytes(data.try_into().ok()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_variable_value_for_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Stored as a single byte of `0` or `1`.
+impl VariableValue for bool {
+    const SIZE: Option<usize> = Some(1);
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        out[0] = u8::from(*self);
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        match data {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> VariableValue for [u8; N] {
+    const SIZE: Option<usize> = Some(N);
+
+    fn encoded_len(&self) -> usize {
+        N
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        out.copy_from_slice(self);
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        data.try_into().ok()
+    }
+}
+
+impl VariableValue for Guid {
+    const SIZE: Option<usize> = Some(size_of::<Self>());
+
+    fn encoded_len(&self) -> usize {
+        size_of::<Self>()
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.to_bytes());
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        Some(Self::from_bytes(data.try_into().ok()?))
+    }
+}
+
+/// Stored as an `EFI_TIME`. Decoding fails if the time is not valid.
+impl VariableValue for Time {
+    const SIZE: Option<usize> = Some(size_of::<Self>());
+
+    fn encoded_len(&self) -> usize {
+        size_of::<Self>()
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.to_bytes());
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() != size_of::<Self>() {
+            return None;
+        }
+        Self::try_from(data).ok()
+    }
+}
+
+/// Stored as a null-terminated UCS-2 string.
+#[cfg(feature = "alloc")]
+impl VariableValue for CString16 {
+    fn encoded_len(&self) -> usize {
+        self.num_bytes()
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        for (out, c) in out.chunks_exact_mut(2).zip(self.to_u16_slice_with_nul()) {
+            out.copy_from_slice(&c.to_le_bytes());
+        }
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() % 2 != 0 {
+            return None;
+        }
+        let chars = data
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect::<Vec<_>>();
+        Self::try_from(chars).ok()
+    }
+}
+
+/// Stored as a device path, including its end node.
+#[cfg(feature = "alloc")]
+impl VariableValue for Box<DevicePath> {
+    fn encoded_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.as_bytes());
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        let path = <&DevicePath>::try_from(data).ok()?;
+        (path.as_bytes().len() == data.len()).then(|| path.to_boxed())
+    }
+}
+
+/// Size of the elements of a `Vec<T>` value, checked at compile time to be
+/// fixed and not zero.
+#[cfg(feature = "alloc")]
+struct ElementSize<T>(core::marker::PhantomData<T>);
+
+#[cfg(feature = "alloc")]
+impl<T: VariableValue> ElementSize<T> {
+    const SIZE: usize = match T::SIZE {
+        Some(size) if size != 0 => size,
+        _ => panic!("the elements of a VariableValue vector must have a fixed, non-zero size"),
+    };
+}
+
+/// Stored as the concatenation of the values, which must have a fixed,
+/// non-zero size. For example, `BootOrder` is a `Vec<u16>`.
+///
+/// Using a vector of values without a fixed size fails to compile:
+///
+/// ```compile_fail
+/// use uefi::CString16;
+/// use uefi::runtime::VariableValue;
+///
+/// let names: Vec<CString16> = Vec::new();
+/// let _ = names.encoded_len();
+/// ```
+#[cfg(feature = "alloc")]
+impl<T: VariableValue> VariableValue for Vec<T> {
+    fn encoded_len(&self) -> usize {
+        self.len() * ElementSize::<T>::SIZE
+    }
+
+    fn encode(&self, out: &mut [u8]) {
+        for (value, out) in self
+            .iter()
+            .zip(out.chunks_exact_mut(ElementSize::<T>::SIZE))
+        {
+            value.encode(out);
+        }
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        let size = ElementSize::<T>::SIZE;
+        if data.len() % size != 0 {
+            return None;
+        }
+        data.chunks_exact(size).map(T::decode).collect()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::runtime::{Daylight, TimeParams, VariableValue};
+    use crate::{cstr16, guid};
+    use alloc::vec;
+
+    /// Encode `value` into a new buffer.
+    fn encode<T: VariableValue>(value: &T) -> Vec<u8> {
+        let mut data = vec![0; value.encoded_len()];
+        value.encode(&mut data);
+        data
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, VariableValue)]
+    #[repr(C)]
+    struct Padded {
+        a: u8,
+        b: u32,
+        c: [u8; 3],
+        d: u16,
+    }
+
+    #[derive(Debug, PartialEq, VariableValue)]
+    #[repr(C, packed)]
+    struct Packed {
+        a: u8,
+        b: u32,
+        padded: Padded,
+    }
+
+    #[derive(Debug, PartialEq, VariableValue)]
+    #[repr(C)]
+    struct Tuple(u16, bool, Guid);
+
+    #[test]
+    fn test_int_values() {
+        assert_eq!(encode(&0x1234_5678u32), [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(u32::decode(&[0x78, 0x56, 0x34, 0x12]), Some(0x1234_5678));
+        assert_eq!(u32::decode(&[0x78, 0x56, 0x34]), None);
+        assert_eq!(encode(&-2i16), [0xfe, 0xff]);
+        assert_eq!(bool::decode(&[1]), Some(true));
+        assert_eq!(bool::decode(&[2]), None);
+        assert_eq!(<Vec<u16>>::decode(&[1, 0, 2, 0]), Some(vec![1, 2]));
+        assert_eq!(<Vec<u16>>::decode(&[1, 0, 2]), None);
+    }
+
+    #[test]
+    fn test_string_values() {
+        let s = CString16::from(cstr16!("ab"));
+        assert_eq!(encode(&s), [b'a', 0, b'b', 0, 0, 0]);
+        assert_eq!(CString16::decode(&[b'a', 0, b'b', 0, 0, 0]), Some(s));
+        assert_eq!(CString16::decode(&[b'a', 0, b'b', 0]), None);
+
+        let path = <&DevicePath>::try_from([0x7f, 0xff, 0x04, 0x00].as_slice()).unwrap();
+        let path = path.to_boxed();
+        assert_eq!(<Box<DevicePath>>::decode(path.as_bytes()), Some(path));
+        assert_eq!(
+            <Box<DevicePath>>::decode(&[0x7f, 0xff, 0x04, 0x00, 0x00]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_time_value() {
+        let time = Time::new(TimeParams {
+            year: 2024,
+            month: 1,
+            day: 2,
+            hour: 3,
+            minute: 4,
+            second: 5,
+            nanosecond: 6,
+            time_zone: None,
+            daylight: Daylight::empty(),
+        })
+        .unwrap();
+        assert_eq!(Time::decode(&encode(&time)), Some(time));
+        assert_eq!(Time::decode(&[0; 16]), None);
+    }
+
+    #[test]
+    fn test_derived_values() {
+        let padded = Padded {
+            a: 1,
+            b: 2,
+            c: [3, 4, 5],
+            d: 6,
+        };
+        let data = encode(&padded);
+        assert_eq!(data, [1, 0, 0, 0, 2, 0, 0, 0, 3, 4, 5, 0, 6, 0, 0, 0]);
+        assert_eq!(Padded::decode(&data), Some(padded));
+        assert_eq!(Padded::decode(&data[1..]), None);
+        assert_eq!(Padded::SIZE, Some(16));
+
+        let packed = Packed {
+            a: 7,
+            b: 8,
+            padded: Padded::decode(&data).unwrap(),
+        };
+        let data = encode(&packed);
+        assert_eq!(data.len(), 21);
+        assert_eq!(data[..5], [7, 8, 0, 0, 0]);
+        assert_eq!(Packed::decode(&data), Some(packed));
+
+        let guid = guid!("01234567-89ab-cdef-0123-456789abcdef");
+        let tuple = Tuple(0x102, true, guid);
+        let data = encode(&tuple);
+        assert_eq!(data[..4], [2, 1, 1, 0]);
+        assert_eq!(data[4..], guid.to_bytes());
+        assert_eq!(Tuple::decode(&data), Some(tuple));
+    }
+}
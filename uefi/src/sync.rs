@@ -0,0 +1,442 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Synchronization primitives based on task priority levels.
+//!
+//! UEFI boot services run on a single processor without preemptive threads.
+//! The only form of concurrency is event notification functions, which
+//! interrupt the main flow of execution when an event is signaled, as long as
+//! the [task priority level][Tpl] of the notification is higher than the
+//! current one. State shared between notification functions and the rest of
+//! the program is protected by raising the TPL to the notification's level
+//! while accessing it, which prevents the notification from running.
+//!
+//! The types in this module do that automatically:
+//!
+//! * [`TplMutex`]: provides mutable access to its value through a guard.
+//! * [`TplCell`]: provides [`Cell`]-like access to its value.
+//! * [`OnceTpl`]: a value that is initialized once.
+//!
+//! They are [`Sync`], so they can be used in statics.
+//!
+//! # Choosing a TPL
+//!
+//! The TPL of a primitive must be at least the highest TPL that it is used
+//! at, usually the `notify_tpl` of the events whose notification functions
+//! use it. Using a primitive above its TPL would lower the TPL, which is not
+//! allowed.
+//!
+//! Only a limited set of boot services can be used while the TPL is raised.
+//! This is a summary of the TPL restrictions listed in the UEFI
+//! Specification:
+//!
+//! | Service                                         | Maximum TPL           |
+//! |-------------------------------------------------|-----------------------|
+//! | [`boot::wait_for_event`]                        | [`Tpl::APPLICATION`]  |
+//! | [`boot::exit_boot_services`]                    | [`Tpl::APPLICATION`]  |
+//! | Image services, such as [`boot::load_image`]    | [`Tpl::CALLBACK`]     |
+//! | [`boot::connect_controller`], [`boot::disconnect_controller`] | [`Tpl::CALLBACK`] |
+//! | [`boot::set_watchdog_timer`]                    | [`Tpl::CALLBACK`]     |
+//! | Variable services, such as [`runtime::get_variable`] | [`Tpl::CALLBACK`] |
+//! | Most protocols, such as console, block and file I/O | [`Tpl::CALLBACK`] |
+//! | Memory allocation services                      | [`Tpl::NOTIFY`]       |
+//! | Protocol handler services                       | [`Tpl::NOTIFY`]       |
+//! | Event services, such as [`boot::create_event`]  | [`Tpl::NOTIFY`]       |
+//! | [`boot::signal_event`], [`boot::stall`]         | [`Tpl::HIGH_LEVEL`]   |
+//!
+//! [`Tpl::HIGH_LEVEL`] also disables interrupts, so it should be avoided.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use uefi::boot::{self, EventType, Tpl};
+//! use uefi::sync::TplCell;
+//! use uefi::Event;
+//! use core::ffi::c_void;
+//! use core::ptr::NonNull;
+//!
+//! // SAFETY: the cell is only used at or below `Tpl::CALLBACK`.
+//! static TICKS: TplCell<u32> = unsafe { TplCell::new(Tpl::CALLBACK, 0) };
+//!
+//! extern "efiapi" fn on_tick(_event: Event, _context: Option<NonNull<c_void>>) {
+//!     TICKS.update(|ticks| ticks + 1);
+//! }
+//!
+//! # fn test() -> uefi::Result {
+//! let event = unsafe {
+//!     boot::create_event(EventType::TIMER | EventType::NOTIFY_SIGNAL, Tpl::CALLBACK, Some(on_tick), None)
+//! }?;
+//! boot::set_timer(&event, boot::TimerTrigger::Periodic(10_000_000))?;
+//! // ...
+//! log::info!("ticks: {}", TICKS.get());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Cell`]: core::cell::Cell
+//! [`runtime::get_variable`]: crate::runtime::get_variable
+
+use crate::boot::{self, Tpl, TplGuard};
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug, Formatter};
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+/// A mutual exclusion primitive that raises the TPL while locked.
+///
+/// Locking the mutex raises the TPL to the mutex's TPL, so that event
+/// notification functions at or below that TPL can't run until the returned
+/// guard is dropped. Since the code holding the lock can't be interrupted by
+/// other users of the mutex, locking never has to wait.
+///
+/// Locking the mutex again while it is locked, for example from a
+/// notification function above the mutex's TPL, is an error. [`lock`]
+/// panics in that case, [`try_lock`] returns `None`.
+///
+/// See the [module documentation][self] for how to choose the TPL.
+///
+/// [`lock`]: Self::lock
+/// [`try_lock`]: Self::try_lock
+pub struct TplMutex<T: ?Sized> {
+    tpl: Tpl,
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: access to the value is synchronized by the `locked` flag.
+unsafe impl<T: ?Sized + Send> Send for TplMutex<T> {}
+unsafe impl<T: ?Sized + Send> Sync for TplMutex<T> {}
+
+impl<T> TplMutex<T> {
+    /// Create a mutex that raises the TPL to `tpl` while locked.
+    ///
+    /// # Safety
+    ///
+    /// The mutex must not be locked while the TPL is above `tpl`.
+    #[must_use]
+    pub const unsafe fn new(tpl: Tpl, value: T) -> Self {
+        Self {
+            tpl,
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consume the mutex and return its value.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> TplMutex<T> {
+    /// The TPL that the mutex raises to while locked.
+    #[must_use]
+    pub const fn tpl(&self) -> Tpl {
+        self.tpl
+    }
+
+    /// Lock the mutex.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is already locked.
+    pub fn lock(&self) -> TplMutexGuard<'_, T> {
+        self.try_lock()
+            .expect("TplMutex is already locked at a lower TPL")
+    }
+
+    /// Lock the mutex, or return `None` if it is already locked.
+    pub fn try_lock(&self) -> Option<TplMutexGuard<'_, T>> {
+        // SAFETY: the TPL is not above `self.tpl`, per the requirements of
+        // `new`.
+        let tpl_guard = unsafe { boot::raise_tpl(self.tpl) };
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(TplMutexGuard {
+            mutex: self,
+            _tpl_guard: tpl_guard,
+        })
+    }
+
+    /// Get a mutable reference to the value. No locking is needed, since the
+    /// mutex is borrowed mutably.
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for TplMutex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("TplMutex");
+        d.field("tpl", &self.tpl);
+        match self.try_lock() {
+            Some(guard) => d.field("value", &&*guard),
+            None => d.field("value", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+/// Guard returned by [`TplMutex::lock`].
+///
+/// The mutex is unlocked and the TPL restored when the guard is dropped.
+pub struct TplMutexGuard<'a, T: ?Sized> {
+    mutex: &'a TplMutex<T>,
+    // Dropped after the mutex is unlocked.
+    _tpl_guard: TplGuard,
+}
+
+impl<T: ?Sized> Deref for TplMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the mutex is locked.
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for TplMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the mutex is locked.
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for TplMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized> Drop for TplMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.locked.store(false, Ordering::Release);
+    }
+}
+
+/// A mutable memory location that raises the TPL while accessed.
+///
+/// This is like [`Cell`], but it can be shared with event notification
+/// functions. Values are copied in and out of the cell while the TPL is
+/// raised to the cell's TPL, so no references to the value are handed out.
+///
+/// See the [module documentation][self] for an example and how to choose the
+/// TPL.
+///
+/// [`Cell`]: core::cell::Cell
+pub struct TplCell<T> {
+    mutex: TplMutex<T>,
+}
+
+impl<T> TplCell<T> {
+    /// Create a cell that raises the TPL to `tpl` while accessed.
+    ///
+    /// # Safety
+    ///
+    /// The cell must not be accessed while the TPL is above `tpl`.
+    #[must_use]
+    pub const unsafe fn new(tpl: Tpl, value: T) -> Self {
+        Self {
+            mutex: unsafe { TplMutex::new(tpl, value) },
+        }
+    }
+
+    /// The TPL that the cell raises to while accessed.
+    #[must_use]
+    pub const fn tpl(&self) -> Tpl {
+        self.mutex.tpl
+    }
+
+    /// Set the value. The old value is dropped after restoring the TPL.
+    pub fn set(&self, value: T) {
+        drop(self.replace(value));
+    }
+
+    /// Replace the value, returning the old value.
+    pub fn replace(&self, value: T) -> T {
+        mem::replace(&mut *self.mutex.lock(), value)
+    }
+
+    /// Get a mutable reference to the value. No TPL change is needed, since
+    /// the cell is borrowed mutably.
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.mutex.get_mut()
+    }
+
+    /// Consume the cell and return its value.
+    pub fn into_inner(self) -> T {
+        self.mutex.into_inner()
+    }
+}
+
+impl<T: Copy> TplCell<T> {
+    /// Get a copy of the value.
+    pub fn get(&self) -> T {
+        *self.mutex.lock()
+    }
+
+    /// Update the value with `f`, and return the new value.
+    ///
+    /// `f` is called while the TPL is raised, so it should be short. It must
+    /// not access the cell.
+    pub fn update(&self, f: impl FnOnce(T) -> T) -> T {
+        let mut value = self.mutex.lock();
+        *value = f(*value);
+        *value
+    }
+}
+
+impl<T: Default> TplCell<T> {
+    /// Take the value, leaving `T::default()` in its place.
+    pub fn take(&self) -> T {
+        self.replace(T::default())
+    }
+}
+
+impl<T: Copy + Debug> Debug for TplCell<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TplCell")
+            .field("tpl", &self.tpl())
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+const UNINIT: u8 = 0;
+const BUSY: u8 = 1;
+const INIT: u8 = 2;
+
+/// A value that is initialized once, raising the TPL while initializing.
+///
+/// This is like [`OnceCell`], but it can be shared with event notification
+/// functions. The initialization function passed to [`get_or_init`] runs
+/// while the TPL is raised to the cell's TPL, so it can only use the boot
+/// services that are allowed at that TPL.
+///
+/// See the [module documentation][self] for how to choose the TPL.
+///
+/// [`OnceCell`]: core::cell::OnceCell
+/// [`get_or_init`]: Self::get_or_init
+pub struct OnceTpl<T> {
+    tpl: Tpl,
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// SAFETY: the value is only written once while the state is `BUSY`, and only
+// read once the state is `INIT`.
+unsafe impl<T: Send> Send for OnceTpl<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceTpl<T> {}
+
+impl<T> OnceTpl<T> {
+    /// Create an uninitialized cell that raises the TPL to `tpl` while
+    /// initializing.
+    ///
+    /// # Safety
+    ///
+    /// The cell must not be initialized while the TPL is above `tpl`.
+    #[must_use]
+    pub const unsafe fn new(tpl: Tpl) -> Self {
+        Self {
+            tpl,
+            state: AtomicU8::new(UNINIT),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// The TPL that the cell raises to while initializing.
+    #[must_use]
+    pub const fn tpl(&self) -> Tpl {
+        self.tpl
+    }
+
+    /// Get the value, or `None` if the cell is not initialized yet.
+    pub fn get(&self) -> Option<&T> {
+        if self.state.load(Ordering::Acquire) == INIT {
+            // SAFETY: the value is initialized and never written again.
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Get the value, initializing it with `f` if the cell is not
+    /// initialized yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is accessed from `f`.
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        let mut f = Some(f);
+        self.initialize(|| f.take().unwrap()());
+        // OK to unwrap: the cell was just initialized.
+        self.get().unwrap()
+    }
+
+    /// Set the value if the cell is not initialized yet. Otherwise, `value`
+    /// is returned as the error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is being initialized.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+        self.initialize(|| value.take().unwrap());
+        value.map_or(Ok(()), Err)
+    }
+
+    /// Get a mutable reference to the value, or `None` if the cell is not
+    /// initialized yet.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if *self.state.get_mut() == INIT {
+            // SAFETY: the value is initialized.
+            Some(unsafe { self.value.get_mut().assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Initialize the value with `f` if the cell is not initialized yet.
+    fn initialize(&self, f: impl FnOnce() -> T) {
+        if self.state.load(Ordering::Acquire) == INIT {
+            return;
+        }
+
+        // SAFETY: the TPL is not above `self.tpl`, per the requirements of
+        // `new`.
+        let _tpl_guard = unsafe { boot::raise_tpl(self.tpl) };
+        match self
+            .state
+            .compare_exchange(UNINIT, BUSY, Ordering::Acquire, Ordering::Acquire)
+        {
+            Ok(_) => {}
+            Err(INIT) => return,
+            Err(_) => panic!("OnceTpl is already being initialized"),
+        }
+
+        // If `f` panics, the cell stays `BUSY`, since panics don't unwind.
+        let value = f();
+        // SAFETY: the state is `BUSY`, so nothing else accesses the value.
+        unsafe { (*self.value.get()).write(value) };
+        self.state.store(INIT, Ordering::Release);
+    }
+}
+
+impl<T: Debug> Debug for OnceTpl<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnceTpl")
+            .field("tpl", &self.tpl)
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+impl<T> Drop for OnceTpl<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == INIT {
+            // SAFETY: the value is initialized.
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
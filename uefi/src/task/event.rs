@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Futures for UEFI events, and the reactor that wakes them.
+
+use crate::{Event, Result, boot};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use core::task::{Context, Poll, Waker};
+
+/// The reactor of the executor that is currently running, or null.
+static CURRENT_REACTOR: AtomicPtr<Reactor> = AtomicPtr::new(ptr::null_mut());
+
+/// A task waiting for an event to be signaled.
+#[derive(Debug)]
+struct Registration {
+    event: Event,
+    waker: Waker,
+}
+
+/// Keeps track of the events that tasks are waiting for.
+///
+/// While an executor runs, its reactor is made available to the futures it
+/// polls through [`CURRENT_REACTOR`]. When no task is ready, the executor
+/// waits for any of the registered events and wakes the tasks waiting for
+/// the event that was signaled.
+#[derive(Debug, Default)]
+pub(super) struct Reactor {
+    registrations: RefCell<Vec<Registration>>,
+}
+
+impl Reactor {
+    /// Make this the current reactor until the returned guard is dropped.
+    pub(super) fn enter(&self) -> ReactorGuard {
+        let prev = CURRENT_REACTOR.swap(ptr::from_ref(self).cast_mut(), Ordering::AcqRel);
+        ReactorGuard { prev }
+    }
+
+    /// Call `f` with the current reactor, if any.
+    fn with_current<R>(f: impl FnOnce(&Self) -> R) -> Option<R> {
+        let reactor = CURRENT_REACTOR.load(Ordering::Acquire);
+        // SAFETY: the pointer is only set while the reactor is alive, see
+        // `Reactor::enter`.
+        unsafe { reactor.as_ref() }.map(f)
+    }
+
+    /// Get clones of all registered events, without duplicates.
+    pub(super) fn events(&self) -> Vec<Event> {
+        let mut events: Vec<Event> = Vec::new();
+        for reg in self.registrations.borrow().iter() {
+            if !events.contains(&reg.event) {
+                // SAFETY: registrations are removed before their event is
+                // closed, see `EventWaiter`'s `Drop` impl.
+                events.push(unsafe { reg.event.unsafe_clone() });
+            }
+        }
+        events
+    }
+
+    /// Wake and remove all tasks waiting for `event`.
+    pub(super) fn wake(&self, event: &Event) {
+        let mut woken = Vec::new();
+        self.registrations.borrow_mut().retain(|reg| {
+            if reg.event == *event {
+                woken.push(reg.waker.clone());
+                false
+            } else {
+                true
+            }
+        });
+        // Wake outside of the borrow in case a waker touches the reactor.
+        woken.into_iter().for_each(Waker::wake);
+    }
+
+    fn register(&self, event: &Event, waker: &Waker) {
+        let mut registrations = self.registrations.borrow_mut();
+        let exists = registrations
+            .iter()
+            .any(|reg| reg.event == *event && reg.waker.will_wake(waker));
+        if !exists {
+            registrations.push(Registration {
+                // SAFETY: the registration is removed before the event is
+                // closed, see `EventWaiter`'s `Drop` impl.
+                event: unsafe { event.unsafe_clone() },
+                waker: waker.clone(),
+            });
+        }
+    }
+
+    fn deregister(&self, event: &Event, waker: &Waker) {
+        self.registrations
+            .borrow_mut()
+            .retain(|reg| !(reg.event == *event && reg.waker.will_wake(waker)));
+    }
+}
+
+/// Restores the previously-current reactor when dropped.
+#[derive(Debug)]
+pub(super) struct ReactorGuard {
+    prev: *mut Reactor,
+}
+
+impl Drop for ReactorGuard {
+    fn drop(&mut self) {
+        CURRENT_REACTOR.store(self.prev, Ordering::Release);
+    }
+}
+
+/// Shared implementation of the futures that wait for an event.
+///
+/// The event must outlive the waiter.
+#[derive(Debug)]
+pub(super) struct EventWaiter {
+    /// Clone of the event being waited for.
+    event: Event,
+    /// Waker registered with the current reactor, if any.
+    registered: Option<Waker>,
+}
+
+impl EventWaiter {
+    /// # Safety
+    ///
+    /// The caller must ensure that the waiter is dropped before `event` is
+    /// closed.
+    pub(super) const unsafe fn new(event: &Event) -> Self {
+        Self {
+            event: unsafe { event.unsafe_clone() },
+            registered: None,
+        }
+    }
+
+    /// Poll the event. Returns `Poll::Ready` once the event is signaled, and
+    /// clears the signaled state.
+    pub(super) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Result> {
+        // SAFETY: the event is valid per the requirements of `new`.
+        let event = unsafe { self.event.unsafe_clone() };
+        match boot::check_event(event) {
+            Ok(true) => {
+                self.deregister();
+                Poll::Ready(Ok(()))
+            }
+            Ok(false) => {
+                let registered = Reactor::with_current(|reactor| {
+                    if let Some(old) = self.registered.take() {
+                        if !old.will_wake(cx.waker()) {
+                            reactor.deregister(&self.event, &old);
+                        }
+                    }
+                    reactor.register(&self.event, cx.waker());
+                })
+                .is_some();
+
+                if registered {
+                    self.registered = Some(cx.waker().clone());
+                } else {
+                    // Not running on a `uefi` executor, so nobody will wait
+                    // for the event. Ask to be polled again.
+                    cx.waker().wake_by_ref();
+                }
+                Poll::Pending
+            }
+            Err(err) => {
+                self.deregister();
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+
+    fn deregister(&mut self) {
+        if let Some(waker) = self.registered.take() {
+            Reactor::with_current(|reactor| reactor.deregister(&self.event, &waker));
+        }
+    }
+}
+
+impl Drop for EventWaiter {
+    fn drop(&mut self) {
+        self.deregister();
+    }
+}
+
+/// Future returned by [`wait_for_event`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct WaitForEvent<'a> {
+    waiter: EventWaiter,
+    _event: &'a Event,
+}
+
+/// Waits for `event` to be signaled.
+///
+/// The future completes once [`boot::check_event`] reports that the event is
+/// signaled, which also clears the signaled state. If several futures wait
+/// for the same event, only one of them is guaranteed to complete each time
+/// the event is signaled.
+///
+/// The event must not be of type [`NOTIFY_SIGNAL`]; such events cannot be
+/// waited for and the future will complete with
+/// [`Status::INVALID_PARAMETER`].
+///
+/// [`NOTIFY_SIGNAL`]: crate::boot::EventType::NOTIFY_SIGNAL
+/// [`Status::INVALID_PARAMETER`]: crate::Status::INVALID_PARAMETER
+pub const fn wait_for_event(event: &Event) -> WaitForEvent<'_> {
+    WaitForEvent {
+        // SAFETY: the waiter cannot outlive the `event` borrow.
+        waiter: unsafe { EventWaiter::new(event) },
+        _event: event,
+    }
+}
+
+impl Future for WaitForEvent<'_> {
+    type Output = Result;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        self.waiter.poll(cx)
+    }
+}
@@ -0,0 +1,279 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Single-threaded executor.
+
+use super::event::Reactor;
+use crate::boot::{self, EventType, OwnedEvent, Tpl};
+use crate::{Event, Result, ResultExt};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::ffi::c_void;
+use core::fmt::{self, Debug, Formatter};
+use core::future::Future;
+use core::pin::{Pin, pin};
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use core::task::{Context, Poll, Waker};
+
+type BoxedTask = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Event signaled whenever a task is woken, so that the executor stops
+/// waiting for other events.
+///
+/// The pointer is cleared when the executor is dropped, since wakers may
+/// outlive it.
+#[derive(Debug)]
+struct WakeEvent(AtomicPtr<c_void>);
+
+impl WakeEvent {
+    fn signal(&self) {
+        let ptr = self.0.load(Ordering::Acquire);
+        // SAFETY: the pointer is either null or a valid event, see
+        // `Executor`'s `Drop` impl.
+        if let Some(event) = unsafe { Event::from_ptr(ptr) } {
+            // Signaling an event can be done at any TPL. There are no
+            // documented errors.
+            let _ = boot::signal_event(&event);
+        }
+    }
+}
+
+/// Waker for a single task.
+#[derive(Debug)]
+struct TaskWaker {
+    woken: AtomicBool,
+    wake_event: Arc<WakeEvent>,
+}
+
+impl TaskWaker {
+    fn new(wake_event: &Arc<WakeEvent>) -> Arc<Self> {
+        Arc::new(Self {
+            // Tasks are polled once when they are first run.
+            woken: AtomicBool::new(true),
+            wake_event: wake_event.clone(),
+        })
+    }
+
+    fn is_woken(&self) -> bool {
+        self.woken.load(Ordering::Acquire)
+    }
+
+    fn take_woken(&self) -> bool {
+        self.woken.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        self.wake_event.signal();
+    }
+}
+
+struct Task {
+    future: BoxedTask,
+    waker: Arc<TaskWaker>,
+}
+
+/// Handle for spawning tasks onto an [`Executor`], including from within
+/// tasks running on it.
+///
+/// Created with [`Executor::spawner`].
+#[derive(Clone)]
+pub struct Spawner {
+    queue: Rc<RefCell<Vec<BoxedTask>>>,
+}
+
+impl Spawner {
+    /// Spawns a task. The task will start running the next time the
+    /// executor polls its tasks.
+    pub fn spawn(&self, future: impl Future<Output = ()> + 'static) {
+        self.queue.borrow_mut().push(Box::pin(future));
+    }
+}
+
+impl Debug for Spawner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Spawner")
+            .field("queued", &self.queue.borrow().len())
+            .finish()
+    }
+}
+
+/// A single-threaded executor for futures, driven by UEFI events.
+///
+/// Tasks are added with [`spawn`] and run with [`run`] or [`block_on`].
+/// While no task can make progress, the executor waits for the events that
+/// its tasks are waiting for using [`boot::wait_for_event`].
+///
+/// See the [module documentation][super] for an example.
+///
+/// [`spawn`]: Self::spawn
+/// [`run`]: Self::run
+/// [`block_on`]: Self::block_on
+pub struct Executor {
+    tasks: Vec<Task>,
+    spawner: Spawner,
+    reactor: Box<Reactor>,
+    wake_event_ptr: Arc<WakeEvent>,
+    wake_event: OwnedEvent,
+}
+
+impl Executor {
+    /// Creates an executor with no tasks.
+    ///
+    /// # Errors
+    ///
+    /// * [`Status::OUT_OF_RESOURCES`]: the executor's wake event could not be
+    ///   allocated.
+    ///
+    /// [`Status::OUT_OF_RESOURCES`]: crate::Status::OUT_OF_RESOURCES
+    pub fn new() -> Result<Self> {
+        // SAFETY: the event has no notification function.
+        let wake_event =
+            unsafe { boot::create_event(EventType::empty(), Tpl::CALLBACK, None, None) }?;
+        // SAFETY: the event was just created and is only closed on drop.
+        let wake_event = unsafe { OwnedEvent::from_event(wake_event) };
+
+        Ok(Self {
+            tasks: Vec::new(),
+            spawner: Spawner {
+                queue: Rc::default(),
+            },
+            reactor: Box::default(),
+            wake_event_ptr: Arc::new(WakeEvent(AtomicPtr::new(wake_event.as_ptr()))),
+            wake_event,
+        })
+    }
+
+    /// Returns a [`Spawner`] that can be used to spawn tasks onto this
+    /// executor.
+    #[must_use]
+    pub fn spawner(&self) -> Spawner {
+        self.spawner.clone()
+    }
+
+    /// Spawns a task. The task will start running the next time the
+    /// executor polls its tasks.
+    pub fn spawn(&self, future: impl Future<Output = ()> + 'static) {
+        self.spawner.spawn(future);
+    }
+
+    /// Runs all tasks until they have completed.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if waiting for events fails. See
+    /// [`boot::wait_for_event`] for details.
+    pub fn run(&mut self) -> Result {
+        let _reactor = self.reactor.enter();
+
+        loop {
+            self.poll_tasks();
+            if self.tasks.is_empty() {
+                return Ok(());
+            }
+            if !self.is_any_task_ready() {
+                self.wait()?;
+            }
+        }
+    }
+
+    /// Runs `future` to completion and returns its output. Spawned tasks
+    /// are run concurrently, but may not have completed when this function
+    /// returns.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if waiting for events fails. See
+    /// [`boot::wait_for_event`] for details.
+    pub fn block_on<F: Future>(&mut self, future: F) -> Result<F::Output> {
+        let _reactor = self.reactor.enter();
+
+        let mut future = pin!(future);
+        let task_waker = TaskWaker::new(&self.wake_event_ptr);
+        let waker = Waker::from(task_waker.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if task_waker.take_woken() {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return Ok(output);
+                }
+            }
+            self.poll_tasks();
+            if !task_waker.is_woken() && !self.is_any_task_ready() {
+                self.wait()?;
+            }
+        }
+    }
+
+    /// Poll every task that has been woken, and remove completed tasks.
+    fn poll_tasks(&mut self) {
+        let spawned: Vec<_> = self.spawner.queue.borrow_mut().drain(..).collect();
+        self.tasks.extend(spawned.into_iter().map(|future| Task {
+            future,
+            waker: TaskWaker::new(&self.wake_event_ptr),
+        }));
+
+        self.tasks.retain_mut(|task| {
+            if !task.waker.take_woken() {
+                return true;
+            }
+            let waker = Waker::from(task.waker.clone());
+            let mut cx = Context::from_waker(&waker);
+            task.future.as_mut().poll(&mut cx).is_pending()
+        });
+    }
+
+    fn is_any_task_ready(&self) -> bool {
+        !self.spawner.queue.borrow().is_empty() || self.tasks.iter().any(|t| t.waker.is_woken())
+    }
+
+    /// Wait until a task is woken, or until one of the events registered with
+    /// the reactor is signaled.
+    fn wait(&self) -> Result {
+        // SAFETY: the wake event is owned by `self`.
+        let mut events = Vec::from([unsafe { self.wake_event.unsafe_clone() }]);
+        events.extend(self.reactor.events());
+
+        let index = boot::wait_for_event(&mut events).discard_errdata()?;
+        if index != 0 {
+            // `wait_for_event` cleared the signaled state. Signal the event
+            // again so that the woken future can observe it with
+            // `check_event`.
+            boot::signal_event(&events[index])?;
+            self.reactor.wake(&events[index]);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Executor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Executor")
+            .field("tasks", &self.tasks.len())
+            .field("spawner", &self.spawner)
+            .field("reactor", &self.reactor)
+            .field("wake_event", &self.wake_event)
+            .finish()
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        // Wakers may outlive the executor, so make sure they no longer
+        // signal the wake event once it has been closed.
+        self.wake_event_ptr
+            .0
+            .store(ptr::null_mut(), Ordering::Release);
+    }
+}
@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Futures for console input.
+
+use super::event::wait_for_event;
+use crate::proto::console::pointer::{Pointer, PointerState};
+use crate::proto::console::text::{Input, Key};
+use crate::{Result, Status};
+
+/// Waits for the next keystroke from `input`.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: there was an issue with the input device.
+/// * [`Status::UNSUPPORTED`]: the protocol does not provide a key event.
+pub async fn read_key(input: &mut Input) -> Result<Key> {
+    loop {
+        if let Some(key) = input.read_key()? {
+            return Ok(key);
+        }
+
+        // The event is owned by the protocol, and `input` is borrowed for the
+        // whole duration of the wait.
+        let event = input.wait_for_key_event().ok_or(Status::UNSUPPORTED)?;
+        wait_for_event(&event).await?;
+    }
+}
+
+/// Waits for the next state change of `pointer`.
+///
+/// # Errors
+///
+/// * [`Status::DEVICE_ERROR`]: there was an issue with the pointer device.
+/// * [`Status::UNSUPPORTED`]: the protocol does not provide an input event.
+pub async fn read_pointer(pointer: &mut Pointer) -> Result<PointerState> {
+    loop {
+        if let Some(state) = pointer.read_state()? {
+            return Ok(state);
+        }
+
+        // The event is owned by the protocol, and `pointer` is borrowed for
+        // the whole duration of the wait.
+        let event = pointer.wait_for_input_event().ok_or(Status::UNSUPPORTED)?;
+        wait_for_event(&event).await?;
+    }
+}
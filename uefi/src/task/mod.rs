@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Async tasks driven by UEFI events.
+//!
+//! This module provides a single-threaded [`Executor`] for running Rust
+//! [`Future`]s, along with futures that complete when a UEFI [`Event`] is
+//! signaled. The executor blocks in [`boot::wait_for_event`] while no task can
+//! make progress, so idle tasks don't busy-wait.
+//!
+//! Futures provided by this module:
+//!
+//! * [`wait_for_event`]: wait for an arbitrary [`Event`] to be signaled.
+//! * [`sleep`] and [`interval`]: one-shot and periodic timers.
+//! * [`read_key`]: wait for a keystroke from an [`Input`] protocol.
+//! * [`read_pointer`]: wait for input from a [`Pointer`] protocol.
+//!
+//! These futures can be combined using the regular `async`/`await` syntax.
+//! The futures that wait for events also work with other executors, but
+//! fall back to busy-polling there.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use core::time::Duration;
+//! use uefi::proto::console::text::Input;
+//! use uefi::task::{self, Executor};
+//! use uefi::{boot, Result};
+//!
+//! # fn test() -> Result {
+//! let mut executor = Executor::new()?;
+//!
+//! // Run a countdown in the background.
+//! executor.spawn(async {
+//!     for i in (1..=3).rev() {
+//!         log::info!("{i}...");
+//!         task::sleep(Duration::from_secs(1)).unwrap().await.unwrap();
+//!     }
+//! });
+//!
+//! // Wait for a key press on stdin.
+//! let handle = boot::get_handle_for_protocol::<Input>()?;
+//! let mut input = boot::open_protocol_exclusive::<Input>(handle)?;
+//! let key = executor.block_on(task::read_key(&mut input))??;
+//! log::info!("pressed: {key:?}");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Task priority levels
+//!
+//! [`boot::wait_for_event`] may only be called at [`Tpl::APPLICATION`], so
+//! [`Executor::run`] and [`Executor::block_on`] must not be called from an
+//! event notification function or while the TPL is raised.
+//!
+//! [`boot::wait_for_event`]: crate::boot::wait_for_event
+//! [`Event`]: crate::Event
+//! [`Input`]: crate::proto::console::text::Input
+//! [`Pointer`]: crate::proto::console::pointer::Pointer
+//! [`Tpl::APPLICATION`]: crate::boot::Tpl::APPLICATION
+
+mod event;
+mod executor;
+mod input;
+mod time;
+
+pub use event::{WaitForEvent, wait_for_event};
+pub use executor::{Executor, Spawner};
+pub use input::{read_key, read_pointer};
+pub use time::{Interval, Sleep, Tick, interval, sleep};
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Timer futures.
+
+use super::event::EventWaiter;
+use crate::Result;
+use crate::boot::{self, EventType, OwnedEvent, TimerTrigger, Tpl};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+/// Create a timer event that is closed on drop.
+fn create_timer_event(trigger: TimerTrigger) -> Result<OwnedEvent> {
+    // SAFETY: the event has no notification function.
+    let event = unsafe { boot::create_event(EventType::TIMER, Tpl::CALLBACK, None, None) }?;
+    // SAFETY: the event was just created and is only closed on drop.
+    let event = unsafe { OwnedEvent::from_event(event) };
+    boot::set_timer(&event, trigger)?;
+    Ok(event)
+}
+
+/// Future returned by [`sleep`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Sleep {
+    // Must be dropped before the event it waits for.
+    waiter: EventWaiter,
+    _event: OwnedEvent,
+}
+
+/// Creates a future that completes after `duration` has elapsed.
+///
+/// The timer starts when this function is called, not when the future is
+/// first polled. The timer resolution is 100ns.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: the timer event could not be allocated.
+///
+/// [`Status::OUT_OF_RESOURCES`]: crate::Status::OUT_OF_RESOURCES
+pub fn sleep(duration: Duration) -> Result<Sleep> {
+    let event = create_timer_event(TimerTrigger::Relative(boot::duration_to_timer_units(
+        duration,
+    )))?;
+    Ok(Sleep {
+        // SAFETY: the waiter is dropped before the event.
+        waiter: unsafe { EventWaiter::new(&event) },
+        _event: event,
+    })
+}
+
+impl Future for Sleep {
+    type Output = Result;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        self.waiter.poll(cx)
+    }
+}
+
+/// A periodic timer created by [`interval`].
+#[derive(Debug)]
+pub struct Interval {
+    // Must be dropped before the event it waits for.
+    waiter: EventWaiter,
+    _event: OwnedEvent,
+}
+
+/// Creates a periodic timer that ticks every `period`.
+///
+/// The first tick happens one `period` after this function is called. If a
+/// tick is not awaited before the next one happens, the ticks are merged.
+///
+/// # Errors
+///
+/// * [`Status::OUT_OF_RESOURCES`]: the timer event could not be allocated.
+///
+/// [`Status::OUT_OF_RESOURCES`]: crate::Status::OUT_OF_RESOURCES
+pub fn interval(period: Duration) -> Result<Interval> {
+    let event = create_timer_event(TimerTrigger::Periodic(boot::duration_to_timer_units(
+        period,
+    )))?;
+    Ok(Interval {
+        // SAFETY: the waiter is dropped before the event.
+        waiter: unsafe { EventWaiter::new(&event) },
+        _event: event,
+    })
+}
+
+impl Interval {
+    /// Waits for the next tick.
+    pub const fn tick(&mut self) -> Tick<'_> {
+        Tick { interval: self }
+    }
+}
+
+/// Future returned by [`Interval::tick`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Tick<'a> {
+    interval: &'a mut Interval,
+}
+
+impl Future for Tick<'_> {
+    type Output = Result;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result> {
+        self.interval.waiter.poll(cx)
+    }
+}